@@ -9,6 +9,7 @@ use super::performance::{into::IntoPerformance, Performance};
 
 /// The result of a difficulty calculation based on the mode.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DifficultyAttributes {
     /// osu!standard difficulty calculation result.
     Osu(OsuDifficultyAttributes),
@@ -49,6 +50,7 @@ impl DifficultyAttributes {
 
 /// The result of a performance calculation based on the mode.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PerformanceAttributes {
     /// osu!standard performance calculation result.
     Osu(OsuPerformanceAttributes),
@@ -112,3 +114,18 @@ impl From<PerformanceAttributes> for DifficultyAttributes {
         attrs.difficulty_attributes()
     }
 }
+
+macro_rules! from_mode_attrs {
+    ( $mode:ident: $attrs:ident ) => {
+        impl From<$attrs> for DifficultyAttributes {
+            fn from(attrs: $attrs) -> Self {
+                Self::$mode(attrs)
+            }
+        }
+    };
+}
+
+from_mode_attrs!(Osu: OsuDifficultyAttributes);
+from_mode_attrs!(Taiko: TaikoDifficultyAttributes);
+from_mode_attrs!(Catch: CatchDifficultyAttributes);
+from_mode_attrs!(Mania: ManiaDifficultyAttributes);
@@ -4,6 +4,7 @@ use super::ModsDependent;
 
 /// [`Difficulty`] but all fields are public for inspection.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct InspectDifficulty {
     /// Specify mods.
     pub mods: GameMods,
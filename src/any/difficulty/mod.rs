@@ -58,6 +58,7 @@ pub struct Difficulty {
 
 /// Wrapper for beatmap attributes in [`Difficulty`].
 #[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ModsDependent {
     /// Value of the beatmap attribute.
     pub value: f32,
@@ -0,0 +1,52 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// The grade of a score.
+///
+/// See [`ScoreState::grade`](crate::any::ScoreState::grade).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Grade {
+    D,
+    C,
+    B,
+    A,
+    S,
+    /// Silver S i.e. S with `Hidden` or `Flashlight`.
+    SH,
+    X,
+    /// Silver SS i.e. SS with `Hidden` or `Flashlight`.
+    XH,
+}
+
+impl Grade {
+    /// Whether the grade is a silver S or silver SS.
+    pub const fn is_silver(self) -> bool {
+        matches!(self, Self::SH | Self::XH)
+    }
+
+    /// Turn S and SS into their silver counterparts.
+    pub(crate) const fn silver(self) -> Self {
+        match self {
+            Self::S => Self::SH,
+            Self::X => Self::XH,
+            _ => self,
+        }
+    }
+}
+
+impl Display for Grade {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let s = match self {
+            Self::D => "D",
+            Self::C => "C",
+            Self::B => "B",
+            Self::A => "A",
+            Self::S => "S",
+            Self::SH => "SH",
+            Self::X => "X",
+            Self::XH => "XH",
+        };
+
+        f.write_str(s)
+    }
+}
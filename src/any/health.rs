@@ -0,0 +1,760 @@
+use rosu_map::section::{events::BreakPeriod, general::GameMode};
+
+use crate::{
+    catch,
+    model::{hit_object::HitObjectKind, mode::ConvertError, mods::GameMods},
+    osu::{self, OsuHitResult},
+    util::difficulty::difficulty_range,
+    Beatmap,
+};
+
+use super::Difficulty;
+
+/// Health increase of a great hit on osu!lazer.
+const LAZER_MAX_HEALTH_INCREASE: f64 = 0.05;
+
+/// Simulates the health bar of a play based on the judgement of each
+/// hitobject.
+///
+/// Health drains over time outside of breaks and changes with every
+/// judgement. Depending on [`HealthSimulator::lazer`], the drain rate and
+/// health changes follow the rules of osu!stable or osu!lazer. osu!taiko does
+/// not drain; instead, the play fails if the health is below half at the end.
+///
+/// Nested objects such as slider ticks or droplets are considered hit unless
+/// their hitobject is a miss.
+///
+/// Mods only affect the HP value and the conversion; `NoFail`, `SuddenDeath`,
+/// and `Perfect` are not considered.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{
+///     any::{HealthSimulator, HitResult},
+///     Beatmap, Performance,
+/// };
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let mut results = vec![HitResult::Great; map.hit_objects.len()];
+/// results[100..150].fill(HitResult::Miss);
+///
+/// let simulation = HealthSimulator::new(&map)
+///     .mods(16) // HR
+///     .lazer(false)
+///     .simulate(&results)
+///     .unwrap();
+///
+/// if let Some(fail) = simulation.fail {
+///     let attrs = Performance::new(&map)
+///         .mods(16)
+///         .lazer(false)
+///         .passed_objects(fail.passed_objects as u32)
+///         .misses(fail.passed_objects.saturating_sub(100).min(50) as u32)
+///         .calculate();
+///
+///     println!("Failed at {}ms for {}pp", fail.time, attrs.pp());
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct HealthSimulator<'map> {
+    map: &'map Beatmap,
+    mode: GameMode,
+    difficulty: Difficulty,
+}
+
+impl<'map> HealthSimulator<'map> {
+    /// Create a new health simulation for the given map.
+    pub const fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mode: map.mode,
+            difficulty: Difficulty::new(),
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`] for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Convert the map to the given mode before simulating.
+    pub const fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Override a beatmap's set HP.
+    ///
+    /// See [`Difficulty::hp`].
+    pub fn hp(mut self, hp: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.hp(hp, with_mods);
+
+        self
+    }
+
+    /// Whether the play was set on osu!lazer or osu!stable.
+    ///
+    /// Defaults to `true`.
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.difficulty = self.difficulty.lazer(lazer);
+
+        self
+    }
+
+    /// Simulate the health bar for the given judgements.
+    ///
+    /// `results` contains the judgement of each hitobject in the map's order.
+    /// If it contains fewer judgements than the map has hitobjects, the
+    /// simulation stops after the last given judgement.
+    pub fn simulate(&self, results: &[HitResult]) -> Result<HealthSimulation, ConvertError> {
+        let mods = self.difficulty.get_mods();
+        let map = self.map.convert_ref(self.mode, mods)?;
+        let hp = map.attributes().difficulty(&self.difficulty).build().hp;
+
+        let mut judgements = match self.mode {
+            GameMode::Osu => osu::health_judgements(&map),
+            GameMode::Taiko => map
+                .hit_objects
+                .iter()
+                .enumerate()
+                .filter(|(_, h)| h.is_circle())
+                .map(|(i, h)| HealthJudgement::new(h.start_time, i, JudgementKind::Object))
+                .collect(),
+            GameMode::Catch => catch::health_judgements(&map),
+            GameMode::Mania => {
+                let mut judgements = Vec::with_capacity(map.hit_objects.len());
+
+                for (i, h) in map.hit_objects.iter().enumerate() {
+                    if let HitObjectKind::Hold(ref hold) = h.kind {
+                        let end_time = h.start_time + hold.duration;
+                        judgements.push(HealthJudgement::new(
+                            h.start_time,
+                            i,
+                            JudgementKind::HoldPart,
+                        ));
+                        judgements.push(HealthJudgement::new(end_time, i, JudgementKind::HoldPart));
+                    } else {
+                        judgements.push(HealthJudgement::new(
+                            h.start_time,
+                            i,
+                            JudgementKind::Object,
+                        ));
+                    }
+                }
+
+                judgements
+            }
+        };
+
+        judgements.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        let drain_start = map.hit_objects.first().map_or(0.0, |h| h.start_time);
+        let mut rules = Rules::new(self.mode, self.difficulty.get_lazer(), hp, &judgements);
+        let drain_rate = rules.drain_rate(&judgements, &map.breaks, drain_start);
+
+        let simulation = rules.simulate(
+            &judgements,
+            results,
+            &map.breaks,
+            drain_start,
+            drain_rate,
+            |fail_time| {
+                map.hit_objects
+                    .partition_point(|h| h.start_time <= fail_time)
+                    .min(results.len())
+            },
+        );
+
+        Ok(simulation)
+    }
+}
+
+/// The judgement of a hitobject for a [`HealthSimulator`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HitResult {
+    /// n320 in osu!mania.
+    Perfect,
+    /// n300 in osu!standard, osu!taiko, and osu!mania; caught fruit in
+    /// osu!catch.
+    Great,
+    /// n200 in osu!mania.
+    Good,
+    /// n100 in osu!standard, osu!taiko, and osu!mania.
+    Ok,
+    /// n50 in osu!standard and osu!mania.
+    Meh,
+    Miss,
+}
+
+impl HitResult {
+    const fn is_hit(self) -> bool {
+        !matches!(self, Self::Miss)
+    }
+}
+
+impl From<OsuHitResult> for HitResult {
+    fn from(result: OsuHitResult) -> Self {
+        match result {
+            OsuHitResult::Great => Self::Great,
+            OsuHitResult::Ok => Self::Ok,
+            OsuHitResult::Meh => Self::Meh,
+            OsuHitResult::Miss => Self::Miss,
+        }
+    }
+}
+
+/// The result of a [`HealthSimulator`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct HealthSimulation {
+    /// The health between `0.0` and `1.0` before and after each judgement.
+    pub health: Vec<HealthPoint>,
+    /// The health that drains per millisecond outside of breaks.
+    ///
+    /// Always `0.0` for osu!taiko.
+    pub drain_rate: f64,
+    /// When the play failed, if at all.
+    pub fail: Option<HealthFail>,
+}
+
+impl HealthSimulation {
+    /// Whether the play passed.
+    pub const fn passed(&self) -> bool {
+        self.fail.is_none()
+    }
+}
+
+/// The health at a point in time.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HealthPoint {
+    pub time: f64,
+    pub health: f64,
+}
+
+/// The point at which a play failed.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HealthFail {
+    /// The time of the fail in milliseconds.
+    pub time: f64,
+    /// The amount of hitobjects that started before the fail.
+    ///
+    /// Can be passed to [`Performance::passed_objects`] to calculate the
+    /// performance at the time of the fail.
+    ///
+    /// [`Performance::passed_objects`]: crate::Performance::passed_objects
+    pub passed_objects: usize,
+}
+
+/// A judgement that affects the health.
+pub(crate) struct HealthJudgement {
+    pub time: f64,
+    /// The index of the hitobject that the judgement belongs to.
+    pub object: usize,
+    pub kind: JudgementKind,
+}
+
+impl HealthJudgement {
+    pub(crate) const fn new(time: f64, object: usize, kind: JudgementKind) -> Self {
+        Self { time, object, kind }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum JudgementKind {
+    /// The judgement of the hitobject itself or of a fruit in a juice stream.
+    Object,
+    /// The head or tail of an osu!mania hold note.
+    HoldPart,
+    /// The head, a repeat, or the tail of an osu!standard slider.
+    SliderEnd,
+    /// A slider tick or a droplet.
+    LargeTick,
+    /// A tiny droplet.
+    SmallTick,
+}
+
+/// Tracks breaks while iterating over judgements in order.
+struct Breaks<'a> {
+    breaks: &'a [BreakPeriod],
+    last_time: f64,
+}
+
+impl<'a> Breaks<'a> {
+    const fn new(breaks: &'a [BreakPeriod], drain_start: f64) -> Self {
+        Self {
+            breaks,
+            last_time: drain_start,
+        }
+    }
+
+    /// The duration since the previous judgement during which health
+    /// drains.
+    fn drain_duration(&mut self, time: f64) -> f64 {
+        // * If two hitobjects are separated by a break period, there is no
+        // * drain for the full duration between the hitobjects.
+        while let Some((next, rest)) = self.breaks.split_first() {
+            if next.end_time > time {
+                break;
+            }
+
+            self.last_time = time;
+            self.breaks = rest;
+        }
+
+        let duration = (time - self.last_time).max(0.0);
+        self.last_time = time;
+
+        duration
+    }
+}
+
+enum Rules {
+    /// osu!stable's health for osu!standard and osu!catch.
+    StableDraining {
+        hp: f64,
+        mode: GameMode,
+        hp_multiplier: f64,
+    },
+    /// osu!stable's health for osu!mania which does not drain.
+    StableMania { hp: f64 },
+    /// osu!lazer's default health processor.
+    LazerDraining { hp: f64, lenience: f64 },
+    /// Health that starts empty and must be filled to half at the end.
+    Accumulating {
+        hp_multiplier: f64,
+        miss_multiplier: f64,
+    },
+}
+
+impl Rules {
+    fn new(mode: GameMode, lazer: bool, hp: f64, judgements: &[HealthJudgement]) -> Self {
+        match (mode, lazer) {
+            (GameMode::Taiko, _) => {
+                let n_objects = judgements.len().max(1) as f64;
+
+                Self::Accumulating {
+                    hp_multiplier: 1.0 / (n_objects * difficulty_range(hp, 0.5, 0.75, 0.98)),
+                    miss_multiplier: difficulty_range(hp, 0.0018, 0.0075, 0.0120),
+                }
+            }
+            (GameMode::Osu | GameMode::Catch, false) => Self::StableDraining {
+                hp,
+                mode,
+                hp_multiplier: 1.0,
+            },
+            (GameMode::Mania, false) => Self::StableMania { hp },
+            (GameMode::Osu | GameMode::Catch, true) => Self::LazerDraining { hp, lenience: 0.0 },
+            (GameMode::Mania, true) => Self::LazerDraining { hp, lenience: 1.0 },
+        }
+    }
+
+    /// Calibrates the drain rate such that a perfect play stays above the
+    /// targeted health. Also adjusts the health multiplier for osu!stable.
+    fn drain_rate(
+        &mut self,
+        judgements: &[HealthJudgement],
+        breaks: &[BreakPeriod],
+        drain_start: f64,
+    ) -> f64 {
+        match *self {
+            Self::StableDraining {
+                hp,
+                mode,
+                ref mut hp_multiplier,
+            } => stable_drain_rate(judgements, breaks, drain_start, hp, mode, hp_multiplier),
+            Self::LazerDraining { hp, lenience } => {
+                lazer_drain_rate(judgements, breaks, drain_start, hp, lenience)
+            }
+            Self::StableMania { .. } | Self::Accumulating { .. } => 0.0,
+        }
+    }
+
+    fn increase(&self, kind: JudgementKind, result: HitResult) -> f64 {
+        match *self {
+            Self::StableDraining {
+                hp,
+                mode,
+                hp_multiplier,
+            } => stable_increase(kind, result, hp, mode, hp_multiplier),
+            Self::StableMania { hp } => {
+                let increase = match result {
+                    HitResult::Perfect => 0.0055 - hp * 0.0005,
+                    HitResult::Great => 0.005 - hp * 0.0005,
+                    HitResult::Good => 0.004 - hp * 0.0004,
+                    HitResult::Ok => 0.0,
+                    HitResult::Meh => -(hp + 1.0) * 0.0016,
+                    HitResult::Miss => -(hp + 1.0) * 0.0075,
+                };
+
+                if kind == JudgementKind::HoldPart {
+                    increase * 0.5
+                } else {
+                    increase
+                }
+            }
+            Self::LazerDraining { .. } => lazer_increase(kind, result),
+            Self::Accumulating {
+                hp_multiplier,
+                miss_multiplier,
+            } => match result {
+                HitResult::Perfect | HitResult::Great | HitResult::Good => 3.0 * hp_multiplier,
+                HitResult::Ok | HitResult::Meh => 1.1 * hp_multiplier,
+                HitResult::Miss => -miss_multiplier,
+            },
+        }
+    }
+
+    fn simulate(
+        &self,
+        judgements: &[HealthJudgement],
+        results: &[HitResult],
+        breaks: &[BreakPeriod],
+        drain_start: f64,
+        drain_rate: f64,
+        passed_objects: impl Fn(f64) -> usize,
+    ) -> HealthSimulation {
+        let accumulating = matches!(self, Self::Accumulating { .. });
+        let mut health = if accumulating { 0.0 } else { 1.0 };
+
+        let mut points = Vec::with_capacity(judgements.len() * 2 + 1);
+        points.push(HealthPoint {
+            time: drain_start,
+            health,
+        });
+
+        let mut breaks = Breaks::new(breaks, drain_start);
+        let mut fail_time = None;
+
+        for judgement in judgements {
+            let Some(&result) = results.get(judgement.object) else {
+                continue;
+            };
+
+            let drained = breaks.drain_duration(judgement.time) * drain_rate;
+
+            if drained > 0.0 {
+                if health <= drained {
+                    let time = judgement.time - drained / drain_rate + health / drain_rate;
+                    points.push(HealthPoint { time, health: 0.0 });
+                    fail_time = Some(time);
+
+                    break;
+                }
+
+                health -= drained;
+
+                points.push(HealthPoint {
+                    time: judgement.time,
+                    health,
+                });
+            }
+
+            let result = if judgement.kind == JudgementKind::Object
+                || judgement.kind == JudgementKind::HoldPart
+                || !result.is_hit()
+            {
+                result
+            } else {
+                HitResult::Great
+            };
+
+            health = (health + self.increase(judgement.kind, result)).clamp(0.0, 1.0);
+
+            points.push(HealthPoint {
+                time: judgement.time,
+                health,
+            });
+
+            if !accumulating && health <= 0.0 {
+                fail_time = Some(judgement.time);
+
+                break;
+            }
+        }
+
+        let completed = results.len() >= judgements.iter().map(|h| h.object + 1).max().unwrap_or(0);
+
+        if accumulating && completed && health < 0.5 {
+            fail_time = points.last().map(|point| point.time);
+        }
+
+        HealthSimulation {
+            health: points,
+            drain_rate,
+            fail: fail_time.map(|time| HealthFail {
+                time,
+                passed_objects: passed_objects(time),
+            }),
+        }
+    }
+}
+
+fn stable_increase(
+    kind: JudgementKind,
+    result: HitResult,
+    hp: f64,
+    mode: GameMode,
+    hp_multiplier: f64,
+) -> f64 {
+    let miss = difficulty_range(hp, -0.03, -0.125, -0.2);
+    let tick_miss = difficulty_range(hp, -0.02, -0.075, -0.14);
+
+    let increase = match (kind, result) {
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Miss) => return miss,
+        (
+            JudgementKind::Object | JudgementKind::HoldPart,
+            HitResult::Perfect | HitResult::Great,
+        ) => 0.03,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Good | HitResult::Ok) => 0.011,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Meh) => 0.002,
+        (JudgementKind::SliderEnd, HitResult::Miss) => return tick_miss,
+        (JudgementKind::SliderEnd, _) => 0.02,
+        (JudgementKind::LargeTick, HitResult::Miss) if mode == GameMode::Catch => return miss,
+        (JudgementKind::LargeTick, HitResult::Miss) => return tick_miss,
+        (JudgementKind::LargeTick, _) => 0.015,
+        (JudgementKind::SmallTick, HitResult::Miss) => return 0.0,
+        (JudgementKind::SmallTick, _) => 0.0015,
+    };
+
+    increase * hp_multiplier
+}
+
+fn lazer_increase(kind: JudgementKind, result: HitResult) -> f64 {
+    let factor = match (kind, result) {
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Perfect) => 1.05,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Great) => 1.0,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Good) => 0.75,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Ok) => 0.5,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Meh) => -0.05,
+        (JudgementKind::Object | JudgementKind::HoldPart, HitResult::Miss) => -1.0,
+        (JudgementKind::SliderEnd | JudgementKind::LargeTick, HitResult::Miss) => -1.0,
+        (JudgementKind::SliderEnd | JudgementKind::LargeTick, _) => 1.0,
+        (JudgementKind::SmallTick, HitResult::Miss) => -0.5,
+        (JudgementKind::SmallTick, _) => 0.5,
+    };
+
+    factor * LAZER_MAX_HEALTH_INCREASE
+}
+
+/// Adjusts the drain rate and health multiplier until a perfect play
+/// satisfies osu!stable's health targets.
+fn stable_drain_rate(
+    judgements: &[HealthJudgement],
+    breaks: &[BreakPeriod],
+    drain_start: f64,
+    hp: f64,
+    mode: GameMode,
+    hp_multiplier: &mut f64,
+) -> f64 {
+    let Some(n_objects) = judgements.iter().map(|h| h.object + 1).max() else {
+        return 0.0;
+    };
+
+    let lowest_hp_ever = difficulty_range(hp, 0.975, 0.8, 0.3);
+    let lowest_hp_end = difficulty_range(hp, 0.99, 0.9, 0.4);
+    let hp_recovery_available = difficulty_range(hp, 0.04, 0.02, 0.0);
+
+    let mut test_drop = 0.000_25;
+
+    // Each failed attempt lowers the drain so this always terminates; the
+    // limit only guards against degenerate maps.
+    for _ in 0..1000 {
+        let mut breaks = Breaks::new(breaks, drain_start);
+        let mut curr_hp = 1.0;
+        let mut curr_hp_uncapped = 1.0;
+        let mut fail = false;
+
+        for judgement in judgements {
+            let drained = test_drop * breaks.drain_duration(judgement.time);
+            curr_hp -= drained;
+            curr_hp_uncapped -= drained;
+
+            if curr_hp <= lowest_hp_ever {
+                fail = true;
+                test_drop *= 0.96;
+
+                break;
+            }
+
+            let increase =
+                stable_increase(judgement.kind, HitResult::Great, hp, mode, *hp_multiplier);
+            curr_hp = f64::min(1.0, curr_hp + increase);
+            curr_hp_uncapped += increase;
+        }
+
+        if !fail && curr_hp < lowest_hp_end {
+            fail = true;
+            test_drop *= 0.94;
+            *hp_multiplier *= 1.01;
+        }
+
+        let recovery = (curr_hp_uncapped - 1.0) / n_objects as f64;
+
+        if !fail && recovery < hp_recovery_available {
+            fail = true;
+            test_drop *= 0.96;
+            *hp_multiplier *= 1.01;
+        }
+
+        if !fail {
+            break;
+        }
+    }
+
+    test_drop
+}
+
+/// Binary searches the drain rate such that the lowest health of a perfect
+/// play is close to osu!lazer's target.
+fn lazer_drain_rate(
+    judgements: &[HealthJudgement],
+    breaks: &[BreakPeriod],
+    drain_start: f64,
+    hp: f64,
+    lenience: f64,
+) -> f64 {
+    const MINIMUM_HEALTH_ERROR: f64 = 0.01;
+
+    if judgements.len() <= 1 {
+        return 0.0;
+    }
+
+    let mut target_minimum_health = difficulty_range(hp, 0.99, 0.9, 0.4);
+    // * Add back a portion of the amount of HP to be drained, depending on
+    // * the lenience requested.
+    target_minimum_health += lenience * (1.0 - target_minimum_health);
+    let target_minimum_health = target_minimum_health.clamp(0.0, 1.0);
+
+    let mut result = 1.0;
+
+    // * Although we expect the following loop to converge within 30
+    // * iterations (health within 1/2^31 accuracy of the target), we'll still
+    // * keep a safety measure to avoid infinite loops.
+    for i in 1..=31 {
+        let mut breaks = Breaks::new(breaks, drain_start);
+        let mut curr_health = 1.0;
+        let mut lowest_health: f64 = 1.0;
+
+        for judgement in judgements {
+            curr_health -= breaks.drain_duration(judgement.time) * result;
+            lowest_health = lowest_health.min(curr_health);
+            curr_health = f64::min(
+                1.0,
+                curr_health + lazer_increase(judgement.kind, HitResult::Great),
+            );
+
+            // * Common scenario for when the drain rate is definitely too
+            // * harsh
+            if lowest_health < 0.0 {
+                break;
+            }
+        }
+
+        // * Stop if the resulting health is within a reasonable offset from
+        // * the target
+        if (lowest_health - target_minimum_health).abs() <= MINIMUM_HEALTH_ERROR {
+            break;
+        }
+
+        // * This effectively works like a binary search - each iteration the
+        // * search space moves closer to the target, but may exceed it.
+        let adjustment = f64::from(1_u32 << i.min(31));
+        result += (lowest_health - target_minimum_health).signum() / adjustment;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MODES: [GameMode; 4] = [
+        GameMode::Osu,
+        GameMode::Taiko,
+        GameMode::Catch,
+        GameMode::Mania,
+    ];
+
+    #[test]
+    fn perfect_play_passes() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mode in MODES {
+            for lazer in [false, true] {
+                let n_objects = map
+                    .convert_ref(mode, &GameMods::DEFAULT)
+                    .unwrap()
+                    .hit_objects
+                    .len();
+                let results = vec![HitResult::Great; n_objects];
+
+                let simulation = HealthSimulator::new(&map)
+                    .mode(mode)
+                    .lazer(lazer)
+                    .simulate(&results)
+                    .unwrap();
+
+                assert!(simulation.passed(), "{mode:?} lazer={lazer}");
+                assert!(simulation
+                    .health
+                    .iter()
+                    .all(|point| (0.0..=1.0).contains(&point.health)));
+            }
+        }
+    }
+
+    #[test]
+    fn misses_fail() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mode in MODES {
+            for lazer in [false, true] {
+                let n_objects = map
+                    .convert_ref(mode, &GameMods::DEFAULT)
+                    .unwrap()
+                    .hit_objects
+                    .len();
+                let results = vec![HitResult::Miss; n_objects];
+
+                let simulation = HealthSimulator::new(&map)
+                    .mode(mode)
+                    .lazer(lazer)
+                    .simulate(&results)
+                    .unwrap();
+
+                let fail = simulation.fail.expect("expected fail");
+
+                assert!(fail.passed_objects <= n_objects, "{mode:?} lazer={lazer}");
+
+                if mode != GameMode::Taiko {
+                    assert!(
+                        fail.passed_objects < n_objects / 2,
+                        "{mode:?} lazer={lazer}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partial_results() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let results = vec![HitResult::Great; 100];
+
+        let simulation = HealthSimulator::new(&map).simulate(&results).unwrap();
+
+        assert!(simulation.passed());
+        assert!(simulation.drain_rate > 0.0);
+
+        let last = simulation.health.last().unwrap();
+        assert!(last.time < map.hit_objects[100].start_time);
+    }
+}
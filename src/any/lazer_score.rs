@@ -0,0 +1,554 @@
+use rosu_map::section::general::GameMode;
+use rosu_mods::{GameMod, GameModIntermode, GameModsIntermode};
+
+use crate::{
+    catch::CatchScoreState,
+    mania::ManiaScoreState,
+    model::mods::GameMods,
+    osu::{OsuScoreOrigin, OsuScoreState},
+    taiko::TaikoScoreState,
+};
+
+use super::{DifficultyAttributes, ScoreState};
+
+/// osu!lazer's maximum standardised score without bonus and mods.
+const MAX_SCORE: f64 = 1_000_000.0;
+
+/// Score of a "small bonus" judgement e.g. a spinner spin.
+const SMALL_BONUS_SCORE: f64 = 10.0;
+
+/// Score of a "large bonus" judgement e.g. a spinner bonus spin or a banana.
+const LARGE_BONUS_SCORE: f64 = 50.0;
+
+/// Calculates the standardised total score of an osu!lazer play.
+///
+/// The total score consists of a combo portion, an accuracy portion, and a
+/// bonus portion, scaled by the mods' score multiplier.
+///
+/// Since a [`ScoreState`] does not contain the order of judgements, the combo
+/// portion is an estimate. The score's maximum combo is assumed to be one
+/// streak while the remaining hits are spread evenly across the other streaks
+/// separated by misses.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{Beatmap, Difficulty};
+/// use rosu_pp::any::{LazerScoreCalculator, ScoreState};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+/// let attrs = Difficulty::new().mods(8).calculate(&map);
+///
+/// let state = ScoreState {
+///     max_combo: 500,
+///     n300: 580,
+///     n100: 19,
+///     misses: 2,
+///     ..ScoreState::new()
+/// };
+///
+/// let score = LazerScoreCalculator::new(attrs)
+///     .mods(8) // HD
+///     .state(state)
+///     .calculate();
+///
+/// println!("Standardised: {}", score.total_score);
+/// println!("Classic: {}", score.classic_score());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct LazerScoreCalculator {
+    attrs: DifficultyAttributes,
+    mods: GameMods,
+    state: ScoreState,
+    small_bonus: u32,
+    large_bonus: u32,
+}
+
+impl LazerScoreCalculator {
+    /// Create a new calculator for the given difficulty attributes.
+    ///
+    /// The mode of the attributes determines the scoring rules.
+    pub fn new(attrs: impl Into<DifficultyAttributes>) -> Self {
+        Self {
+            attrs: attrs.into(),
+            mods: GameMods::DEFAULT,
+            state: ScoreState::new(),
+            small_bonus: 0,
+            large_bonus: 0,
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`](crate::Difficulty::mods) for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.mods = mods.into();
+
+        self
+    }
+
+    /// Specify the score's judgements.
+    ///
+    /// For osu!mania, the maximum combo may be `0` in which case it is
+    /// estimated based on the amount of misses.
+    pub fn state(mut self, state: impl Into<ScoreState>) -> Self {
+        self.state = state.into();
+
+        self
+    }
+
+    /// Specify the amount of "small bonus" judgements such as spinner spins,
+    /// drum roll ticks, or swell ticks.
+    pub const fn small_bonus_hits(mut self, small_bonus: u32) -> Self {
+        self.small_bonus = small_bonus;
+
+        self
+    }
+
+    /// Specify the amount of "large bonus" judgements such as spinner bonus
+    /// spins, strong drum roll ticks, or bananas.
+    pub const fn large_bonus_hits(mut self, large_bonus: u32) -> Self {
+        self.large_bonus = large_bonus;
+
+        self
+    }
+
+    /// Calculate the standardised total score.
+    pub fn calculate(&self) -> LazerScoreAttributes {
+        let mode = self.mode();
+        let state = self.state.clone();
+
+        let (accuracy, hit_quality) = match self.attrs {
+            DifficultyAttributes::Osu(ref attrs) => {
+                let origin = if self.mods.no_slider_head_acc(true) {
+                    OsuScoreOrigin::WithoutSliderAcc {
+                        max_large_ticks: attrs.n_sliders + attrs.n_large_ticks,
+                        max_small_ticks: attrs.n_sliders,
+                    }
+                } else {
+                    OsuScoreOrigin::WithSliderAcc {
+                        max_large_ticks: attrs.n_large_ticks,
+                        max_slider_ends: attrs.n_sliders,
+                    }
+                };
+
+                let hit_quality = hit_quality(
+                    6 * state.n300 + 2 * state.n100 + state.n50,
+                    6,
+                    state.n300 + state.n100 + state.n50,
+                );
+
+                (OsuScoreState::from(state).accuracy(origin), hit_quality)
+            }
+            DifficultyAttributes::Taiko(_) => {
+                let hit_quality =
+                    hit_quality(2 * state.n300 + state.n100, 2, state.n300 + state.n100);
+
+                (TaikoScoreState::from(state).accuracy(), hit_quality)
+            }
+            DifficultyAttributes::Catch(_) => (CatchScoreState::from(state).accuracy(), 1.0),
+            // * The combo portion of osu!mania always uses the value of a
+            // * perfect judgement.
+            DifficultyAttributes::Mania(_) => {
+                (ManiaScoreState::from(state).accuracy(self.mods.cl()), 1.0)
+            }
+        };
+
+        let (max_judged, judged) = self.judgements();
+        let progress = if max_judged == 0 {
+            1.0
+        } else {
+            (f64::from(judged) / f64::from(max_judged)).min(1.0)
+        };
+
+        let combo_progress = hit_quality * self.combo_progress(progress);
+
+        let (combo_weight, accuracy_weight) = match mode {
+            GameMode::Osu => (700_000.0, 300_000.0 * accuracy.powi(10)),
+            GameMode::Taiko => (250_000.0, 750_000.0 * accuracy.powf(3.6)),
+            GameMode::Catch => (600_000.0, 400_000.0 * accuracy.powi(7)),
+            GameMode::Mania => (150_000.0, 850_000.0 * accuracy.powf(2.0 + 2.0 * accuracy)),
+        };
+
+        let combo_portion = combo_weight * combo_progress;
+        let accuracy_portion = accuracy_weight * progress;
+        let bonus_portion = f64::from(self.small_bonus) * SMALL_BONUS_SCORE
+            + f64::from(self.large_bonus) * LARGE_BONUS_SCORE;
+
+        let mod_multiplier = mod_multiplier(&self.mods, mode);
+        let total = (combo_portion + accuracy_portion + bonus_portion) * mod_multiplier;
+
+        LazerScoreAttributes {
+            mode,
+            total_score: total.round() as u64,
+            combo_portion,
+            accuracy_portion,
+            bonus_portion,
+            mod_multiplier,
+            max_basic_judgements: self.max_basic_judgements(),
+        }
+    }
+
+    const fn mode(&self) -> GameMode {
+        match self.attrs {
+            DifficultyAttributes::Osu(_) => GameMode::Osu,
+            DifficultyAttributes::Taiko(_) => GameMode::Taiko,
+            DifficultyAttributes::Catch(_) => GameMode::Catch,
+            DifficultyAttributes::Mania(_) => GameMode::Mania,
+        }
+    }
+
+    /// The maximum and current amount of judgements that determine the
+    /// score's progress through the map.
+    fn judgements(&self) -> (u32, u32) {
+        let max = match self.attrs {
+            DifficultyAttributes::Osu(ref attrs) => attrs.n_objects(),
+            DifficultyAttributes::Taiko(ref attrs) => attrs.max_combo,
+            DifficultyAttributes::Catch(ref attrs) => attrs.max_combo(),
+            DifficultyAttributes::Mania(ref attrs) => attrs.n_objects,
+        };
+
+        let state = &self.state;
+
+        let current = match self.attrs {
+            DifficultyAttributes::Osu(_) => state.n300 + state.n100 + state.n50 + state.misses,
+            DifficultyAttributes::Taiko(_) => state.n300 + state.n100 + state.misses,
+            DifficultyAttributes::Catch(_) => state.n300 + state.n100 + state.misses,
+            DifficultyAttributes::Mania(_) => state.total_hits(GameMode::Mania),
+        };
+
+        (max, current)
+    }
+
+    /// The amount of judgements that osu!lazer uses to convert between
+    /// standardised and classic scoring.
+    const fn max_basic_judgements(&self) -> u32 {
+        match self.attrs {
+            DifficultyAttributes::Osu(ref attrs) => attrs.n_objects(),
+            DifficultyAttributes::Taiko(ref attrs) => attrs.max_combo,
+            DifficultyAttributes::Catch(ref attrs) => attrs.n_fruits,
+            DifficultyAttributes::Mania(ref attrs) => attrs.n_objects + attrs.n_hold_notes,
+        }
+    }
+
+    /// The ratio between the estimated combo portion and the maximum combo
+    /// portion, assuming every combo judgement is perfect.
+    fn combo_progress(&self, progress: f64) -> f64 {
+        let mode = self.mode();
+        let max_combo = self.attrs.max_combo();
+
+        if max_combo == 0 {
+            return 1.0;
+        }
+
+        // Prefix sums of the combo's score increase
+        let mut prefix = Vec::with_capacity(max_combo as usize + 1);
+        prefix.push(0.0);
+
+        for combo in 1..=max_combo {
+            let curr = prefix[combo as usize - 1] + combo_score_change(mode, combo);
+            prefix.push(curr);
+        }
+
+        let judged = (f64::from(max_combo) * progress).round() as u32;
+        let misses = self.state.misses.min(judged);
+        let hits = judged - misses;
+
+        if hits == 0 {
+            return 0.0;
+        }
+
+        let max_streak = match self.state.max_combo {
+            0 if mode == GameMode::Mania => hits.div_ceil(misses + 1),
+            max_streak => max_streak.min(hits),
+        };
+
+        if max_streak == 0 {
+            return 0.0;
+        }
+
+        let mut portion = prefix[max_streak as usize];
+        let remaining = hits - max_streak;
+
+        if remaining > 0 {
+            let streaks = misses.max(1).max(remaining.div_ceil(max_streak));
+            let len = remaining / streaks;
+            let longer = remaining % streaks;
+
+            portion += f64::from(streaks - longer) * prefix[len as usize]
+                + f64::from(longer) * prefix[len as usize + 1];
+        }
+
+        portion / prefix[max_combo as usize]
+    }
+}
+
+/// The result of a [`LazerScoreCalculator`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct LazerScoreAttributes {
+    /// The mode of the score.
+    pub mode: GameMode,
+    /// The standardised total score including the mod multiplier.
+    pub total_score: u64,
+    /// The score gained through combo, excluding the mod multiplier.
+    pub combo_portion: f64,
+    /// The score gained through accuracy, excluding the mod multiplier.
+    pub accuracy_portion: f64,
+    /// The score gained through bonus judgements, excluding the mod
+    /// multiplier.
+    pub bonus_portion: f64,
+    /// The mods' score multiplier.
+    pub mod_multiplier: f64,
+    /// The amount of hitobject judgements in the map, excluding nested
+    /// objects.
+    pub max_basic_judgements: u32,
+}
+
+impl LazerScoreAttributes {
+    /// The total score as displayed with osu!lazer's "classic" scoring mode.
+    pub fn classic_score(&self) -> u64 {
+        standardised_to_classic(self.mode, self.total_score, self.max_basic_judgements)
+    }
+}
+
+/// Convert osu!lazer's standardised total score to its "classic" display
+/// score.
+///
+/// `max_basic_judgements` is the amount of hitobject judgements in the map,
+/// see [`LazerScoreAttributes::max_basic_judgements`].
+pub fn standardised_to_classic(
+    mode: GameMode,
+    standardised: u64,
+    max_basic_judgements: u32,
+) -> u64 {
+    let score = standardised as f64;
+    let n = f64::from(max_basic_judgements);
+
+    let classic = match mode {
+        GameMode::Osu => (n * n * 32.57 + 100_000.0) * score / MAX_SCORE,
+        GameMode::Taiko => (n * 1109.0 + 100_000.0) * score / MAX_SCORE,
+        GameMode::Catch => (score / MAX_SCORE * n).powi(2) * 21.62 + score / 10.0,
+        GameMode::Mania => score,
+    };
+
+    classic.round() as u64
+}
+
+/// Convert osu!lazer's "classic" display score to its standardised total
+/// score.
+///
+/// This is the inverse of [`standardised_to_classic`].
+pub fn classic_to_standardised(mode: GameMode, classic: u64, max_basic_judgements: u32) -> u64 {
+    let score = classic as f64;
+    let n = f64::from(max_basic_judgements);
+
+    let standardised = match mode {
+        GameMode::Osu => score * MAX_SCORE / (n * n * 32.57 + 100_000.0),
+        GameMode::Taiko => score * MAX_SCORE / (n * 1109.0 + 100_000.0),
+        GameMode::Catch => {
+            // Solve `a * x^2 + x / 10 = score` for `x`
+            let a = (n / MAX_SCORE).powi(2) * 21.62;
+
+            if a > 0.0 {
+                (-0.1 + (0.01 + 4.0 * a * score).sqrt()) / (2.0 * a)
+            } else {
+                score * 10.0
+            }
+        }
+        GameMode::Mania => score,
+    };
+
+    standardised.round() as u64
+}
+
+/// The ratio between the values of hit judgements and their maximum value.
+fn hit_quality(value: u32, max_value: u32, hits: u32) -> f64 {
+    if hits == 0 {
+        1.0
+    } else {
+        f64::from(value) / f64::from(max_value * hits)
+    }
+}
+
+/// The combo portion's increase for a judgement that results in the given
+/// combo.
+fn combo_score_change(mode: GameMode, combo: u32) -> f64 {
+    const COMBO_BASE: f64 = 4.0;
+
+    let combo = f64::from(combo);
+
+    let combo_cap: f64 = match mode {
+        GameMode::Osu => return combo.sqrt(),
+        GameMode::Taiko | GameMode::Mania => 400.0,
+        GameMode::Catch => 200.0,
+    };
+
+    combo
+        .log(COMBO_BASE)
+        .max(0.5)
+        .min(combo_cap.log(COMBO_BASE))
+}
+
+/// osu!lazer's score multiplier of the mods.
+///
+/// Mods without a multiplier, e.g. conversion mods, count as `1.0`.
+fn mod_multiplier(mods: &GameMods, mode: GameMode) -> f64 {
+    let intermode: GameModsIntermode = match mods {
+        GameMods::Lazer(ref mods) => mods.iter().map(GameMod::intermode).collect(),
+        GameMods::Intermode(ref mods) => mods.clone(),
+        GameMods::Legacy(mods) => mods.to_intermode(),
+    };
+
+    // * Round to the nearest multiple of 0.1.
+    let rate = f64::from((mods.clock_rate() * 10.0) as i32) / 10.0;
+
+    intermode
+        .iter()
+        .map(|gamemod| match gamemod {
+            GameModIntermode::Easy | GameModIntermode::NoFail => 0.5,
+            GameModIntermode::HalfTime | GameModIntermode::Daycore => rate,
+            // * Each 0.1 multiplier changes ScoreMultiplier by 0.02.
+            GameModIntermode::DoubleTime | GameModIntermode::Nightcore => 1.0 + (rate - 1.0) / 5.0,
+            GameModIntermode::Hidden | GameModIntermode::Flashlight if mode == GameMode::Mania => {
+                1.0
+            }
+            GameModIntermode::Hidden => 1.06,
+            GameModIntermode::HardRock => match mode {
+                GameMode::Osu | GameMode::Taiko => 1.06,
+                GameMode::Catch => 1.12,
+                GameMode::Mania => 1.0,
+            },
+            GameModIntermode::Flashlight | GameModIntermode::Blinds => 1.12,
+            GameModIntermode::SpunOut => 0.9,
+            GameModIntermode::Relax | GameModIntermode::Autopilot => 0.1,
+            GameModIntermode::Classic => 0.96,
+            GameModIntermode::DifficultyAdjust => 0.5,
+            GameModIntermode::OneKey
+            | GameModIntermode::TwoKeys
+            | GameModIntermode::ThreeKeys
+            | GameModIntermode::FourKeys
+            | GameModIntermode::FiveKeys
+            | GameModIntermode::SixKeys
+            | GameModIntermode::SevenKeys
+            | GameModIntermode::EightKeys
+            | GameModIntermode::NineKeys
+            | GameModIntermode::TenKeys => 0.9,
+            _ => 1.0,
+        })
+        .product()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Beatmap, Difficulty};
+
+    use super::*;
+
+    #[test]
+    fn perfect_play() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mode in [
+            GameMode::Osu,
+            GameMode::Taiko,
+            GameMode::Catch,
+            GameMode::Mania,
+        ] {
+            let map = map.convert_ref(mode, &GameMods::DEFAULT).unwrap();
+            let attrs = Difficulty::new().calculate(&map);
+            let max_combo = attrs.max_combo();
+
+            let state = match attrs {
+                DifficultyAttributes::Osu(ref attrs) => ScoreState {
+                    max_combo,
+                    osu_large_tick_hits: attrs.n_large_ticks,
+                    slider_end_hits: attrs.n_sliders,
+                    n300: attrs.n_objects(),
+                    ..ScoreState::new()
+                },
+                DifficultyAttributes::Taiko(_) => ScoreState {
+                    max_combo,
+                    n300: max_combo,
+                    ..ScoreState::new()
+                },
+                DifficultyAttributes::Catch(ref attrs) => ScoreState {
+                    max_combo,
+                    n300: attrs.n_fruits,
+                    n100: attrs.n_droplets,
+                    n50: attrs.n_tiny_droplets,
+                    ..ScoreState::new()
+                },
+                DifficultyAttributes::Mania(ref attrs) => ScoreState {
+                    n_geki: attrs.n_objects,
+                    ..ScoreState::new()
+                },
+            };
+
+            let score = LazerScoreCalculator::new(attrs.clone())
+                .state(state.clone())
+                .calculate();
+
+            assert_eq!(score.total_score, 1_000_000, "{mode:?}");
+
+            let hd = LazerScoreCalculator::new(attrs)
+                .mods(8)
+                .state(state)
+                .calculate();
+
+            let expected = if mode == GameMode::Mania {
+                1_000_000
+            } else {
+                1_060_000
+            };
+
+            assert_eq!(hd.total_score, expected, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn misses_reduce_combo_portion() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let attrs = Difficulty::new().calculate(&map);
+        let max_combo = attrs.max_combo();
+
+        let calculate = |max_combo, misses| {
+            let state = ScoreState {
+                max_combo,
+                n300: 601 - misses,
+                misses,
+                ..ScoreState::new()
+            };
+
+            LazerScoreCalculator::new(attrs.clone())
+                .mods(GameModsIntermode::from_acronyms("CL"))
+                .state(state)
+                .calculate()
+        };
+
+        let fc = calculate(max_combo, 0);
+        let half = calculate(max_combo / 2, 1);
+        let quarter = calculate(max_combo / 4, 1);
+
+        assert!(fc.combo_portion > half.combo_portion);
+        assert!(half.combo_portion > quarter.combo_portion);
+        assert!((fc.mod_multiplier - 0.96).abs() < 1e-9);
+    }
+
+    #[test]
+    fn classic_roundtrip() {
+        for mode in [
+            GameMode::Osu,
+            GameMode::Taiko,
+            GameMode::Catch,
+            GameMode::Mania,
+        ] {
+            for standardised in [0, 123_456, 1_000_000] {
+                let classic = standardised_to_classic(mode, standardised, 1000);
+                let converted = classic_to_standardised(mode, classic, 1000);
+
+                assert!(converted.abs_diff(standardised) <= 1, "{mode:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    catch, mania,
+    model::{mode::ConvertError, mods::GameMods},
+    osu, taiko, Beatmap,
+};
+
+/// Simulates the osu!stable score (`ScoreV1`) of a play that hits every object
+/// perfectly.
+///
+/// The score of each judgement consists of its hit value and a bonus based on
+/// the current combo, the map's difficulty multiplier, and the mods' score
+/// multiplier.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::{any::LegacyScoreSimulator, Beatmap};
+///
+/// let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+///
+/// let attrs = LegacyScoreSimulator::new(&map)
+///     .mods(8 + 64) // HDDT
+///     .simulate()
+///     .unwrap();
+///
+/// println!("Max score: {}", attrs.max_score());
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct LegacyScoreSimulator<'map> {
+    map: &'map Beatmap,
+    mode: GameMode,
+    mods: GameMods,
+}
+
+impl<'map> LegacyScoreSimulator<'map> {
+    /// Create a new simulator for the given map.
+    ///
+    /// For converts, the map should be the original osu!standard map because
+    /// the difficulty multiplier is based on its objects; see
+    /// [`LegacyScoreSimulator::mode`].
+    pub const fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            mode: map.mode,
+            mods: GameMods::DEFAULT,
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`](crate::Difficulty::mods) for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.mods = mods.into();
+
+        self
+    }
+
+    /// Convert the map to the given mode before simulating.
+    pub const fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Simulate the score of a perfect play.
+    pub fn simulate(&self) -> Result<LegacyScoreAttributes, ConvertError> {
+        let difficulty_multiplier = difficulty_multiplier(self.map);
+        let map = self.map.convert_ref(self.mode, &self.mods)?;
+
+        let attrs = match self.mode {
+            GameMode::Osu => osu::legacy_score(&map, &self.mods, difficulty_multiplier),
+            GameMode::Taiko => taiko::legacy_score(&map, &self.mods, difficulty_multiplier),
+            GameMode::Catch => catch::legacy_score(&map, &self.mods, difficulty_multiplier),
+            GameMode::Mania => mania::legacy_score(self.map, &map, &self.mods),
+        };
+
+        Ok(attrs)
+    }
+}
+
+/// The osu!stable score of a perfect play.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LegacyScoreAttributes {
+    /// The sum of all hit values, excluding bonus.
+    ///
+    /// For osu!mania, this contains the whole score.
+    pub accuracy_score: u64,
+    /// The score gained through combo.
+    pub combo_score: u64,
+    /// The score of spinner bonus, drum roll ticks, swells, and bananas.
+    pub bonus_score: u64,
+    /// The map's difficulty multiplier based on its difficulty settings and
+    /// object density.
+    ///
+    /// Always `0` for osu!mania since its score does not depend on it.
+    pub difficulty_multiplier: u32,
+    /// The mods' score multiplier.
+    pub mod_multiplier: f64,
+    /// The maximum combo.
+    pub max_combo: u32,
+    /// The score of each hitobject in the map's order, including its nested
+    /// objects.
+    pub objects: Vec<LegacyObjectScore>,
+}
+
+impl LegacyScoreAttributes {
+    pub(crate) fn new(
+        difficulty_multiplier: u32,
+        mod_multiplier: f64,
+        max_combo: u32,
+        objects: Vec<LegacyObjectScore>,
+    ) -> Self {
+        let mut attrs = Self {
+            difficulty_multiplier,
+            mod_multiplier,
+            max_combo,
+            ..Self::default()
+        };
+
+        for h in objects.iter() {
+            attrs.accuracy_score += u64::from(h.accuracy_score);
+            attrs.combo_score += u64::from(h.combo_score);
+            attrs.bonus_score += u64::from(h.bonus_score);
+        }
+
+        attrs.objects = objects;
+
+        attrs
+    }
+
+    /// The maximum achievable total score.
+    pub const fn max_score(&self) -> u64 {
+        self.accuracy_score + self.combo_score + self.bonus_score
+    }
+}
+
+/// The osu!stable score of a single hitobject.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LegacyObjectScore {
+    pub start_time: f64,
+    /// The sum of hit values of the object and its nested objects.
+    pub accuracy_score: u32,
+    /// The score gained through combo.
+    pub combo_score: u32,
+    /// The score of spinner bonus, drum roll ticks, swells, and bananas.
+    pub bonus_score: u32,
+}
+
+impl LegacyObjectScore {
+    pub(crate) const fn new(start_time: f64) -> Self {
+        Self {
+            start_time,
+            accuracy_score: 0,
+            combo_score: 0,
+            bonus_score: 0,
+        }
+    }
+
+    /// The object's total score.
+    pub const fn total(&self) -> u32 {
+        self.accuracy_score + self.combo_score + self.bonus_score
+    }
+}
+
+/// The score gained through combo when hitting a judgement with the given
+/// hit value in osu!standard and osu!catch.
+pub(crate) fn combo_score(combo: u32, hit_value: u32, score_multiplier: f64) -> u32 {
+    // Integer division is intentional to match osu!stable
+    (f64::from(combo.saturating_sub(1)) * (f64::from(hit_value / 25) * score_multiplier)) as u32
+}
+
+/// osu!stable's difficulty multiplier, also known as "peppy stars".
+///
+/// Always based on the map before conversion and without mods.
+fn difficulty_multiplier(map: &Beatmap) -> u32 {
+    let object_count = map.hit_objects.len() as i32;
+
+    let drain_len = match (map.hit_objects.first(), map.hit_objects.last()) {
+        (Some(first), Some(last)) => {
+            let break_len: i32 = map
+                .breaks
+                .iter()
+                .map(|b| round(b.end_time) - round(b.start_time))
+                .sum();
+
+            (round(last.start_time) - round(first.start_time) - break_len) / 1000
+        }
+        _ => 0,
+    };
+
+    let object_to_drain_ratio = if drain_len == 0 {
+        16
+    } else {
+        (object_count / drain_len * 8).clamp(0, 16)
+    };
+
+    let multiplier = (map.hp + map.od + map.cs + object_to_drain_ratio as f32) / 38.0 * 5.0;
+
+    f64::from(multiplier).round_ties_even() as u32
+}
+
+/// Rounds like C#'s `Math.Round`, i.e. to the nearest even number on
+/// midpoints.
+const fn round(n: f64) -> i32 {
+    n.round_ties_even() as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::{beatmap::BeatmapBuilder, hit_object::Pos},
+        Difficulty,
+    };
+
+    use super::*;
+
+    #[test]
+    fn converts() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mode in [
+            GameMode::Osu,
+            GameMode::Taiko,
+            GameMode::Catch,
+            GameMode::Mania,
+        ] {
+            let attrs = LegacyScoreSimulator::new(&map)
+                .mode(mode)
+                .simulate()
+                .unwrap();
+            let sum: u64 = attrs.objects.iter().map(|h| u64::from(h.total())).sum();
+
+            let max_combo = Difficulty::new()
+                .calculate(&map.convert_ref(mode, &GameMods::DEFAULT).unwrap())
+                .max_combo();
+
+            assert_eq!(attrs.max_score(), sum, "{mode:?}");
+            assert_eq!(attrs.max_combo, max_combo, "{mode:?}");
+
+            let expected_multiplier = if mode == GameMode::Mania { 0 } else { 5 };
+            assert_eq!(attrs.difficulty_multiplier, expected_multiplier, "{mode:?}");
+        }
+    }
+
+    #[test]
+    fn mod_multiplier() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let nomod = LegacyScoreSimulator::new(&map).simulate().unwrap();
+        let hdhr = LegacyScoreSimulator::new(&map)
+            .mods(8 + 16)
+            .simulate()
+            .unwrap();
+        let relax = LegacyScoreSimulator::new(&map)
+            .mods(128)
+            .simulate()
+            .unwrap();
+
+        assert!((hdhr.mod_multiplier - 1.06 * 1.06).abs() < 1e-9);
+        assert_eq!(hdhr.accuracy_score, nomod.accuracy_score);
+        assert!(hdhr.combo_score > nomod.combo_score);
+        assert_eq!(relax.combo_score, 0);
+    }
+
+    #[test]
+    fn circles() {
+        let map = BeatmapBuilder::new()
+            .circle(Pos::default(), 0.0)
+            .circle(Pos::default(), 1000.0)
+            .circle(Pos::default(), 2000.0)
+            .build()
+            .unwrap();
+
+        let attrs = LegacyScoreSimulator::new(&map).simulate().unwrap();
+
+        // (5 + 5 + 5 + 3 / 2 * 8) / 38 * 5 = 3.03
+        assert_eq!(attrs.difficulty_multiplier, 3);
+        assert_eq!(attrs.accuracy_score, 3 * 300);
+        // Only the third circle gains combo score: 1 * 300 / 25 * 3
+        assert_eq!(attrs.combo_score, 36);
+        assert_eq!(attrs.bonus_score, 0);
+        assert_eq!(attrs.max_combo, 3);
+    }
+
+    #[test]
+    fn values() {
+        let osu = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let taiko = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let catch = Beatmap::from_path("./resources/2118524.osu").unwrap();
+        let mania = Beatmap::from_path("./resources/1638954.osu").unwrap();
+
+        for (map, mode, expected) in [
+            (&osu, GameMode::Osu, [198_330, 15_729_840, 8000, 909]),
+            (&osu, GameMode::Taiko, [275_700, 691_040, 4800, 908]),
+            (&osu, GameMode::Catch, [273_990, 24_652_260, 18_700, 908]),
+            (&osu, GameMode::Mania, [1_000_000, 0, 0, 1381]),
+            (&taiko, GameMode::Taiko, [102_300, 181_632, 16_800, 289]),
+            (&catch, GameMode::Catch, [221_230, 12_734_112, 18_700, 730]),
+            (&mania, GameMode::Mania, [1_000_000, 0, 0, 956]),
+        ] {
+            let attrs = LegacyScoreSimulator::new(map)
+                .mode(mode)
+                .simulate()
+                .unwrap();
+
+            let actual = [
+                attrs.accuracy_score,
+                attrs.combo_score,
+                attrs.bonus_score,
+                u64::from(attrs.max_combo),
+            ];
+
+            assert_eq!(actual, expected, "{mode:?}");
+        }
+    }
+}
@@ -3,6 +3,13 @@ pub use self::{
     difficulty::{
         gradual::GradualDifficulty, inspect::InspectDifficulty, Difficulty, ModsDependent,
     },
+    grade::Grade,
+    health::{HealthFail, HealthPoint, HealthSimulation, HealthSimulator, HitResult},
+    lazer_score::{
+        classic_to_standardised, standardised_to_classic, LazerScoreAttributes,
+        LazerScoreCalculator,
+    },
+    legacy_score::{LegacyObjectScore, LegacyScoreAttributes, LegacyScoreSimulator},
     performance::{
         gradual::GradualPerformance,
         into::{IntoModePerformance, IntoPerformance},
@@ -14,6 +21,10 @@ pub use self::{
 
 mod attributes;
 pub(crate) mod difficulty;
+mod grade;
+pub(crate) mod health;
+mod lazer_score;
+pub(crate) mod legacy_score;
 mod performance;
 mod score_state;
 mod strains;
@@ -434,6 +434,7 @@ impl<'map> Performance<'map> {
 
 /// While generating remaining hitresults, decide how they should be distributed.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HitResultPriority {
     /// Prioritize good hitresults over bad ones
     BestCase,
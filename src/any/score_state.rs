@@ -1,11 +1,18 @@
 use rosu_map::section::general::GameMode;
 
 use crate::{
-    catch::CatchScoreState, mania::ManiaScoreState, osu::OsuScoreState, taiko::TaikoScoreState,
+    catch::CatchScoreState,
+    mania::ManiaScoreState,
+    model::mods::GameMods,
+    osu::{OsuScoreOrigin, OsuScoreState},
+    taiko::TaikoScoreState,
 };
 
+use super::Grade;
+
 /// Aggregation for a score's current state.
 #[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ScoreState {
     /// Maximum combo that the score has had so far. **Not** the maximum
     /// possible combo of the map so far.
@@ -85,6 +92,169 @@ impl ScoreState {
 
         amount
     }
+
+    /// Calculate the accuracy between `0.0` and `1.0` for this state.
+    ///
+    /// For osu!lazer scores in osu!standard, the state does not contain the
+    /// map's amount of slider ticks and ends so their hits are considered as
+    /// if none were missed. Use [`OsuScoreState::accuracy`] to take their
+    /// misses into account.
+    pub fn accuracy(&self, mode: GameMode, lazer: bool) -> f64 {
+        self.accuracy_with_classic(mode, lazer, !lazer)
+    }
+
+    /// Calculate the grade of this state.
+    ///
+    /// Grades are based on the accuracy and the amount of misses, and S or
+    /// SS turn silver for `Hidden` and `Flashlight`, as well as `FadeIn` in
+    /// osu!mania. On osu!lazer, osu!mania scores with only PERFECT and GREAT
+    /// judgements are SS regardless of their accuracy.
+    pub fn grade(&self, mode: GameMode, mods: impl Into<GameMods>, lazer: bool) -> Grade {
+        let mods = mods.into();
+        let classic = !lazer || mods.cl();
+        let accuracy = self.accuracy_with_classic(mode, lazer, classic);
+
+        let grade = if lazer {
+            self.lazer_grade(mode, accuracy)
+        } else {
+            self.stable_grade(mode, accuracy)
+        };
+
+        let silver = mods.hd() || mods.fl() || (mode == GameMode::Mania && mods.fi());
+
+        if silver {
+            grade.silver()
+        } else {
+            grade
+        }
+    }
+
+    fn accuracy_with_classic(&self, mode: GameMode, lazer: bool, classic: bool) -> f64 {
+        match mode {
+            GameMode::Osu => {
+                let origin = if !lazer {
+                    OsuScoreOrigin::Stable
+                } else if self.osu_small_tick_hits > 0 {
+                    OsuScoreOrigin::WithoutSliderAcc {
+                        max_large_ticks: self.osu_large_tick_hits,
+                        max_small_ticks: self.osu_small_tick_hits,
+                    }
+                } else {
+                    OsuScoreOrigin::WithSliderAcc {
+                        max_large_ticks: self.osu_large_tick_hits,
+                        max_slider_ends: self.slider_end_hits,
+                    }
+                };
+
+                OsuScoreState::from(self.clone()).accuracy(origin)
+            }
+            GameMode::Taiko => TaikoScoreState::from(self.clone()).accuracy(),
+            GameMode::Catch => CatchScoreState::from(self.clone()).accuracy(),
+            GameMode::Mania => ManiaScoreState::from(self.clone()).accuracy(classic),
+        }
+    }
+
+    fn stable_grade(&self, mode: GameMode, accuracy: f64) -> Grade {
+        match mode {
+            GameMode::Osu | GameMode::Taiko => {
+                let total_hits = self.total_hits(mode);
+
+                if total_hits == 0 {
+                    return Grade::D;
+                }
+
+                let ratio300 = f64::from(self.n300) / f64::from(total_hits);
+                let ratio50 = f64::from(self.n50) / f64::from(total_hits);
+
+                if self.n300 == total_hits {
+                    Grade::X
+                } else if ratio300 > 0.9 && ratio50 < 0.01 && self.misses == 0 {
+                    Grade::S
+                } else if (ratio300 > 0.8 && self.misses == 0) || ratio300 > 0.9 {
+                    Grade::A
+                } else if (ratio300 > 0.7 && self.misses == 0) || ratio300 > 0.8 {
+                    Grade::B
+                } else if ratio300 > 0.6 {
+                    Grade::C
+                } else {
+                    Grade::D
+                }
+            }
+            GameMode::Catch => catch_grade(accuracy),
+            GameMode::Mania => {
+                if accuracy >= 1.0 {
+                    Grade::X
+                } else if accuracy > 0.95 {
+                    Grade::S
+                } else if accuracy > 0.9 {
+                    Grade::A
+                } else if accuracy > 0.8 {
+                    Grade::B
+                } else if accuracy > 0.7 {
+                    Grade::C
+                } else {
+                    Grade::D
+                }
+            }
+        }
+    }
+
+    fn lazer_grade(&self, mode: GameMode, accuracy: f64) -> Grade {
+        if mode == GameMode::Catch {
+            return catch_grade(accuracy);
+        }
+
+        let grade = if accuracy >= 1.0 {
+            Grade::X
+        } else if accuracy >= 0.95 {
+            Grade::S
+        } else if accuracy >= 0.9 {
+            Grade::A
+        } else if accuracy >= 0.8 {
+            Grade::B
+        } else if accuracy >= 0.7 {
+            Grade::C
+        } else {
+            Grade::D
+        };
+
+        match mode {
+            // * SS is expected as long as all hitobjects have been hit with
+            // * either a GREAT or PERFECT result.
+            // Same as `ManiaScoreProcessor.RankFromScore` which, unlike osu!
+            // and taiko, does not lower S to A for misses
+            GameMode::Mania => {
+                let imperfect = self.n_katu + self.n100 + self.n50 + self.misses;
+
+                if grade == Grade::S && imperfect == 0 {
+                    Grade::X
+                } else {
+                    grade
+                }
+            }
+            // No S or above with misses, see `OsuScoreProcessor` and
+            // `TaikoScoreProcessor`
+            _ if grade >= Grade::S && self.misses > 0 => Grade::A,
+            _ => grade,
+        }
+    }
+}
+
+/// osu!catch uses the same grades on osu!stable and osu!lazer.
+fn catch_grade(accuracy: f64) -> Grade {
+    if accuracy >= 1.0 {
+        Grade::X
+    } else if accuracy > 0.98 {
+        Grade::S
+    } else if accuracy > 0.94 {
+        Grade::A
+    } else if accuracy > 0.9 {
+        Grade::B
+    } else if accuracy > 0.85 {
+        Grade::C
+    } else {
+        Grade::D
+    }
 }
 
 impl From<ScoreState> for OsuScoreState {
@@ -212,3 +382,74 @@ impl Default for ScoreState {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stable_grades() {
+        let state = ScoreState {
+            max_combo: 100,
+            n300: 95,
+            n100: 5,
+            ..ScoreState::new()
+        };
+
+        assert_eq!(state.grade(GameMode::Osu, 0, false), Grade::S);
+        assert_eq!(state.grade(GameMode::Osu, 8, false), Grade::SH);
+
+        let state = ScoreState {
+            n300: 95,
+            n100: 4,
+            misses: 1,
+            ..state
+        };
+
+        assert_eq!(state.grade(GameMode::Osu, 0, false), Grade::A);
+        assert_eq!(state.grade(GameMode::Taiko, 1024, false), Grade::A);
+
+        let state = ScoreState {
+            n300: 100,
+            n100: 0,
+            misses: 0,
+            ..state
+        };
+
+        assert_eq!(state.grade(GameMode::Osu, 1024, false), Grade::XH);
+        assert_eq!(state.grade(GameMode::Catch, 0, false), Grade::X);
+    }
+
+    #[test]
+    fn lazer_grades() {
+        let state = ScoreState {
+            n_geki: 90,
+            n300: 10,
+            ..ScoreState::new()
+        };
+
+        // Only PERFECT and GREAT judgements give SS on lazer despite the
+        // accuracy being below 100%
+        assert!(state.accuracy(GameMode::Mania, true) < 1.0);
+        assert_eq!(state.grade(GameMode::Mania, 0, false), Grade::X);
+        assert_eq!(state.grade(GameMode::Mania, 0, true), Grade::X);
+        assert_eq!(state.grade(GameMode::Mania, 1_048_576, true), Grade::XH);
+
+        let state = ScoreState { n_katu: 1, ..state };
+
+        assert_eq!(state.grade(GameMode::Mania, 0, true), Grade::S);
+
+        let state = ScoreState {
+            max_combo: 500,
+            osu_large_tick_hits: 50,
+            slider_end_hits: 20,
+            n300: 298,
+            misses: 1,
+            ..ScoreState::new()
+        };
+
+        assert!(state.accuracy(GameMode::Osu, true) > state.accuracy(GameMode::Osu, false));
+        assert_eq!(state.grade(GameMode::Osu, 0, true), Grade::A);
+        assert_eq!(state.grade(GameMode::Mania, 0, true), Grade::S);
+    }
+}
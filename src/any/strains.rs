@@ -4,6 +4,7 @@ use crate::{catch::CatchStrains, mania::ManiaStrains, osu::OsuStrains, taiko::Ta
 ///
 /// Suitable to plot the difficulty of a map over time.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Strains {
     Osu(OsuStrains),
     Taiko(TaikoStrains),
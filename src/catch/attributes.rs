@@ -4,6 +4,7 @@ use crate::catch::performance::CatchPerformance;
 
 /// The result of a difficulty calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CatchDifficultyAttributes {
     /// The final star rating
     pub stars: f64,
@@ -58,6 +59,7 @@ impl CatchDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!catch map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CatchPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: CatchDifficultyAttributes,
@@ -0,0 +1,367 @@
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::Difficulty,
+    model::{mode::ConvertError, mods::GameMods, replay::ReplayFrame},
+    Beatmap,
+};
+
+use super::{
+    attributes::ObjectCountBuilder, catcher::Catcher, convert::convert_objects,
+    object::palpable::PalpableObject, PLAYFIELD_WIDTH,
+};
+
+/// Tolerance for floating point errors when comparing distances.
+const EPSILON: f32 = 1e-3;
+
+/// Simulates a catcher that catches the fruits and droplets of an osu!catch
+/// [`Beatmap`] as well as possible.
+///
+/// The catcher walks whenever possible, dashes if needed, and hyperdashes
+/// after catching a hyperfruit. Objects that cannot be reached in time are
+/// reported as uncaught, e.g. to detect impossible jumps or patterns broken by
+/// HR offsets.
+///
+/// Since the catcher moves in gameplay time, the clock rate does not affect
+/// whether an object can be caught and all times are in milliseconds of the
+/// map.
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{catch::CatchAutoplay, Beatmap};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let map = Beatmap::from_path("/path/to/map.osu")?;
+///
+/// let path = CatchAutoplay::new(&map).mods(16).generate()?; // HR
+///
+/// for h in path.uncatchable() {
+///     println!("Cannot catch object at {}ms", h.start_time);
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct CatchAutoplay<'map> {
+    map: &'map Beatmap,
+    difficulty: Difficulty,
+}
+
+impl<'map> CatchAutoplay<'map> {
+    /// Create a new catcher simulation for the given map.
+    pub const fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            difficulty: Difficulty::new(),
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`] for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Override a beatmap's set CS.
+    ///
+    /// See [`Difficulty::cs`].
+    pub fn cs(mut self, cs: f32, with_mods: bool) -> Self {
+        self.difficulty = self.difficulty.cs(cs, with_mods);
+
+        self
+    }
+
+    /// Adjust patterns as if the HR mod is enabled.
+    pub fn hardrock_offsets(mut self, hardrock_offsets: bool) -> Self {
+        self.difficulty = self.difficulty.hardrock_offsets(hardrock_offsets);
+
+        self
+    }
+
+    /// Simulate the catcher's path.
+    pub fn generate(&self) -> Result<CatchAutoplayPath, ConvertError> {
+        let mods = self.difficulty.get_mods();
+        let map = self.map.convert_ref(GameMode::Catch, mods)?;
+
+        let cs = map.attributes().difficulty(&self.difficulty).build().cs as f32;
+
+        let objects = convert_objects(
+            &map,
+            &mut ObjectCountBuilder::new_regular(usize::MAX),
+            mods.reflection(),
+            self.difficulty.get_hardrock_offsets(),
+            cs,
+        );
+
+        let half_catch_width = Catcher::calculate_catch_width(cs) / 2.0;
+
+        Ok(simulate(&objects, half_catch_width))
+    }
+}
+
+/// The path of a catcher as simulated by [`CatchAutoplay`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CatchAutoplayPath {
+    /// Each fruit and droplet sorted by time alongside the catcher's
+    /// position.
+    ///
+    /// Tiny droplets and bananas are not included.
+    pub objects: Vec<CatchAutoplayObject>,
+}
+
+impl CatchAutoplayPath {
+    /// Whether every fruit and droplet can be caught.
+    pub fn is_catchable(&self) -> bool {
+        self.objects.iter().all(|h| h.caught)
+    }
+
+    /// All fruits and droplets that cannot be caught.
+    pub fn uncatchable(&self) -> impl Iterator<Item = &CatchAutoplayObject> {
+        self.objects.iter().filter(|h| !h.caught)
+    }
+
+    /// Replay frames of the catcher's path.
+    ///
+    /// Frames contain the catcher's position as `x` and [`ReplayFrame::M1`]
+    /// while dashing.
+    pub fn frames(&self) -> Vec<ReplayFrame> {
+        let mut frames = Vec::with_capacity(self.objects.len() * 2);
+        let mut prev_x = None;
+
+        for h in self.objects.iter() {
+            let keys = match h.movement {
+                CatcherMovement::Walk => 0,
+                CatcherMovement::Dash | CatcherMovement::HyperDash => ReplayFrame::M1,
+            };
+
+            let frame = |time: f64, x: f32| ReplayFrame {
+                time,
+                x,
+                y: 0.0,
+                keys,
+            };
+
+            if let Some(prev_x) = prev_x.filter(|_| h.movement_start < h.start_time) {
+                frames.push(frame(h.movement_start, prev_x));
+            }
+
+            frames.push(frame(h.start_time, h.catcher_x));
+            prev_x = Some(h.catcher_x);
+        }
+
+        frames
+    }
+}
+
+/// A fruit or droplet and how the catcher reaches it.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CatchAutoplayObject {
+    pub start_time: f64,
+    /// The object's position including HR offsets.
+    pub x: f32,
+    /// Whether the object is a droplet rather than a fruit.
+    pub is_droplet: bool,
+    /// Whether catching the object triggers a hyperdash.
+    pub hyper_dash: bool,
+    /// The catcher's position when the object arrives.
+    pub catcher_x: f32,
+    /// The time at which the catcher starts moving towards the object.
+    pub movement_start: f64,
+    /// How the catcher moves from the previous object to this one.
+    pub movement: CatcherMovement,
+    /// Whether the catcher catches the object.
+    pub caught: bool,
+}
+
+/// The speed at which the catcher moves.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CatcherMovement {
+    Walk,
+    Dash,
+    HyperDash,
+}
+
+/// Positions that the catcher can be at when an object arrives.
+#[derive(Copy, Clone)]
+struct Bounds {
+    min: f32,
+    max: f32,
+    caught: bool,
+    /// Whether the catcher hyperdashes to the next object.
+    hyper_dash: bool,
+}
+
+fn simulate(objects: &[PalpableObject], half_catch_width: f32) -> CatchAutoplayPath {
+    let bounds = bounds(objects, half_catch_width);
+    let positions = positions(objects, &bounds);
+
+    let mut prev: Option<(f64, f32, bool)> = None;
+
+    let objects = objects
+        .iter()
+        .zip(bounds)
+        .zip(positions)
+        .map(|((h, bounds), catcher_x)| {
+            let (movement, movement_start) = match prev {
+                Some((prev_time, prev_x, hyper_dash)) => {
+                    let dist = f64::from((catcher_x - prev_x).abs());
+                    let time = h.start_time - prev_time;
+                    let walk_time = dist / Catcher::BASE_WALK_SPEED;
+                    let dash_time = dist / Catcher::BASE_SPEED;
+
+                    if walk_time <= time + f64::from(EPSILON) {
+                        (CatcherMovement::Walk, h.start_time - walk_time)
+                    } else if dash_time <= time + f64::from(EPSILON) || !hyper_dash {
+                        (CatcherMovement::Dash, h.start_time - dash_time.min(time))
+                    } else {
+                        (CatcherMovement::HyperDash, prev_time)
+                    }
+                }
+                None => (CatcherMovement::Walk, h.start_time),
+            };
+
+            prev = Some((h.start_time, catcher_x, bounds.hyper_dash));
+
+            CatchAutoplayObject {
+                start_time: h.start_time,
+                x: h.effective_x(),
+                is_droplet: h.is_droplet,
+                hyper_dash: h.hyper_dash,
+                catcher_x,
+                movement_start,
+                movement,
+                caught: bounds.caught,
+            }
+        })
+        .collect();
+
+    CatchAutoplayPath { objects }
+}
+
+/// The distance that the catcher can dash between two points in time.
+fn reach(start_time: f64, end_time: f64) -> f32 {
+    ((end_time - start_time) * Catcher::BASE_SPEED) as f32
+}
+
+/// Narrows down the catcher's possible positions from the first to the last
+/// object.
+fn bounds(objects: &[PalpableObject], half_catch_width: f32) -> Vec<Bounds> {
+    let mut bounds = Vec::with_capacity(objects.len());
+    let mut prev: Option<(f64, Bounds)> = None;
+
+    for h in objects {
+        let (min, max) = match prev {
+            // * Hyperdashes reach their target in time unless both objects
+            // * appear simultaneously.
+            Some((prev_time, prev)) if !prev.hyper_dash || h.start_time <= prev_time => {
+                let reach = reach(prev_time, h.start_time);
+
+                (
+                    (prev.min - reach).max(0.0),
+                    (prev.max + reach).min(PLAYFIELD_WIDTH),
+                )
+            }
+            Some(_) | None => (0.0, PLAYFIELD_WIDTH),
+        };
+
+        let x = h.effective_x();
+        let catch_min = min.max(x - half_catch_width);
+        let catch_max = max.min(x + half_catch_width);
+
+        let curr = if catch_min <= catch_max + EPSILON {
+            Bounds {
+                min: catch_min.min(catch_max),
+                max: catch_max,
+                caught: true,
+                hyper_dash: h.hyper_dash,
+            }
+        } else {
+            Bounds {
+                min,
+                max,
+                caught: false,
+                hyper_dash: false,
+            }
+        };
+
+        bounds.push(curr);
+        prev = Some((h.start_time, curr));
+    }
+
+    bounds
+}
+
+/// Picks the catcher's position for each object from the last to the first
+/// object, staying as close to the objects' center as possible.
+fn positions(objects: &[PalpableObject], bounds: &[Bounds]) -> Vec<f32> {
+    let mut positions = vec![0.0; objects.len()];
+    let mut next: Option<(f64, f32)> = None;
+
+    for ((h, bounds), pos) in objects.iter().zip(bounds).zip(positions.iter_mut()).rev() {
+        let mut min = bounds.min;
+        let mut max = bounds.max;
+
+        let constraint =
+            next.filter(|(next_time, _)| !bounds.hyper_dash || *next_time <= h.start_time);
+
+        if let Some((next_time, next_pos)) = constraint {
+            let reach = reach(h.start_time, next_time);
+            min = min.max(next_pos - reach);
+            max = max.min(next_pos + reach);
+        }
+
+        *pos = h.effective_x().max(min).min(max);
+        next = Some((h.start_time, *pos));
+    }
+
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::util::Pos;
+
+    use crate::model::{beatmap::BeatmapBuilder, control_point::TimingPoint};
+
+    use super::*;
+
+    #[test]
+    fn catchable() {
+        let map = Beatmap::from_path("./resources/2118524.osu").unwrap();
+
+        for mods in [0, 16, 64] {
+            let path = CatchAutoplay::new(&map).mods(mods).generate().unwrap();
+
+            assert!(path.is_catchable(), "Mods: {mods}");
+            assert!(path
+                .objects
+                .iter()
+                .all(|h| (h.catcher_x - h.x).abs() <= Catcher::calculate_catch_width(0.0) / 2.0));
+
+            let frames = path.frames();
+            assert!(frames.windows(2).all(|w| w[0].time <= w[1].time));
+        }
+    }
+
+    #[test]
+    fn simultaneous_fruits() {
+        let map = BeatmapBuilder::new()
+            .mode(GameMode::Catch)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .circle(Pos::new(64.0, 192.0), 1000.0)
+            .circle(Pos::new(448.0, 192.0), 1000.0)
+            .circle(Pos::new(448.0, 192.0), 1500.0)
+            .build()
+            .unwrap();
+
+        let path = CatchAutoplay::new(&map).generate().unwrap();
+
+        assert_eq!(path.objects.len(), 3);
+        assert_eq!(path.uncatchable().count(), 1);
+        assert!(path.objects[2].caught);
+    }
+}
@@ -4,6 +4,7 @@ const AREA_CATCHER_SIZE: f32 = 106.75;
 
 impl Catcher {
     pub const BASE_SPEED: f64 = 1.0;
+    pub const BASE_WALK_SPEED: f64 = 0.5;
     pub const ALLOWED_CATCH_RANGE: f32 = 0.8;
 
     pub fn calculate_catch_width(cs: f32) -> f32 {
@@ -127,7 +127,10 @@ impl Iterator for ObjectIter<'_> {
             ObjectIterState::JuiceStream(ref mut stream) => stream
                 .nested_objects
                 .find(|nested| !matches!(nested.kind, NestedJuiceStreamObjectKind::TinyDroplet))
-                .map(|nested| PalpableObject::new(nested.pos, 0.0, nested.start_time)),
+                .map(|nested| PalpableObject {
+                    is_droplet: matches!(nested.kind, NestedJuiceStreamObjectKind::Droplet),
+                    ..PalpableObject::new(nested.pos, 0.0, nested.start_time)
+                }),
             ObjectIterState::BananaShower(_) => None,
         }
     }
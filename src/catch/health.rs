@@ -0,0 +1,57 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+
+use crate::{
+    any::health::{HealthJudgement, JudgementKind},
+    model::hit_object::HitObjectKind,
+    Beatmap,
+};
+
+use super::{
+    attributes::ObjectCountBuilder,
+    object::juice_stream::{JuiceStream, JuiceStreamBufs, NestedJuiceStreamObjectKind},
+    PLAYFIELD_WIDTH,
+};
+
+pub fn health_judgements(map: &Beatmap) -> Vec<HealthJudgement> {
+    let mut bufs = JuiceStreamBufs {
+        curve: CurveBuffers::default(),
+        nested_objects: Vec::new(),
+        ticks: Vec::new(),
+    };
+
+    let mut count = ObjectCountBuilder::new_regular(usize::MAX);
+    let mut judgements = Vec::with_capacity(map.hit_objects.len());
+
+    for (i, h) in map.hit_objects.iter().enumerate() {
+        match h.kind {
+            HitObjectKind::Circle => {
+                judgements.push(HealthJudgement::new(h.start_time, i, JudgementKind::Object));
+            }
+            HitObjectKind::Slider(ref slider) => {
+                let effective_x = h.pos.x.clamp(0.0, PLAYFIELD_WIDTH);
+                let stream = JuiceStream::new(
+                    effective_x,
+                    h.start_time,
+                    slider,
+                    map,
+                    &mut count,
+                    &mut bufs,
+                );
+
+                for nested in stream.nested_objects {
+                    let kind = match nested.kind {
+                        NestedJuiceStreamObjectKind::Fruit => JudgementKind::Object,
+                        NestedJuiceStreamObjectKind::Droplet => JudgementKind::LargeTick,
+                        NestedJuiceStreamObjectKind::TinyDroplet => JudgementKind::SmallTick,
+                    };
+
+                    judgements.push(HealthJudgement::new(nested.start_time, i, kind));
+                }
+            }
+            // Bananas do not affect health
+            HitObjectKind::Spinner(_) | HitObjectKind::Hold(_) => {}
+        }
+    }
+
+    judgements
+}
@@ -0,0 +1,129 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+use rosu_mods::GameModsLegacy;
+
+use crate::{
+    any::{legacy_score::combo_score, LegacyObjectScore, LegacyScoreAttributes},
+    model::{
+        hit_object::{HitObjectKind, HoldNote, Spinner},
+        mods::GameMods,
+    },
+    Beatmap,
+};
+
+use super::{
+    attributes::ObjectCountBuilder,
+    object::{
+        banana_shower::BananaShower,
+        juice_stream::{JuiceStream, JuiceStreamBufs, NestedJuiceStreamObjectKind},
+    },
+    PLAYFIELD_WIDTH,
+};
+
+pub fn legacy_score(
+    map: &Beatmap,
+    mods: &GameMods,
+    difficulty_multiplier: u32,
+) -> LegacyScoreAttributes {
+    let mod_multiplier = mod_multiplier(mods);
+    let score_multiplier = f64::from(difficulty_multiplier) * mod_multiplier;
+
+    let mut bufs = JuiceStreamBufs {
+        curve: CurveBuffers::default(),
+        nested_objects: Vec::new(),
+        ticks: Vec::new(),
+    };
+
+    let mut count = ObjectCountBuilder::new_regular(usize::MAX);
+    let mut combo = 0;
+
+    let fruit = |score: &mut LegacyObjectScore, combo: &mut u32| {
+        score.accuracy_score += 300;
+        score.combo_score += combo_score(*combo, 300, score_multiplier);
+        *combo += 1;
+    };
+
+    let objects = map
+        .hit_objects
+        .iter()
+        .map(|h| {
+            let mut score = LegacyObjectScore::new(h.start_time);
+
+            match h.kind {
+                HitObjectKind::Circle => fruit(&mut score, &mut combo),
+                HitObjectKind::Slider(ref slider) => {
+                    let effective_x = h.pos.x.clamp(0.0, PLAYFIELD_WIDTH);
+                    let stream = JuiceStream::new(
+                        effective_x,
+                        h.start_time,
+                        slider,
+                        map,
+                        &mut count,
+                        &mut bufs,
+                    );
+
+                    for nested in stream.nested_objects {
+                        match nested.kind {
+                            NestedJuiceStreamObjectKind::Fruit => fruit(&mut score, &mut combo),
+                            NestedJuiceStreamObjectKind::Droplet => {
+                                score.accuracy_score += 100;
+                                combo += 1;
+                            }
+                            NestedJuiceStreamObjectKind::TinyDroplet => {
+                                score.accuracy_score += 10;
+                            }
+                        }
+                    }
+                }
+                HitObjectKind::Spinner(Spinner { duration })
+                | HitObjectKind::Hold(HoldNote { duration }) => {
+                    let shower = BananaShower::new(h.start_time, h.start_time + duration);
+                    score.bonus_score += 1100 * shower.n_bananas as u32;
+                }
+            }
+
+            score
+        })
+        .collect();
+
+    LegacyScoreAttributes::new(difficulty_multiplier, mod_multiplier, combo, objects)
+}
+
+fn mod_multiplier(mods: &GameMods) -> f64 {
+    let mods = GameModsLegacy::from_bits(mods.bits());
+
+    if mods.contains(GameModsLegacy::Relax) {
+        return 0.0;
+    }
+
+    let mut multiplier = 1.0;
+
+    if mods.contains(GameModsLegacy::NoFail) && !mods.contains(GameModsLegacy::ScoreV2) {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::Easy) {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::HalfTime) {
+        multiplier *= 0.3;
+    }
+
+    if mods.contains(GameModsLegacy::Hidden) {
+        multiplier *= 1.06;
+    }
+
+    if mods.contains(GameModsLegacy::HardRock) {
+        multiplier *= 1.12;
+    }
+
+    if mods.contains(GameModsLegacy::DoubleTime) {
+        multiplier *= 1.06;
+    }
+
+    if mods.contains(GameModsLegacy::Flashlight) {
+        multiplier *= 1.12;
+    }
+
+    multiplier
+}
@@ -8,8 +8,11 @@ use crate::{
     Difficulty,
 };
 
+pub(crate) use self::{health::health_judgements, legacy_score::legacy_score};
+
 pub use self::{
     attributes::{CatchDifficultyAttributes, CatchPerformanceAttributes},
+    autoplay::{CatchAutoplay, CatchAutoplayObject, CatchAutoplayPath, CatcherMovement},
     difficulty::gradual::CatchGradualDifficulty,
     performance::{gradual::CatchGradualPerformance, CatchPerformance},
     score_state::CatchScoreState,
@@ -17,9 +20,12 @@ pub use self::{
 };
 
 mod attributes;
+mod autoplay;
 mod catcher;
 mod convert;
 mod difficulty;
+mod health;
+mod legacy_score;
 mod object;
 mod performance;
 mod score_state;
@@ -6,6 +6,7 @@ pub struct PalpableObject {
     pub start_time: f64,
     pub dist_to_hyper_dash: f32,
     pub hyper_dash: bool,
+    pub is_droplet: bool,
 }
 
 impl PalpableObject {
@@ -16,6 +17,7 @@ impl PalpableObject {
             start_time,
             dist_to_hyper_dash: 0.0,
             hyper_dash: false,
+            is_droplet: false,
         }
     }
 
@@ -8,7 +8,7 @@ use crate::{
     any::{Difficulty, IntoModePerformance, IntoPerformance},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{map_or_attrs::MapOrAttrs, solve},
     Performance,
 };
 
@@ -427,6 +427,53 @@ impl<'map> CatchPerformance<'map> {
         Ok(CatchPerformanceCalculator::new(attrs, self.difficulty.get_mods(), state).calculate())
     }
 
+    /// Find the minimum accuracy between `0.0` and `100.0` that still reaches
+    /// `target_pp` or `None` if even 100% accuracy falls short.
+    ///
+    /// Specified fruits, droplets, and tiny droplets are ignored. Since they
+    /// are generated from the accuracy, the result may be marginally above
+    /// the true minimum.
+    pub fn min_accuracy_for_pp(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        self.map_or_attrs.difficulty_attrs(&self.difficulty)?;
+        self.fruits = None;
+        self.droplets = None;
+        self.tiny_droplets = None;
+        self.tiny_droplet_misses = None;
+
+        solve::min_accuracy(target_pp, |acc| {
+            self.clone().accuracy(acc).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the maximum amount of misses that still reaches `target_pp` or
+    /// `None` if even no misses fall short.
+    pub fn max_misses_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .max_combo();
+
+        solve::max_value(target_pp, max, |misses| {
+            self.clone()
+                .misses(misses)
+                .calculate()
+                .map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the minimum combo that still reaches `target_pp` or `None` if
+    /// even a full combo falls short.
+    pub fn min_combo_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .max_combo();
+
+        solve::min_value(target_pp, max, |combo| {
+            self.clone().combo(combo).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Catch>) -> Self {
         Self {
             map_or_attrs,
@@ -474,6 +521,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for CatchPerformance<'map> {
             n100,
             n50,
             misses,
+            speed_deviation: _,
             hitresult_priority: _,
         } = osu;
 
@@ -121,6 +121,7 @@
 //! | ------------- | ------------------- | ------------
 //! | `default`     | No features enabled |
 //! | `raw_strains` | With this feature, internal strain values will be stored in a plain `Vec`. This introduces an out-of-memory risk on maliciously long maps (see [/b/3739922](https://osu.ppy.sh/b/3739922)), but comes with a ~5% gain in performance. |
+//! | `serde`       | Implements `serde::Serialize` and `serde::Deserialize` for attributes, score states, strains, configuration types such as `GameMods`, and `Beatmap` through its binary cache format. | [`serde`]
 //! | `sync`        | Some gradual calculation types can only be shared across threads if this feature is enabled. This feature adds a small performance penalty. |
 //! | `tracing`     | Any error encountered during beatmap decoding will be logged through `tracing::error`. If this feature is **not** enabled, errors will be ignored. | [`tracing`]
 //!
@@ -133,6 +134,7 @@
 //! [osu!]: https://osu.ppy.sh/home
 //! [osu!lazer]: https://github.com/ppy/osu
 //! [osu!tools]: https://github.com/ppy/osu-tools
+//! [`serde`]: https://docs.rs/serde
 //! [`tracing`]: https://docs.rs/tracing
 //! [rosu-pp-js]: https://github.com/MaxOhn/rosu-pp-js
 //! [rosu-pp-py]: https://github.com/MaxOhn/rosu-pp-py
@@ -153,7 +155,8 @@
     clippy::cast_sign_loss,
     clippy::explicit_iter_loop,
     clippy::similar_names,
-    clippy::cast_possible_wrap
+    clippy::cast_possible_wrap,
+    clippy::manual_midpoint
 )]
 
 #[doc(inline)]
@@ -232,6 +232,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_is_multiple_of)]
     fn next_and_nth() {
         let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
 
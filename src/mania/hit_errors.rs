@@ -0,0 +1,145 @@
+use std::iter::Peekable;
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{
+        beatmap::{MANIA_MEH, MANIA_MISS},
+        mode::ConvertError,
+        replay::ReplayFrame,
+    },
+    Beatmap, Difficulty,
+};
+
+use super::{
+    convert,
+    object::{ManiaObject, ObjectParams},
+};
+
+/// Match column presses of the frames to notes and hold note heads and return
+/// the offset of each successful hit, adjusted to the clock rate.
+///
+/// The pressed columns of a frame are stored as bitflags in its `x`
+/// coordinate.
+pub fn hit_errors(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    frames: &[ReplayFrame],
+) -> Result<Vec<f64>, ConvertError> {
+    let mods = difficulty.get_mods();
+    let mut map = map.convert_ref(GameMode::Mania, mods)?;
+
+    if mods.ho() {
+        convert::apply_hold_off_to_beatmap(map.to_mut());
+    }
+
+    if mods.invert() {
+        convert::apply_invert_to_beatmap(map.to_mut());
+    }
+
+    if let Some(seed) = mods.random_seed() {
+        convert::apply_random_to_beatmap(map.to_mut(), seed);
+    }
+
+    let clock_rate = difficulty.get_clock_rate();
+    let total_columns = map.cs.round_ties_even().max(1.0);
+    let mut params = ObjectParams::new(&map);
+
+    let mut columns: Vec<Vec<f64>> = vec![Vec::new(); total_columns as usize];
+
+    for h in map.hit_objects.iter() {
+        let h = ManiaObject::new(h, total_columns, &mut params);
+        columns[h.column].push(h.start_time);
+    }
+
+    let mut columns: Vec<Peekable<_>> = columns
+        .into_iter()
+        .map(|column| column.into_iter().peekable())
+        .collect();
+
+    let attrs = map.attributes().difficulty(difficulty);
+
+    // Hit windows in map time
+    let meh_window = f64::from(attrs.mania_hit_window(MANIA_MEH)) * clock_rate;
+    let miss_window = f64::from(attrs.mania_hit_window(MANIA_MISS)) * clock_rate;
+
+    let mut hit_errors = Vec::new();
+    let mut prev_keys = 0;
+
+    for frame in frames {
+        let keys = frame.x as u32;
+        let mut pressed = keys & !prev_keys;
+        prev_keys = keys;
+
+        while pressed != 0 {
+            let column = pressed.trailing_zeros() as usize;
+            pressed &= pressed - 1;
+
+            let Some(notes) = columns.get_mut(column) else {
+                continue;
+            };
+
+            // Notes whose hit window passed are missed
+            while notes
+                .next_if(|start_time| start_time + meh_window < frame.time)
+                .is_some()
+            {}
+
+            let Some(start_time) =
+                notes.next_if(|start_time| start_time - miss_window <= frame.time)
+            else {
+                continue;
+            };
+
+            let offset = frame.time - start_time;
+
+            // Early hits outside of the meh window are misses
+            if offset >= -meh_window {
+                hit_errors.push(offset / clock_rate);
+            }
+        }
+    }
+
+    Ok(hit_errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let total_columns = map.cs.round_ties_even();
+        let mut params = ObjectParams::new(&map);
+
+        let mut frames: Vec<_> = map
+            .hit_objects
+            .iter()
+            .map(|h| ManiaObject::new(h, total_columns, &mut params))
+            .flat_map(|h| {
+                let press = ReplayFrame {
+                    time: h.start_time - 3.0,
+                    x: (1 << h.column) as f32,
+                    y: 0.0,
+                    keys: 0,
+                };
+
+                let release = ReplayFrame {
+                    time: h.start_time - 2.0,
+                    x: 0.0,
+                    ..press
+                };
+
+                [press, release]
+            })
+            .collect();
+
+        frames.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        let hit_errors = hit_errors(&Difficulty::new(), &map, &frames).unwrap();
+
+        assert_eq!(hit_errors.len(), map.hit_objects.len());
+        assert!(hit_errors.iter().all(|offset| (offset + 3.0).abs() < 1e-9));
+    }
+}
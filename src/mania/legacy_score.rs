@@ -0,0 +1,166 @@
+use rosu_map::section::general::GameMode;
+use rosu_mods::GameModsLegacy;
+
+use crate::{
+    any::{LegacyObjectScore, LegacyScoreAttributes},
+    model::{
+        hit_object::{HitObjectKind, HoldNote, Spinner},
+        mods::GameMods,
+    },
+    Beatmap,
+};
+
+use super::convert::target_columns;
+
+/// osu!stable's maximum score of osu!mania maps without mods.
+const MAX_SCORE: f64 = 1_000_000.0;
+
+/// The max score is distributed across all judgements where hold notes have
+/// a judgement for both their head and their tail.
+pub fn legacy_score(original: &Beatmap, map: &Beatmap, mods: &GameMods) -> LegacyScoreAttributes {
+    let mod_multiplier = mod_multiplier(original, mods);
+    let max_score = (MAX_SCORE * mod_multiplier) as u64;
+
+    let mut max_combo = 0;
+
+    let judgements: Vec<_> = map
+        .hit_objects
+        .iter()
+        .map(|h| {
+            // Hold notes add a combo for every 100ms of their duration
+            let (judgements, combo) = match h.kind {
+                HitObjectKind::Hold(HoldNote { duration })
+                | HitObjectKind::Spinner(Spinner { duration }) => {
+                    (2, 1 + (duration / 100.0) as u32)
+                }
+                HitObjectKind::Circle | HitObjectKind::Slider(_) => (1, 1),
+            };
+
+            max_combo += combo;
+
+            (h.start_time, judgements)
+        })
+        .collect();
+
+    let total_judgements: u64 = judgements.iter().map(|(_, n)| n).sum();
+
+    let mut judged = 0;
+    let mut prev_score = 0;
+
+    let objects = judgements
+        .into_iter()
+        .map(|(start_time, n)| {
+            judged += n;
+            let curr_score = max_score * judged / total_judgements;
+
+            let score = LegacyObjectScore {
+                accuracy_score: (curr_score - prev_score) as u32,
+                ..LegacyObjectScore::new(start_time)
+            };
+
+            prev_score = curr_score;
+
+            score
+        })
+        .collect();
+
+    LegacyScoreAttributes::new(0, mod_multiplier, max_combo, objects)
+}
+
+/// Same as lazer's `ManiaLegacyScoreSimulator.GetLegacyScoreMultiplier`.
+fn mod_multiplier(original: &Beatmap, mods: &GameMods) -> f64 {
+    let legacy = GameModsLegacy::from_bits(mods.bits());
+
+    let mut multiplier = 1.0;
+
+    if legacy.contains(GameModsLegacy::NoFail) && !legacy.contains(GameModsLegacy::ScoreV2) {
+        multiplier *= 0.5;
+    }
+
+    if legacy.contains(GameModsLegacy::Easy) {
+        multiplier *= 0.5;
+    }
+
+    if legacy.contains(GameModsLegacy::HalfTime) {
+        multiplier *= 0.5;
+    }
+
+    // Key mods only apply to converts
+    if let Some(keys) = mods.mania_keys().filter(|_| original.mode == GameMode::Osu) {
+        let original_keys = target_columns(original, &GameMods::DEFAULT);
+
+        if keys > original_keys {
+            multiplier *= 0.9;
+        } else if keys < original_keys {
+            multiplier *= 0.9 - 0.04 * f64::from(original_keys - keys);
+        }
+    }
+
+    multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::any::LegacyScoreSimulator;
+
+    use super::*;
+
+    const K4: u32 = 1 << 15;
+    const K9: u32 = 1 << 24;
+
+    #[test]
+    fn key_mods() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let original_keys = target_columns(&map, &GameMods::DEFAULT);
+
+        assert!((original_keys - 7.0).abs() < f32::EPSILON);
+
+        let simulate = |mods: u32| {
+            LegacyScoreSimulator::new(&map)
+                .mode(GameMode::Mania)
+                .mods(mods)
+                .simulate()
+                .unwrap()
+        };
+
+        // Fewer keys: 0.9 - 0.04 * (7 - 4)
+        let fewer = simulate(K4);
+        assert!((fewer.mod_multiplier - 0.78).abs() < 1e-9);
+        assert_eq!(fewer.max_score(), 780_000);
+
+        // More keys
+        assert!((simulate(K9).mod_multiplier - 0.9).abs() < 1e-9);
+
+        // Key mods don't affect osu!mania maps
+        let mania = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let attrs = LegacyScoreSimulator::new(&mania)
+            .mods(K9)
+            .simulate()
+            .unwrap();
+        assert!((attrs.mod_multiplier - 1.0).abs() < f64::EPSILON);
+
+        // NF and HT
+        let attrs = LegacyScoreSimulator::new(&mania)
+            .mods(1 + 256)
+            .simulate()
+            .unwrap();
+        assert_eq!(attrs.max_score(), 250_000);
+    }
+
+    #[test]
+    fn distribution() {
+        let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
+        let attrs = LegacyScoreSimulator::new(&map).simulate().unwrap();
+
+        let n_holds = map.hit_objects.iter().filter(|h| !h.is_circle()).count();
+        let total_judgements = (map.hit_objects.len() + n_holds) as u32;
+
+        let first = &attrs.objects[0];
+        let expected =
+            1_000_000 / total_judgements * if map.hit_objects[0].is_circle() { 1 } else { 2 };
+
+        assert_eq!(attrs.objects.len(), map.hit_objects.len());
+        assert!(first.accuracy_score.abs_diff(expected) <= 1);
+        assert_eq!(attrs.max_score(), 1_000_000);
+    }
+}
@@ -8,6 +8,8 @@ use crate::{
     Difficulty, GameMods,
 };
 
+pub(crate) use self::{hit_errors::hit_errors, legacy_score::legacy_score};
+
 pub use self::{
     attributes::{ManiaDifficultyAttributes, ManiaPerformanceAttributes},
     difficulty::gradual::ManiaGradualDifficulty,
@@ -19,6 +21,8 @@ pub use self::{
 mod attributes;
 mod convert;
 mod difficulty;
+mod hit_errors;
+mod legacy_score;
 mod object;
 mod performance;
 mod score_state;
@@ -122,6 +122,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::manual_is_multiple_of)]
     fn next_and_nth() {
         let map = Beatmap::from_path("./resources/1638954.osu").unwrap();
 
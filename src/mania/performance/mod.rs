@@ -8,7 +8,7 @@ use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{map_or_attrs::MapOrAttrs, solve},
     Performance,
 };
 
@@ -18,6 +18,9 @@ mod calculator;
 pub mod gradual;
 
 /// Performance calculator on osu!mania maps.
+///
+/// Unlike osu! and osu!catch, there is no `min_combo_for_pp` because combo
+/// does not affect osu!mania performance and can't be specified.
 #[derive(Clone, Debug, PartialEq)]
 #[must_use]
 pub struct ManiaPerformance<'map> {
@@ -617,6 +620,41 @@ impl<'map> ManiaPerformance<'map> {
         Ok(ManiaPerformanceCalculator::new(attrs, self.difficulty.get_mods(), state).calculate())
     }
 
+    /// Find the minimum accuracy between `0.0` and `100.0` that still reaches
+    /// `target_pp` or `None` if even 100% accuracy falls short.
+    ///
+    /// Specified `n320`, `n300`, `n200`, `n100`, and `n50` are ignored. Since
+    /// they are generated from the accuracy, the result may be marginally
+    /// above the true minimum.
+    pub fn min_accuracy_for_pp(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        self.map_or_attrs.difficulty_attrs(&self.difficulty)?;
+        self.n320 = None;
+        self.n300 = None;
+        self.n200 = None;
+        self.n100 = None;
+        self.n50 = None;
+
+        solve::min_accuracy(target_pp, |acc| {
+            self.clone().accuracy(acc).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the maximum amount of misses that still reaches `target_pp` or
+    /// `None` if even no misses fall short.
+    pub fn max_misses_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .n_objects();
+
+        solve::max_value(target_pp, max, |misses| {
+            self.clone()
+                .misses(misses)
+                .calculate()
+                .map(|attrs| attrs.pp)
+        })
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Mania>) -> Self {
         Self {
             map_or_attrs,
@@ -665,6 +703,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for ManiaPerformance<'map> {
             n100,
             n50,
             misses,
+            speed_deviation: _,
             hitresult_priority,
         } = osu;
 
@@ -737,6 +776,7 @@ mod tests {
 
     /// Creates a [`rosu_mods::GameMods`] instance and inserts `CL` if `classic`
     /// is true.
+    #[allow(clippy::default_trait_access)]
     fn mods(classic: bool) -> rosu_mods::GameMods {
         if classic {
             let mut mods = rosu_mods::GameMods::new();
@@ -753,7 +793,7 @@ mod tests {
     ///
     /// Very slow but accurate. Only slight optimizations have been applied so
     /// that it doesn't run unreasonably long.
-    #[allow(clippy::too_many_arguments, clippy::too_many_lines)]
+    #[allow(clippy::too_many_arguments, clippy::too_many_lines, clippy::float_cmp)]
     fn brute_force_best(
         classic: bool,
         acc: f64,
@@ -1108,6 +1148,11 @@ mod tests {
     }
 
     #[test]
+    #[allow(
+        clippy::doc_markdown,
+        clippy::items_after_statements,
+        clippy::uninlined_format_args
+    )]
     fn rng_mania_hitresults() {
         /// Generates a random seed by measuring the time it takes to calculate
         /// all primes up to 10_000.
@@ -1166,6 +1211,7 @@ mod tests {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn exec_mania_hitresults(
         classic: bool,
         acc: f64,
@@ -11,6 +11,7 @@ use crate::{
 ///
 /// Suitable to plot the difficulty of a map over time.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ManiaStrains {
     /// Strain peaks of the strain skill.
     pub strains: Vec<f64>,
@@ -6,6 +6,7 @@ use super::Beatmap;
 
 /// Summary struct for a [`Beatmap`]'s attributes.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeatmapAttributes {
     /// The approach rate.
     pub ar: f64,
@@ -23,6 +24,7 @@ pub struct BeatmapAttributes {
 
 /// AR and OD hit windows
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HitWindows {
     /// Hit window for approach rate i.e. `TimePreempt` in milliseconds.
     pub ar: f64,
@@ -94,6 +96,29 @@ const AR_WINDOWS: GameModeHitWindows = GameModeHitWindows {
     max: 450.0,
 };
 
+/// An osu!mania hit window at OD 10 and for converted maps with an OD of at
+/// most 4. Converted maps with a higher OD use the former.
+#[derive(Copy, Clone)]
+pub(crate) struct ManiaHitWindow {
+    max: f32,
+    convert_low_od: f32,
+}
+
+const MANIA_GREAT: ManiaHitWindow = ManiaHitWindow {
+    max: 34.0,
+    convert_low_od: 47.0,
+};
+
+pub(crate) const MANIA_MEH: ManiaHitWindow = ManiaHitWindow {
+    max: 121.0,
+    convert_low_od: 121.0,
+};
+
+pub(crate) const MANIA_MISS: ManiaHitWindow = ManiaHitWindow {
+    max: 158.0,
+    convert_low_od: 158.0,
+};
+
 impl BeatmapAttributesBuilder {
     /// Create a new [`BeatmapAttributesBuilder`].
     ///
@@ -288,22 +313,7 @@ impl BeatmapAttributesBuilder {
                 (great, Some(ok), None)
             }
             GameMode::Mania => {
-                let mut value = if !self.is_convert {
-                    34.0 + 3.0 * (10.0 - self.od.value(mods, GameMods::od)).clamp(0.0, 10.0)
-                } else if self.od.value(mods, GameMods::od).round_ties_even() > 4.0 {
-                    34.0
-                } else {
-                    47.0
-                };
-
-                if !self.od.with_mods() {
-                    if mods.hr() {
-                        value /= 1.4;
-                    } else if mods.ez() {
-                        value *= 1.4;
-                    }
-                }
-
+                let value = self.mania_hit_window(MANIA_GREAT);
                 let great = ((f64::from(value) * od_clock_rate).floor() / od_clock_rate).ceil();
 
                 (great, None, None)
@@ -318,6 +328,30 @@ impl BeatmapAttributesBuilder {
         }
     }
 
+    /// Calculate an osu!mania hit window in milliseconds.
+    pub(crate) fn mania_hit_window(&self, window: ManiaHitWindow) -> f32 {
+        let mods = &self.mods;
+        let od = self.od.value(mods, GameMods::od);
+
+        let mut value = if !self.is_convert {
+            window.max + 3.0 * (10.0 - od).clamp(0.0, 10.0)
+        } else if od.round_ties_even() > 4.0 {
+            window.max
+        } else {
+            window.convert_low_od
+        };
+
+        if !self.od.with_mods() {
+            if mods.hr() {
+                value /= 1.4;
+            } else if mods.ez() {
+                value *= 1.4;
+            }
+        }
+
+        value
+    }
+
     /// Calculate the [`BeatmapAttributes`].
     pub fn build(&self) -> BeatmapAttributes {
         let mods = &self.mods;
@@ -518,4 +552,27 @@ mod tests {
 
         assert_eq!(attrs.ar, 8.5);
     }
+
+    #[test]
+    fn mania_hit_windows() {
+        let builder = BeatmapAttributesBuilder::new()
+            .mode(GameMode::Mania, false)
+            .od(8.0, false);
+
+        assert_eq!(builder.mania_hit_window(MANIA_MEH), 127.0);
+        assert_eq!(
+            builder.clone().mods(16).mania_hit_window(MANIA_MISS),
+            164.0 / 1.4
+        );
+
+        // OD that already considers mods
+        let with_mods = builder.clone().od(8.0, true).mods(16);
+        assert_eq!(with_mods.mania_hit_window(MANIA_MEH), 127.0);
+
+        // Converted maps have fixed hit windows
+        let convert = builder.mode(GameMode::Mania, true);
+        assert_eq!(convert.mania_hit_window(MANIA_GREAT), 34.0);
+        assert_eq!(convert.mania_hit_window(MANIA_MEH), 121.0);
+        assert_eq!(convert.od(4.0, false).mania_hit_window(MANIA_GREAT), 47.0);
+    }
 }
@@ -1,15 +1,9 @@
 use std::collections::HashMap;
 
-use crate::model::{control_point::TimingPoint, hit_object::HitObject};
-
-pub fn bpm(last_hit_object: Option<&HitObject>, timing_points: &[TimingPoint]) -> f64 {
-    // This is incorrect if the last object is a slider since there
-    // is no reasonable way to get the slider end time at this point.
-    let last_time = last_hit_object
-        .map(HitObject::end_time)
-        .or_else(|| timing_points.last().map(|t| t.time))
-        .unwrap_or(0.0);
+use crate::model::control_point::TimingPoint;
 
+/// The most common bpm until `last_time`.
+pub fn bpm(last_time: f64, timing_points: &[TimingPoint]) -> f64 {
     let mut bpm_points = BeatLenDuration::new(last_time);
 
     // * osu-stable forced the first control point to start at 0.
@@ -0,0 +1,636 @@
+use std::{error, fmt};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    model::{
+        control_point::{DifficultyPoint, EffectPoint, TimingPoint},
+        hit_object::{
+            HitObject, HitObjectKind, HitSoundType, HoldNote, PathControlPoint, Pos, Slider,
+            Spinner,
+        },
+    },
+    util::sort,
+};
+
+use super::{Beatmap, BeatmapMetadata, BreakPeriod};
+
+/// A builder for a [`Beatmap`].
+///
+/// Hitobjects and control points may be added in any order; they will be
+/// sorted the same way as when decoding a `.osu` file.
+///
+/// # Example
+///
+/// ```
+/// use rosu_pp::model::beatmap::BeatmapBuilder;
+/// use rosu_pp::model::control_point::TimingPoint;
+/// use rosu_pp::model::hit_object::Pos;
+///
+/// let map = BeatmapBuilder::new()
+///     .od(8.0)
+///     .timing_point(TimingPoint::new(0.0, 500.0))
+///     .circle(Pos::new(256.0, 192.0), 1000.0)
+///     .circle(Pos::new(128.0, 192.0), 500.0)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(map.hit_objects[0].start_time, 500.0);
+/// assert_eq!(map.hit_sounds.len(), 2);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct BeatmapBuilder {
+    version: i32,
+    mode: GameMode,
+    stack_leniency: f32,
+    metadata: Option<Box<BeatmapMetadata>>,
+    ar: f32,
+    cs: f32,
+    hp: f32,
+    od: f32,
+    slider_multiplier: f64,
+    slider_tick_rate: f64,
+    breaks: Vec<BreakPeriod>,
+    timing_points: Vec<TimingPoint>,
+    difficulty_points: Vec<DifficultyPoint>,
+    effect_points: Vec<EffectPoint>,
+    hit_objects: Vec<(HitObject, HitSoundType)>,
+}
+
+impl BeatmapBuilder {
+    /// Create a new [`BeatmapBuilder`].
+    ///
+    /// All values are the same as for [`Beatmap::default`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Specify the `.osu` file format version.
+    pub const fn version(mut self, version: i32) -> Self {
+        self.version = version;
+
+        self
+    }
+
+    /// Specify the [`GameMode`].
+    pub const fn mode(mut self, mode: GameMode) -> Self {
+        self.mode = mode;
+
+        self
+    }
+
+    /// Specify the stack leniency.
+    pub const fn stack_leniency(mut self, stack_leniency: f32) -> Self {
+        self.stack_leniency = stack_leniency;
+
+        self
+    }
+
+    /// Specify the [`BeatmapMetadata`].
+    pub fn metadata(mut self, metadata: BeatmapMetadata) -> Self {
+        self.metadata = Some(Box::new(metadata));
+
+        self
+    }
+
+    /// Specify the approach rate.
+    pub const fn ar(mut self, ar: f32) -> Self {
+        self.ar = ar;
+
+        self
+    }
+
+    /// Specify the circle size.
+    pub const fn cs(mut self, cs: f32) -> Self {
+        self.cs = cs;
+
+        self
+    }
+
+    /// Specify the drain rate.
+    pub const fn hp(mut self, hp: f32) -> Self {
+        self.hp = hp;
+
+        self
+    }
+
+    /// Specify the overall difficulty.
+    pub const fn od(mut self, od: f32) -> Self {
+        self.od = od;
+
+        self
+    }
+
+    /// Specify the slider multiplier.
+    pub const fn slider_multiplier(mut self, slider_multiplier: f64) -> Self {
+        self.slider_multiplier = slider_multiplier;
+
+        self
+    }
+
+    /// Specify the slider tick rate.
+    pub const fn slider_tick_rate(mut self, slider_tick_rate: f64) -> Self {
+        self.slider_tick_rate = slider_tick_rate;
+
+        self
+    }
+
+    /// Add a [`BreakPeriod`].
+    pub fn break_period(mut self, break_period: BreakPeriod) -> Self {
+        self.breaks.push(break_period);
+
+        self
+    }
+
+    /// Add a [`TimingPoint`].
+    pub fn timing_point(mut self, point: TimingPoint) -> Self {
+        self.timing_points.push(point);
+
+        self
+    }
+
+    /// Add a [`DifficultyPoint`].
+    pub fn difficulty_point(mut self, point: DifficultyPoint) -> Self {
+        self.difficulty_points.push(point);
+
+        self
+    }
+
+    /// Add an [`EffectPoint`].
+    pub fn effect_point(mut self, point: EffectPoint) -> Self {
+        self.effect_points.push(point);
+
+        self
+    }
+
+    /// Add a circle.
+    pub fn circle(self, pos: Pos, start_time: f64) -> Self {
+        self.hit_object(
+            HitObject {
+                pos,
+                start_time,
+                kind: HitObjectKind::Circle,
+            },
+            HitSoundType::default(),
+        )
+    }
+
+    /// Add a slider.
+    ///
+    /// `control_points` are relative to `pos` and `repeats` is the amount of
+    /// times the slider turns around. If `expected_dist` is `None`, the
+    /// length of the slider will be determined by its control points.
+    pub fn slider(
+        self,
+        pos: Pos,
+        start_time: f64,
+        control_points: Vec<PathControlPoint>,
+        repeats: usize,
+        expected_dist: Option<f64>,
+    ) -> Self {
+        let slider = Slider {
+            expected_dist,
+            repeats,
+            control_points: control_points.into_boxed_slice(),
+            node_sounds: vec![HitSoundType::default(); repeats + 2].into_boxed_slice(),
+        };
+
+        self.hit_object(
+            HitObject {
+                pos,
+                start_time,
+                kind: HitObjectKind::Slider(slider),
+            },
+            HitSoundType::default(),
+        )
+    }
+
+    /// Add a spinner.
+    pub fn spinner(self, pos: Pos, start_time: f64, duration: f64) -> Self {
+        self.hit_object(
+            HitObject {
+                pos,
+                start_time,
+                kind: HitObjectKind::Spinner(Spinner { duration }),
+            },
+            HitSoundType::default(),
+        )
+    }
+
+    /// Add a hold note.
+    pub fn hold_note(self, pos: Pos, start_time: f64, duration: f64) -> Self {
+        self.hit_object(
+            HitObject {
+                pos,
+                start_time,
+                kind: HitObjectKind::Hold(HoldNote { duration }),
+            },
+            HitSoundType::default(),
+        )
+    }
+
+    /// Add a [`HitObject`] alongside its [`HitSoundType`].
+    pub fn hit_object(mut self, hit_object: HitObject, hit_sound: HitSoundType) -> Self {
+        self.hit_objects.push((hit_object, hit_sound));
+
+        self
+    }
+
+    /// Validate all values, sort hitobjects and control points, and create
+    /// the [`Beatmap`].
+    pub fn build(self) -> Result<Beatmap, BuildBeatmapError> {
+        self.validate()?;
+
+        let Self {
+            version,
+            mode,
+            stack_leniency,
+            metadata,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            mut breaks,
+            mut timing_points,
+            mut difficulty_points,
+            mut effect_points,
+            mut hit_objects,
+        } = self;
+
+        breaks.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        timing_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+        difficulty_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+        effect_points.sort_by(|a, b| a.time.total_cmp(&b.time));
+        hit_objects.sort_by(|(a, _), (b, _)| a.start_time.total_cmp(&b.start_time));
+
+        if mode == GameMode::Mania {
+            sort::osu_legacy_by_key(&mut hit_objects, |(h, _)| h.start_time);
+        }
+
+        let (hit_objects, hit_sounds) = hit_objects.into_iter().unzip();
+
+        Ok(Beatmap {
+            version,
+            is_convert: false,
+            checksum: None,
+            stack_leniency,
+            mode,
+            metadata,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            breaks,
+            timing_points,
+            difficulty_points,
+            effect_points,
+            hit_objects,
+            hit_sounds,
+        })
+    }
+
+    fn validate(&self) -> Result<(), BuildBeatmapError> {
+        let settings = [
+            ("stack leniency", f64::from(self.stack_leniency)),
+            ("approach rate", f64::from(self.ar)),
+            ("circle size", f64::from(self.cs)),
+            ("drain rate", f64::from(self.hp)),
+            ("overall difficulty", f64::from(self.od)),
+            ("slider multiplier", self.slider_multiplier),
+            ("slider tick rate", self.slider_tick_rate),
+        ];
+
+        if let Some((name, _)) = settings.iter().find(|(_, value)| !value.is_finite()) {
+            return Err(BuildBeatmapError::Setting(name));
+        }
+
+        for b in self.breaks.iter() {
+            if !(b.start_time.is_finite() && b.end_time.is_finite()) {
+                return Err(BuildBeatmapError::Time);
+            } else if b.end_time < b.start_time {
+                return Err(BuildBeatmapError::BreakPeriod {
+                    start_time: b.start_time,
+                });
+            }
+        }
+
+        for point in self.timing_points.iter() {
+            if !point.time.is_finite() {
+                return Err(BuildBeatmapError::Time);
+            } else if !(point.beat_len.is_finite() && point.beat_len > 0.0) {
+                return Err(BuildBeatmapError::BeatLength { time: point.time });
+            }
+        }
+
+        for point in self.difficulty_points.iter() {
+            if !point.time.is_finite() {
+                return Err(BuildBeatmapError::Time);
+            }
+
+            let is_valid = |value: f64| value.is_finite() && value > 0.0;
+
+            if !(is_valid(point.slider_velocity) && is_valid(point.bpm_multiplier)) {
+                return Err(BuildBeatmapError::SliderVelocity { time: point.time });
+            }
+        }
+
+        for point in self.effect_points.iter() {
+            if !point.time.is_finite() {
+                return Err(BuildBeatmapError::Time);
+            } else if !point.scroll_speed.is_finite() {
+                return Err(BuildBeatmapError::ScrollSpeed { time: point.time });
+            }
+        }
+
+        for (h, _) in self.hit_objects.iter() {
+            validate_hit_object(h)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_hit_object(h: &HitObject) -> Result<(), BuildBeatmapError> {
+    if !h.start_time.is_finite() {
+        return Err(BuildBeatmapError::Time);
+    }
+
+    let invalid = |reason| BuildBeatmapError::HitObject {
+        start_time: h.start_time,
+        reason,
+    };
+
+    if !(h.pos.x.is_finite() && h.pos.y.is_finite()) {
+        return Err(invalid(InvalidHitObject::Position));
+    }
+
+    match h.kind {
+        HitObjectKind::Circle => {}
+        HitObjectKind::Slider(ref slider) => {
+            if slider.control_points.is_empty() {
+                return Err(invalid(InvalidHitObject::NoControlPoints));
+            } else if slider
+                .control_points
+                .iter()
+                .any(|point| !(point.pos.x.is_finite() && point.pos.y.is_finite()))
+            {
+                return Err(invalid(InvalidHitObject::Position));
+            } else if slider
+                .expected_dist
+                .is_some_and(|dist| !(dist.is_finite() && dist >= 0.0))
+            {
+                return Err(invalid(InvalidHitObject::ExpectedDistance));
+            } else if slider.node_sounds.len() != slider.repeats + 2 {
+                return Err(invalid(InvalidHitObject::NodeSounds));
+            }
+        }
+        HitObjectKind::Spinner(Spinner { duration })
+        | HitObjectKind::Hold(HoldNote { duration }) => {
+            if !(duration.is_finite() && duration >= 0.0) {
+                return Err(invalid(InvalidHitObject::Duration));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+impl Default for BeatmapBuilder {
+    fn default() -> Self {
+        let Beatmap {
+            version,
+            mode,
+            stack_leniency,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            ..
+        } = Beatmap::default();
+
+        Self {
+            version,
+            mode,
+            stack_leniency,
+            metadata: None,
+            ar,
+            cs,
+            hp,
+            od,
+            slider_multiplier,
+            slider_tick_rate,
+            breaks: Vec::new(),
+            timing_points: Vec::new(),
+            difficulty_points: Vec::new(),
+            effect_points: Vec::new(),
+            hit_objects: Vec::new(),
+        }
+    }
+}
+
+/// All the ways that building a [`Beatmap`] through a [`BeatmapBuilder`] can
+/// fail.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BuildBeatmapError {
+    /// A timing point's beat length is not positive.
+    BeatLength { time: f64 },
+    /// A break period ends before it starts.
+    BreakPeriod { start_time: f64 },
+    /// A hitobject is invalid.
+    HitObject {
+        start_time: f64,
+        reason: InvalidHitObject,
+    },
+    /// An effect point's scroll speed is not finite.
+    ScrollSpeed { time: f64 },
+    /// A difficulty setting such as approach rate is not finite.
+    Setting(&'static str),
+    /// A difficulty point's slider velocity or BPM multiplier is not
+    /// positive.
+    SliderVelocity { time: f64 },
+    /// The time of a control point, break period, or hitobject is not
+    /// finite.
+    Time,
+}
+
+/// The reason why a [`HitObject`] is invalid.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InvalidHitObject {
+    /// A spinner's or hold note's duration is negative or not finite.
+    Duration,
+    /// A slider's expected distance is negative or not finite.
+    ExpectedDistance,
+    /// A slider has no control points.
+    NoControlPoints,
+    /// The amount of a slider's node sounds does not equal its repeats plus
+    /// two.
+    NodeSounds,
+    /// A position is not finite.
+    Position,
+}
+
+impl error::Error for BuildBeatmapError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for BuildBeatmapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BeatLength { time } => write!(f, "invalid beat length at {time}ms"),
+            Self::BreakPeriod { start_time } => {
+                write!(f, "break period at {start_time}ms ends before it starts")
+            }
+            Self::HitObject { start_time, reason } => {
+                let reason = match reason {
+                    InvalidHitObject::Duration => "invalid duration",
+                    InvalidHitObject::ExpectedDistance => "invalid expected distance",
+                    InvalidHitObject::NoControlPoints => "missing control points",
+                    InvalidHitObject::NodeSounds => "invalid amount of node sounds",
+                    InvalidHitObject::Position => "invalid position",
+                };
+
+                write!(f, "hitobject at {start_time}ms: {reason}")
+            }
+            Self::ScrollSpeed { time } => write!(f, "invalid scroll speed at {time}ms"),
+            Self::Setting(name) => write!(f, "invalid {name}"),
+            Self::SliderVelocity { time } => write!(f, "invalid slider velocity at {time}ms"),
+            Self::Time => f.write_str("time must be finite"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rebuild_decoded() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let mut builder = BeatmapBuilder::new()
+            .version(map.version)
+            .mode(map.mode)
+            .stack_leniency(map.stack_leniency)
+            .ar(map.ar)
+            .cs(map.cs)
+            .hp(map.hp)
+            .od(map.od)
+            .slider_multiplier(map.slider_multiplier)
+            .slider_tick_rate(map.slider_tick_rate);
+
+        if let Some(ref metadata) = map.metadata {
+            builder = builder.metadata(BeatmapMetadata::clone(metadata));
+        }
+
+        // Insert everything in reverse order to check sorting
+        for &b in map.breaks.iter().rev() {
+            builder = builder.break_period(b);
+        }
+
+        for point in map.timing_points.iter().rev() {
+            builder = builder.timing_point(*point);
+        }
+
+        for point in map.difficulty_points.iter().rev() {
+            builder = builder.difficulty_point(point.clone());
+        }
+
+        for point in map.effect_points.iter().rev() {
+            builder = builder.effect_point(*point);
+        }
+
+        for (h, &sound) in map.hit_objects.iter().zip(map.hit_sounds.iter()).rev() {
+            builder = builder.hit_object(h.clone(), sound);
+        }
+
+        let built = builder.build().unwrap();
+
+        assert_eq!(built, map);
+    }
+
+    #[test]
+    fn mania_objects() {
+        let map = BeatmapBuilder::new()
+            .mode(GameMode::Mania)
+            .cs(4.0)
+            .timing_point(TimingPoint::new(0.0, 400.0))
+            .hold_note(Pos::new(448.0, 192.0), 800.0, 400.0)
+            .circle(Pos::new(64.0, 192.0), 400.0)
+            .circle(Pos::new(192.0, 192.0), 400.0)
+            .build()
+            .unwrap();
+
+        assert_eq!(map.hit_objects.len(), 3);
+        assert_eq!(map.hit_sounds.len(), 3);
+        assert!(map.hit_objects[2].is_hold_note());
+    }
+
+    #[test]
+    fn invalid() {
+        let res = BeatmapBuilder::new()
+            .timing_point(TimingPoint {
+                beat_len: 0.0,
+                ..TimingPoint::new(100.0, 500.0)
+            })
+            .build();
+
+        assert_eq!(res, Err(BuildBeatmapError::BeatLength { time: 100.0 }));
+
+        let res = BeatmapBuilder::new().od(f32::NAN).build();
+
+        assert_eq!(res, Err(BuildBeatmapError::Setting("overall difficulty")));
+
+        let res = BeatmapBuilder::new()
+            .slider(Pos::new(0.0, 0.0), 500.0, Vec::new(), 0, None)
+            .build();
+
+        assert_eq!(
+            res,
+            Err(BuildBeatmapError::HitObject {
+                start_time: 500.0,
+                reason: InvalidHitObject::NoControlPoints
+            })
+        );
+
+        let slider = Slider {
+            expected_dist: None,
+            repeats: 1,
+            control_points: vec![PathControlPoint::default(), PathControlPoint::default()]
+                .into_boxed_slice(),
+            node_sounds: Box::default(),
+        };
+
+        let h = HitObject {
+            pos: Pos::default(),
+            start_time: 200.0,
+            kind: HitObjectKind::Slider(slider),
+        };
+
+        let res = BeatmapBuilder::new()
+            .hit_object(h, HitSoundType::default())
+            .build();
+
+        assert_eq!(
+            res,
+            Err(BuildBeatmapError::HitObject {
+                start_time: 200.0,
+                reason: InvalidHitObject::NodeSounds
+            })
+        );
+
+        let res = BeatmapBuilder::new()
+            .spinner(Pos::default(), f64::NAN, 100.0)
+            .build();
+
+        assert_eq!(res, Err(BuildBeatmapError::Time));
+    }
+}
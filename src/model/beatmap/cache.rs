@@ -0,0 +1,668 @@
+use std::{
+    error, fmt,
+    num::{NonZeroI32, NonZeroU32},
+};
+
+use rosu_map::section::{
+    general::GameMode,
+    hit_objects::{hit_samples::HitSoundType, PathControlPoint, PathType, SplineType},
+};
+
+use crate::model::{
+    control_point::{DifficultyPoint, EffectPoint, TimeSignature, TimingPoint},
+    hit_object::{HitObject, HitObjectKind, HoldNote, Pos, Slider, Spinner},
+};
+
+use super::{Beatmap, BeatmapMetadata, BreakPeriod, Md5Hash};
+
+const MAGIC: [u8; 4] = *b"RPPC";
+
+/// The version of the binary cache format.
+///
+/// Whenever the format changes, this version is incremented so that outdated
+/// caches are rejected.
+pub const CACHE_VERSION: u32 = 1;
+
+/// Error type when loading a [`Beatmap`] from its binary cache format.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CacheError {
+    /// The content ended unexpectedly.
+    Eof,
+    /// A value had an invalid representation.
+    Invalid(&'static str),
+    /// The content did not start with the expected magic bytes.
+    Magic,
+    /// There were bytes left after the [`Beatmap`] was decoded.
+    TrailingBytes,
+    /// The content was encoded with another format version.
+    Version { found: u32, expected: u32 },
+}
+
+impl error::Error for CacheError {}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof => f.write_str("unexpected end of content"),
+            Self::Invalid(what) => write!(f, "invalid {what}"),
+            Self::Magic => f.write_str("content is not a beatmap cache"),
+            Self::TrailingBytes => f.write_str("unexpected bytes after beatmap"),
+            Self::Version { found, expected } => write!(
+                f,
+                "cache format version {found} does not match expected version {expected}"
+            ),
+        }
+    }
+}
+
+pub fn encode(map: &Beatmap) -> Vec<u8> {
+    let mut writer = Writer(Vec::with_capacity(64 + 64 * map.hit_objects.len()));
+
+    let Beatmap {
+        version,
+        is_convert,
+        checksum,
+        stack_leniency,
+        mode,
+        metadata,
+        ar,
+        cs,
+        hp,
+        od,
+        slider_multiplier,
+        slider_tick_rate,
+        breaks,
+        timing_points,
+        difficulty_points,
+        effect_points,
+        hit_objects,
+        hit_sounds,
+    } = map;
+
+    writer.bytes(&MAGIC);
+    writer.u32(CACHE_VERSION);
+
+    writer.i32(*version);
+    writer.bool(*is_convert);
+
+    match checksum {
+        Some(checksum) => {
+            writer.bool(true);
+            writer.bytes(&checksum.0);
+        }
+        None => writer.bool(false),
+    }
+
+    writer.f32(*stack_leniency);
+    writer.u8(*mode as u8);
+
+    match metadata {
+        Some(metadata) => {
+            writer.bool(true);
+            writer.metadata(metadata);
+        }
+        None => writer.bool(false),
+    }
+
+    writer.f32(*ar);
+    writer.f32(*cs);
+    writer.f32(*hp);
+    writer.f32(*od);
+    writer.f64(*slider_multiplier);
+    writer.f64(*slider_tick_rate);
+
+    writer.len(breaks.len());
+
+    for b in breaks {
+        writer.break_period(b);
+    }
+
+    writer.len(timing_points.len());
+
+    for point in timing_points {
+        writer.f64(point.time);
+        writer.f64(point.beat_len);
+        writer.u32(point.time_signature.numerator.get());
+    }
+
+    writer.len(difficulty_points.len());
+
+    for point in difficulty_points {
+        writer.difficulty_point(point);
+    }
+
+    writer.len(effect_points.len());
+
+    for point in effect_points {
+        writer.effect_point(point);
+    }
+
+    writer.len(hit_objects.len());
+
+    for h in hit_objects {
+        writer.hit_object(h);
+    }
+
+    writer.len(hit_sounds.len());
+
+    for &sound in hit_sounds {
+        writer.u8(u8::from(sound));
+    }
+
+    writer.0
+}
+
+pub fn decode(bytes: &[u8]) -> Result<Beatmap, CacheError> {
+    let mut reader = Reader(bytes);
+
+    if reader.bytes::<4>()? != MAGIC {
+        return Err(CacheError::Magic);
+    }
+
+    let found = reader.u32()?;
+
+    if found != CACHE_VERSION {
+        return Err(CacheError::Version {
+            found,
+            expected: CACHE_VERSION,
+        });
+    }
+
+    let version = reader.i32()?;
+    let is_convert = reader.bool()?;
+
+    let checksum = if reader.bool()? {
+        Some(Md5Hash(reader.bytes()?))
+    } else {
+        None
+    };
+
+    let stack_leniency = reader.f32()?;
+    let mode = reader.mode()?;
+
+    let metadata = if reader.bool()? {
+        Some(Box::new(reader.metadata()?))
+    } else {
+        None
+    };
+
+    let ar = reader.f32()?;
+    let cs = reader.f32()?;
+    let hp = reader.f32()?;
+    let od = reader.f32()?;
+    let slider_multiplier = reader.f64()?;
+    let slider_tick_rate = reader.f64()?;
+
+    let breaks = reader.vec(|reader| {
+        Ok(BreakPeriod {
+            start_time: reader.f64()?,
+            end_time: reader.f64()?,
+        })
+    })?;
+
+    let timing_points = reader.vec(|reader| {
+        Ok(TimingPoint {
+            time: reader.f64()?,
+            beat_len: reader.f64()?,
+            time_signature: NonZeroU32::new(reader.u32()?)
+                .map(|numerator| TimeSignature { numerator })
+                .ok_or(CacheError::Invalid("time signature"))?,
+        })
+    })?;
+
+    let difficulty_points = reader.vec(|reader| {
+        Ok(DifficultyPoint {
+            time: reader.f64()?,
+            slider_velocity: reader.f64()?,
+            bpm_multiplier: reader.f64()?,
+            generate_ticks: reader.bool()?,
+        })
+    })?;
+
+    let effect_points = reader.vec(|reader| {
+        Ok(EffectPoint {
+            time: reader.f64()?,
+            kiai: reader.bool()?,
+            scroll_speed: reader.f64()?,
+        })
+    })?;
+
+    let hit_objects = reader.vec(Reader::hit_object)?;
+    let hit_sounds = reader.vec(|reader| reader.u8().map(HitSoundType::from))?;
+
+    if !reader.0.is_empty() {
+        return Err(CacheError::TrailingBytes);
+    }
+
+    Ok(Beatmap {
+        version,
+        is_convert,
+        checksum,
+        stack_leniency,
+        mode,
+        metadata,
+        ar,
+        cs,
+        hp,
+        od,
+        slider_multiplier,
+        slider_tick_rate,
+        breaks,
+        timing_points,
+        difficulty_points,
+        effect_points,
+        hit_objects,
+        hit_sounds,
+    })
+}
+
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn metadata(&mut self, metadata: &BeatmapMetadata) {
+        let BeatmapMetadata {
+            audio_file,
+            audio_lead_in,
+            preview_time,
+            title,
+            title_unicode,
+            artist,
+            artist_unicode,
+            creator,
+            version,
+            source,
+            tags,
+            beatmap_id,
+            beatmap_set_id,
+        } = metadata;
+
+        self.str(audio_file);
+        self.f64(*audio_lead_in);
+        self.i32(*preview_time);
+        self.str(title);
+        self.str(title_unicode);
+        self.str(artist);
+        self.str(artist_unicode);
+        self.str(creator);
+        self.str(version);
+        self.str(source);
+        self.str(tags);
+        self.i32(*beatmap_id);
+        self.i32(*beatmap_set_id);
+    }
+}
+
+impl FieldWriter for Writer {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.extend_from_slice(bytes);
+    }
+}
+
+/// Writes the fields of a [`Beatmap`] as little-endian bytes.
+///
+/// Shared by the cache and [`Beatmap::fingerprint`] so that both consider the
+/// same fields.
+///
+/// [`Beatmap::fingerprint`]: super::Beatmap::fingerprint
+pub(super) trait FieldWriter {
+    fn bytes(&mut self, bytes: &[u8]);
+
+    fn break_period(&mut self, b: &BreakPeriod) {
+        self.f64(b.start_time);
+        self.f64(b.end_time);
+    }
+
+    fn difficulty_point(&mut self, point: &DifficultyPoint) {
+        self.f64(point.time);
+        self.f64(point.slider_velocity);
+        self.f64(point.bpm_multiplier);
+        self.bool(point.generate_ticks);
+    }
+
+    fn effect_point(&mut self, point: &EffectPoint) {
+        self.f64(point.time);
+        self.bool(point.kiai);
+        self.f64(point.scroll_speed);
+    }
+
+    fn hit_object(&mut self, h: &HitObject) {
+        self.f32(h.pos.x);
+        self.f32(h.pos.y);
+        self.f64(h.start_time);
+
+        match h.kind {
+            HitObjectKind::Circle => self.u8(0),
+            HitObjectKind::Slider(ref slider) => {
+                self.u8(1);
+
+                match slider.expected_dist {
+                    Some(dist) => {
+                        self.bool(true);
+                        self.f64(dist);
+                    }
+                    None => self.bool(false),
+                }
+
+                self.len(slider.repeats);
+                self.len(slider.control_points.len());
+
+                for point in slider.control_points.iter() {
+                    self.f32(point.pos.x);
+                    self.f32(point.pos.y);
+
+                    match point.path_type {
+                        Some(path_type) => {
+                            self.u8(path_type.kind as u8 + 1);
+                            self.i32(path_type.degree.map_or(0, i32::from));
+                        }
+                        None => self.u8(0),
+                    }
+                }
+
+                self.len(slider.node_sounds.len());
+
+                for &sound in slider.node_sounds.iter() {
+                    self.u8(u8::from(sound));
+                }
+            }
+            HitObjectKind::Spinner(ref spinner) => {
+                self.u8(2);
+                self.f64(spinner.duration);
+            }
+            HitObjectKind::Hold(ref hold) => {
+                self.u8(3);
+                self.f64(hold.duration);
+            }
+        }
+    }
+
+    fn u8(&mut self, n: u8) {
+        self.bytes(&[n]);
+    }
+
+    fn bool(&mut self, b: bool) {
+        self.u8(u8::from(b));
+    }
+
+    fn i32(&mut self, n: i32) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn u32(&mut self, n: u32) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn len(&mut self, len: usize) {
+        self.bytes(&(len as u64).to_le_bytes());
+    }
+
+    fn f32(&mut self, n: f32) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn f64(&mut self, n: f64) {
+        self.bytes(&n.to_le_bytes());
+    }
+
+    fn str(&mut self, s: &str) {
+        self.len(s.len());
+        self.bytes(s.as_bytes());
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn metadata(&mut self) -> Result<BeatmapMetadata, CacheError> {
+        Ok(BeatmapMetadata {
+            audio_file: self.str()?,
+            audio_lead_in: self.f64()?,
+            preview_time: self.i32()?,
+            title: self.str()?,
+            title_unicode: self.str()?,
+            artist: self.str()?,
+            artist_unicode: self.str()?,
+            creator: self.str()?,
+            version: self.str()?,
+            source: self.str()?,
+            tags: self.str()?,
+            beatmap_id: self.i32()?,
+            beatmap_set_id: self.i32()?,
+        })
+    }
+
+    fn hit_object(&mut self) -> Result<HitObject, CacheError> {
+        let pos = Pos::new(self.f32()?, self.f32()?);
+        let start_time = self.f64()?;
+
+        let kind = match self.u8()? {
+            0 => HitObjectKind::Circle,
+            1 => {
+                let expected_dist = if self.bool()? {
+                    Some(self.f64()?)
+                } else {
+                    None
+                };
+
+                let repeats = self.len()?;
+                let control_points = self.vec(Self::control_point)?.into_boxed_slice();
+                let node_sounds = self
+                    .vec(|reader| reader.u8().map(HitSoundType::from))?
+                    .into_boxed_slice();
+
+                HitObjectKind::Slider(Slider {
+                    expected_dist,
+                    repeats,
+                    control_points,
+                    node_sounds,
+                })
+            }
+            2 => HitObjectKind::Spinner(Spinner {
+                duration: self.f64()?,
+            }),
+            3 => HitObjectKind::Hold(HoldNote {
+                duration: self.f64()?,
+            }),
+            _ => return Err(CacheError::Invalid("hitobject kind")),
+        };
+
+        Ok(HitObject {
+            pos,
+            start_time,
+            kind,
+        })
+    }
+
+    fn control_point(&mut self) -> Result<PathControlPoint, CacheError> {
+        let pos = Pos::new(self.f32()?, self.f32()?);
+
+        let kind = match self.u8()? {
+            0 => return Ok(PathControlPoint::new(pos)),
+            1 => SplineType::Catmull,
+            2 => SplineType::BSpline,
+            3 => SplineType::Linear,
+            4 => SplineType::PerfectCurve,
+            _ => return Err(CacheError::Invalid("path type")),
+        };
+
+        let path_type = PathType {
+            kind,
+            degree: NonZeroI32::new(self.i32()?),
+        };
+
+        Ok(PathControlPoint {
+            pos,
+            path_type: Some(path_type),
+        })
+    }
+
+    fn vec<T>(
+        &mut self,
+        mut f: impl FnMut(&mut Self) -> Result<T, CacheError>,
+    ) -> Result<Vec<T>, CacheError> {
+        let len = self.len()?;
+
+        // Each element takes at least one byte so this prevents huge
+        // allocations on corrupted lengths.
+        if len > self.0.len() {
+            return Err(CacheError::Eof);
+        }
+
+        let mut vec = Vec::with_capacity(len);
+
+        for _ in 0..len {
+            vec.push(f(self)?);
+        }
+
+        Ok(vec)
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], CacheError> {
+        let (bytes, rest) = self.0.split_first_chunk().ok_or(CacheError::Eof)?;
+        self.0 = rest;
+
+        Ok(*bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, CacheError> {
+        self.bytes().map(|[n]| n)
+    }
+
+    fn bool(&mut self) -> Result<bool, CacheError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(CacheError::Invalid("bool")),
+        }
+    }
+
+    fn mode(&mut self) -> Result<GameMode, CacheError> {
+        match self.u8()? {
+            n @ 0..=3 => Ok(GameMode::from(n)),
+            _ => Err(CacheError::Invalid("mode")),
+        }
+    }
+
+    fn i32(&mut self) -> Result<i32, CacheError> {
+        self.bytes().map(i32::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, CacheError> {
+        self.bytes().map(u32::from_le_bytes)
+    }
+
+    fn len(&mut self) -> Result<usize, CacheError> {
+        let len = self.bytes().map(u64::from_le_bytes)?;
+
+        usize::try_from(len).map_err(|_| CacheError::Invalid("length"))
+    }
+
+    fn f32(&mut self) -> Result<f32, CacheError> {
+        self.bytes().map(f32::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, CacheError> {
+        self.bytes().map(f64::from_le_bytes)
+    }
+
+    fn str(&mut self) -> Result<String, CacheError> {
+        let len = self.len()?;
+
+        if len > self.0.len() {
+            return Err(CacheError::Eof);
+        }
+
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| CacheError::Invalid("string"))
+    }
+}
+
+// With the `serde` feature, a `Beatmap` is (de)serialized as its binary cache
+// so that the types of `rosu_map` need no `serde` support.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Beatmap {
+    fn serialize<S: serde::Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_bytes(&encode(self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Beatmap {
+    fn deserialize<D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        use serde::de::{Error, SeqAccess, Visitor};
+
+        struct CacheVisitor;
+
+        impl<'de> Visitor<'de> for CacheVisitor {
+            type Value = Beatmap;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("beatmap cache bytes")
+            }
+
+            fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Beatmap, E> {
+                decode(v).map_err(E::custom)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Beatmap, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(1 << 16));
+
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+
+                self.visit_bytes(&bytes)
+            }
+        }
+
+        d.deserialize_bytes(CacheVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roundtrip() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let map = Beatmap::from_path_with_checksum(path).unwrap();
+            let bytes = map.to_cache();
+
+            assert_eq!(Beatmap::from_cache(&bytes), Ok(map), "{path}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let mut bytes = map.to_cache();
+
+        assert_eq!(
+            Beatmap::from_cache(&bytes[..bytes.len() - 1]),
+            Err(CacheError::Eof)
+        );
+
+        bytes.push(0);
+        assert_eq!(Beatmap::from_cache(&bytes), Err(CacheError::TrailingBytes));
+
+        bytes[4..8].copy_from_slice(&(CACHE_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            Beatmap::from_cache(&bytes),
+            Err(CacheError::Version {
+                found: CACHE_VERSION + 1,
+                expected: CACHE_VERSION
+            })
+        );
+
+        bytes[0] = b'X';
+        assert_eq!(Beatmap::from_cache(&bytes), Err(CacheError::Magic));
+    }
+}
@@ -0,0 +1,111 @@
+use std::fmt;
+
+use crate::util::md5::Md5;
+
+use super::{cache::FieldWriter, Beatmap};
+
+/// An MD5 hash.
+///
+/// Its [`Display`](fmt::Display) implementation writes the hash as 32
+/// lowercase hexadecimal characters, i.e. the same format osu! uses for
+/// beatmap checksums.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Md5Hash(pub [u8; 16]);
+
+impl Md5Hash {
+    /// Calculate the MD5 hash of the given bytes.
+    pub fn new(bytes: &[u8]) -> Self {
+        Self(Md5::digest(bytes))
+    }
+}
+
+impl fmt::Display for Md5Hash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
+/// Hashes all fields of a [`Beatmap`] that are relevant for difficulty and
+/// performance calculation.
+pub fn fingerprint(map: &Beatmap) -> Md5Hash {
+    let mut hasher = Fingerprint(Md5::new());
+
+    let Beatmap {
+        version,
+        is_convert,
+        checksum: _,
+        stack_leniency,
+        mode,
+        metadata: _,
+        ar,
+        cs,
+        hp,
+        od,
+        slider_multiplier,
+        slider_tick_rate,
+        breaks,
+        timing_points,
+        difficulty_points,
+        effect_points,
+        hit_objects,
+        hit_sounds,
+    } = map;
+
+    hasher.i32(*version);
+    hasher.bool(*is_convert);
+    hasher.f32(*stack_leniency);
+    hasher.u8(*mode as u8);
+    hasher.f32(*ar);
+    hasher.f32(*cs);
+    hasher.f32(*hp);
+    hasher.f32(*od);
+    hasher.f64(*slider_multiplier);
+    hasher.f64(*slider_tick_rate);
+
+    hasher.len(breaks.len());
+
+    for b in breaks {
+        hasher.break_period(b);
+    }
+
+    hasher.len(timing_points.len());
+
+    for point in timing_points {
+        hasher.f64(point.time);
+        hasher.f64(point.beat_len);
+    }
+
+    hasher.len(difficulty_points.len());
+
+    for point in difficulty_points {
+        hasher.difficulty_point(point);
+    }
+
+    hasher.len(effect_points.len());
+
+    for point in effect_points {
+        hasher.effect_point(point);
+    }
+
+    hasher.len(hit_objects.len());
+
+    for h in hit_objects {
+        hasher.hit_object(h);
+    }
+
+    hasher.len(hit_sounds.len());
+
+    for &sound in hit_sounds {
+        hasher.u8(u8::from(sound));
+    }
+
+    Md5Hash(hasher.0.finalize())
+}
+
+struct Fingerprint(Md5);
+
+impl FieldWriter for Fingerprint {
+    fn bytes(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+}
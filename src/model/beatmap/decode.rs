@@ -9,6 +9,7 @@ use rosu_map::{
             hit_samples::{HitSoundType, ParseHitSoundTypeError},
             HitObjectType, ParseHitObjectTypeError, PathControlPoint, PathType,
         },
+        metadata::MetadataKey,
         timing_points::{ControlPoint, EffectFlags, ParseEffectFlagsError},
     },
     util::{KeyValue, ParseNumber, ParseNumberError, Pos, StrExt, MAX_PARSE_VALUE},
@@ -18,28 +19,30 @@ use rosu_map::{
 use crate::{
     model::{
         control_point::{
-            difficulty_point_at, effect_point_at, DifficultyPoint, EffectPoint, TimingPoint,
+            difficulty_point_at, effect_point_at, DifficultyPoint, EffectPoint, TimeSignature,
+            TimingPoint,
         },
         hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
     },
     util::{float_ext::FloatExt, hint::unlikely, sort},
 };
 
-use super::{Beatmap, DEFAULT_SLIDER_LENIENCY};
+use super::{Beatmap, BeatmapMetadata, DEFAULT_SLIDER_LENIENCY};
 
 /// The state of a [`Beatmap`] for [`DecodeBeatmap`].
 pub struct BeatmapState {
-    version: i32,
-    stack_leniency: f32,
-    mode: GameMode,
+    pub(super) version: i32,
+    pub(super) stack_leniency: f32,
+    pub(super) mode: GameMode,
+    pub(super) metadata: Option<Box<BeatmapMetadata>>,
     has_approach_rate: bool,
-    difficulty: Difficulty,
-    breaks: Vec<BreakPeriod>,
+    pub(super) difficulty: Difficulty,
+    pub(super) breaks: Vec<BreakPeriod>,
     timing_points: Vec<TimingPoint>,
     difficulty_points: Vec<DifficultyPoint>,
     effect_points: Vec<EffectPoint>,
-    hit_objects: Vec<HitObject>,
-    hit_sounds: Vec<HitSoundType>,
+    pub(super) hit_objects: Vec<HitObject>,
+    pub(super) hit_sounds: Vec<HitSoundType>,
 
     pending_control_points_time: f64,
     pending_timing_point: Option<TimingPoint>,
@@ -52,6 +55,10 @@ pub struct BeatmapState {
 }
 
 impl BeatmapState {
+    fn metadata_mut(&mut self) -> &mut BeatmapMetadata {
+        self.metadata.get_or_insert_with(Box::default)
+    }
+
     fn add_pending_point<P: Pending>(&mut self, time: f64, point: P, timing_change: bool) {
         if time.not_eq(self.pending_control_points_time) {
             self.flush_pending_points();
@@ -80,7 +87,7 @@ impl BeatmapState {
         }
     }
 
-    fn add_control_point<P: ControlPoint<Self>>(&mut self, point: P) {
+    pub(super) fn add_control_point<P: ControlPoint<Self>>(&mut self, point: P) {
         if !point.check_already_existing(self) {
             point.add(self);
         }
@@ -252,6 +259,7 @@ impl DecodeState for BeatmapState {
             version,
             stack_leniency: DEFAULT_SLIDER_LENIENCY,
             mode: GameMode::Osu,
+            metadata: None,
             has_approach_rate: false,
             difficulty: Difficulty::default(),
             breaks: Vec::new(),
@@ -279,28 +287,13 @@ impl From<BeatmapState> for Beatmap {
         state.flush_pending_points();
 
         let Difficulty {
-            mut hp_drain_rate,
-            mut circle_size,
-            mut overall_difficulty,
-            mut approach_rate,
-            mut slider_multiplier,
-            mut slider_tick_rate,
-        } = state.difficulty;
-
-        hp_drain_rate = hp_drain_rate.clamp(0.0, 10.0);
-
-        // * mania uses "circle size" for key count, thus different allowable range
-        circle_size = if state.mode == GameMode::Mania {
-            circle_size.clamp(1.0, 18.0)
-        } else {
-            circle_size.clamp(0.0, 10.0)
-        };
-
-        overall_difficulty = overall_difficulty.clamp(0.0, 10.0);
-        approach_rate = approach_rate.clamp(0.0, 10.0);
-
-        slider_multiplier = slider_multiplier.clamp(0.4, 3.6);
-        slider_tick_rate = slider_tick_rate.clamp(0.5, 8.0);
+            hp_drain_rate,
+            circle_size,
+            overall_difficulty,
+            approach_rate,
+            slider_multiplier,
+            slider_tick_rate,
+        } = clamp_difficulty(&state.difficulty, state.mode);
 
         let mut sorter = sort::TandemSorter::new_stable(&state.hit_objects, |a, b| {
             a.start_time.total_cmp(&b.start_time)
@@ -316,8 +309,10 @@ impl From<BeatmapState> for Beatmap {
         Beatmap {
             version: state.version,
             is_convert: false,
+            checksum: None,
             stack_leniency: state.stack_leniency,
             mode: state.mode,
+            metadata: state.metadata,
             ar: approach_rate,
             cs: circle_size,
             hp: hp_drain_rate,
@@ -334,6 +329,54 @@ impl From<BeatmapState> for Beatmap {
     }
 }
 
+/// Parses a line of the `[Difficulty]` section.
+pub(super) fn parse_difficulty(
+    difficulty: &mut Difficulty,
+    has_approach_rate: &mut bool,
+    line: &str,
+) -> Result<(), ParseBeatmapError> {
+    let Ok(KeyValue { key, value }) = KeyValue::parse(line.trim_comment()) else {
+        return Ok(());
+    };
+
+    match key {
+        DifficultyKey::HPDrainRate => difficulty.hp_drain_rate = value.parse_num()?,
+        DifficultyKey::CircleSize => difficulty.circle_size = value.parse_num()?,
+        DifficultyKey::OverallDifficulty => {
+            difficulty.overall_difficulty = value.parse_num()?;
+
+            if !*has_approach_rate {
+                difficulty.approach_rate = difficulty.overall_difficulty;
+            }
+        }
+        DifficultyKey::ApproachRate => {
+            difficulty.approach_rate = value.parse_num()?;
+            *has_approach_rate = true;
+        }
+        DifficultyKey::SliderMultiplier => difficulty.slider_multiplier = f64::parse(value)?,
+        DifficultyKey::SliderTickRate => difficulty.slider_tick_rate = f64::parse(value)?,
+    }
+
+    Ok(())
+}
+
+/// Clamps difficulty values into their allowed range.
+pub(super) fn clamp_difficulty(difficulty: &Difficulty, mode: GameMode) -> Difficulty {
+    Difficulty {
+        hp_drain_rate: difficulty.hp_drain_rate.clamp(0.0, 10.0),
+        // * mania uses "circle size" for key count, thus different allowable range
+        circle_size: if mode == GameMode::Mania {
+            difficulty.circle_size.clamp(1.0, 18.0)
+        } else {
+            difficulty.circle_size.clamp(0.0, 10.0)
+        },
+        overall_difficulty: difficulty.overall_difficulty.clamp(0.0, 10.0),
+        approach_rate: difficulty.approach_rate.clamp(0.0, 10.0),
+        slider_multiplier: difficulty.slider_multiplier.clamp(0.4, 3.6),
+        slider_tick_rate: difficulty.slider_tick_rate.clamp(0.5, 8.0),
+    }
+}
+
 /// All the ways that parsing a [`Beatmap`] can fail.
 #[derive(Debug)]
 pub enum ParseBeatmapError {
@@ -450,6 +493,13 @@ impl DecodeBeatmap for Beatmap {
         };
 
         match key {
+            GeneralKey::AudioFilename => {
+                state.metadata_mut().audio_file = value.to_standardized_path();
+            }
+            GeneralKey::AudioLeadIn => {
+                state.metadata_mut().audio_lead_in = f64::from(i32::parse(value)?);
+            }
+            GeneralKey::PreviewTime => state.metadata_mut().preview_time = i32::parse(value)?,
             GeneralKey::StackLeniency => state.stack_leniency = value.parse_num()?,
             GeneralKey::Mode => state.mode = value.parse()?,
             _ => {}
@@ -462,38 +512,34 @@ impl DecodeBeatmap for Beatmap {
         Ok(())
     }
 
-    fn parse_metadata(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
-        Ok(())
-    }
-
-    fn parse_difficulty(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
-        let Ok(KeyValue { key, value }) = KeyValue::parse(line.trim_comment()) else {
+    fn parse_metadata(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        // No comment trimming since titles and tags may contain `//`
+        let Ok(KeyValue { key, value }) = KeyValue::parse(line) else {
             return Ok(());
         };
 
-        match key {
-            DifficultyKey::HPDrainRate => state.difficulty.hp_drain_rate = value.parse_num()?,
-            DifficultyKey::CircleSize => state.difficulty.circle_size = value.parse_num()?,
-            DifficultyKey::OverallDifficulty => {
-                state.difficulty.overall_difficulty = value.parse_num()?;
+        let metadata = state.metadata_mut();
 
-                if !state.has_approach_rate {
-                    state.difficulty.approach_rate = state.difficulty.overall_difficulty;
-                }
-            }
-            DifficultyKey::ApproachRate => {
-                state.difficulty.approach_rate = value.parse_num()?;
-                state.has_approach_rate = true;
-            }
-            DifficultyKey::SliderMultiplier => {
-                state.difficulty.slider_multiplier = f64::parse(value)?;
-            }
-            DifficultyKey::SliderTickRate => state.difficulty.slider_tick_rate = f64::parse(value)?,
+        match key {
+            MetadataKey::Title => value.clone_into(&mut metadata.title),
+            MetadataKey::TitleUnicode => value.clone_into(&mut metadata.title_unicode),
+            MetadataKey::Artist => value.clone_into(&mut metadata.artist),
+            MetadataKey::ArtistUnicode => value.clone_into(&mut metadata.artist_unicode),
+            MetadataKey::Creator => value.clone_into(&mut metadata.creator),
+            MetadataKey::Version => value.clone_into(&mut metadata.version),
+            MetadataKey::Source => value.clone_into(&mut metadata.source),
+            MetadataKey::Tags => value.clone_into(&mut metadata.tags),
+            MetadataKey::BeatmapID => metadata.beatmap_id = value.parse_num()?,
+            MetadataKey::BeatmapSetID => metadata.beatmap_set_id = value.parse_num()?,
         }
 
         Ok(())
     }
 
+    fn parse_difficulty(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        parse_difficulty(&mut state.difficulty, &mut state.has_approach_rate, line)
+    }
+
     fn parse_events(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
         let mut split = line.trim_comment().split(',');
 
@@ -547,11 +593,11 @@ impl DecodeBeatmap for Beatmap {
             1.0
         };
 
-        if let Some(numerator) = split.next() {
-            if unlikely(i32::parse(numerator)? < 1) {
-                return Err(ParseBeatmapError::TimeSignature);
-            }
-        }
+        let time_signature = match split.next() {
+            Some(numerator) => TimeSignature::new(i32::parse(numerator)?)
+                .map_err(|_| ParseBeatmapError::TimeSignature)?,
+            None => TimingPoint::DEFAULT_TIME_SIGNATURE,
+        };
 
         let _ = split.next(); // sample set
         let _ = split.next(); // custom sample bank
@@ -572,7 +618,11 @@ impl DecodeBeatmap for Beatmap {
                 return Err(ParseBeatmapError::TimingControlPointNaN);
             }
 
-            let timing = TimingPoint::new(time, beat_len);
+            let timing = TimingPoint {
+                time_signature,
+                ..TimingPoint::new(time, beat_len)
+            };
+
             state.add_pending_point(time, timing, timing_change);
         }
 
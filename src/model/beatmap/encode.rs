@@ -0,0 +1,494 @@
+use std::io::{Result as IoResult, Write};
+
+use rosu_map::section::{
+    difficulty::DifficultyKey,
+    general::{GameMode, GeneralKey},
+    hit_objects::{
+        hit_samples::HitSoundType, HitObjectType, PathControlPoint, PathType, SplineType,
+    },
+    metadata::MetadataKey,
+    timing_points::EffectFlags,
+};
+
+use crate::{
+    model::{
+        control_point::{difficulty_point_at, effect_point_at, DifficultyPoint, EffectPoint},
+        hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
+    },
+    util::sort,
+};
+
+use super::{Beatmap, BeatmapMetadata};
+
+/// Encode a [`Beatmap`] into content of a `.osu` file.
+pub fn encode<W: Write>(map: &Beatmap, mut writer: W) -> IoResult<()> {
+    writeln!(writer, "osu file format v{}", map.version)?;
+
+    writer.write_all(b"\n")?;
+    encode_general(map, &mut writer)?;
+
+    if let Some(ref metadata) = map.metadata {
+        writer.write_all(b"\n")?;
+        encode_metadata(metadata, &mut writer)?;
+    }
+
+    writer.write_all(b"\n")?;
+    encode_difficulty(map, &mut writer)?;
+
+    writer.write_all(b"\n")?;
+    encode_events(map, &mut writer)?;
+
+    writer.write_all(b"\n")?;
+    encode_timing_points(map, &mut writer)?;
+
+    writer.write_all(b"\n")?;
+    encode_hit_objects(map, &mut writer)?;
+
+    writer.flush()
+}
+
+fn encode_general<W: Write>(map: &Beatmap, writer: &mut W) -> IoResult<()> {
+    writer.write_all(b"[General]\n")?;
+
+    if let Some(ref metadata) = map.metadata {
+        writeln!(
+            writer,
+            "{}: {}
+{}: {}
+{}: {}",
+            GeneralKey::AudioFilename,
+            metadata.audio_file,
+            GeneralKey::AudioLeadIn,
+            metadata.audio_lead_in,
+            GeneralKey::PreviewTime,
+            metadata.preview_time,
+        )?;
+    }
+
+    writeln!(
+        writer,
+        "{}: {}
+{}: {}",
+        GeneralKey::StackLeniency,
+        map.stack_leniency,
+        GeneralKey::Mode,
+        map.mode as i32,
+    )
+}
+
+fn encode_metadata<W: Write>(metadata: &BeatmapMetadata, writer: &mut W) -> IoResult<()> {
+    writeln!(
+        writer,
+        "[Metadata]
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}",
+        MetadataKey::Title,
+        metadata.title,
+        MetadataKey::TitleUnicode,
+        metadata.title_unicode,
+        MetadataKey::Artist,
+        metadata.artist,
+        MetadataKey::ArtistUnicode,
+        metadata.artist_unicode,
+        MetadataKey::Creator,
+        metadata.creator,
+        MetadataKey::Version,
+        metadata.version,
+        MetadataKey::Source,
+        metadata.source,
+        MetadataKey::Tags,
+        metadata.tags,
+        MetadataKey::BeatmapID,
+        metadata.beatmap_id,
+        MetadataKey::BeatmapSetID,
+        metadata.beatmap_set_id,
+    )
+}
+
+fn encode_difficulty<W: Write>(map: &Beatmap, writer: &mut W) -> IoResult<()> {
+    writeln!(
+        writer,
+        "[Difficulty]
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}
+{}: {}",
+        DifficultyKey::HPDrainRate,
+        map.hp,
+        DifficultyKey::CircleSize,
+        map.cs,
+        DifficultyKey::OverallDifficulty,
+        map.od,
+        DifficultyKey::ApproachRate,
+        map.ar,
+        DifficultyKey::SliderMultiplier,
+        map.slider_multiplier,
+        DifficultyKey::SliderTickRate,
+        map.slider_tick_rate,
+    )
+}
+
+fn encode_events<W: Write>(map: &Beatmap, writer: &mut W) -> IoResult<()> {
+    /// `EventType::Break`
+    const BREAK: i32 = 2;
+
+    writer.write_all(b"[Events]\n")?;
+
+    for b in map.breaks.iter() {
+        writeln!(writer, "{BREAK},{},{}", b.start_time, b.end_time)?;
+    }
+
+    Ok(())
+}
+
+fn encode_timing_points<W: Write>(map: &Beatmap, writer: &mut W) -> IoResult<()> {
+    let mut times: Vec<_> = map
+        .timing_points
+        .iter()
+        .map(|point| point.time)
+        .chain(map.difficulty_points.iter().map(|point| point.time))
+        .chain(map.effect_points.iter().map(|point| point.time))
+        .collect();
+
+    times.sort_unstable_by(f64::total_cmp);
+    times.dedup_by(|a, b| a.total_cmp(b).is_eq());
+
+    writer.write_all(b"[TimingPoints]\n")?;
+
+    for time in times {
+        let difficulty = difficulty_point_at(&map.difficulty_points, time)
+            .cloned()
+            .unwrap_or_default();
+
+        let effect = effect_point_at(&map.effect_points, time)
+            .copied()
+            .unwrap_or_default();
+
+        let effect_flags: i32 = if effect.kiai {
+            EffectFlags::KIAI
+        } else {
+            EffectFlags::NONE
+        };
+
+        let timing = map
+            .timing_points
+            .binary_search_by(|probe| probe.time.total_cmp(&time))
+            .ok()
+            .map(|i| &map.timing_points[i]);
+
+        // A timing line resets the slider velocity and scroll speed so we
+        // only need an additional inherited line if those differ.
+        if let Some(timing) = timing {
+            writeln!(
+                writer,
+                "{time},{},{},0,0,100,1,{effect_flags}",
+                timing.beat_len, timing.time_signature.numerator
+            )?;
+
+            let default_difficulty = DifficultyPoint::default();
+            let default_effect = EffectPoint::default();
+
+            if difficulty.is_redundant(&default_difficulty)
+                && (!uses_scroll_speed(map.mode) || effect.is_redundant(&default_effect))
+            {
+                continue;
+            }
+        }
+
+        let beat_len = inherited_beat_len(map, time, &difficulty, &effect);
+        writeln!(writer, "{time},{beat_len},4,0,0,100,0,{effect_flags}")?;
+    }
+
+    Ok(())
+}
+
+/// Whether [`EffectPoint::scroll_speed`] is read from timing point lines.
+const fn uses_scroll_speed(mode: GameMode) -> bool {
+    matches!(mode, GameMode::Taiko | GameMode::Mania)
+}
+
+/// Find the negative beat length of an inherited timing point line so that
+/// decoding it results in the given control points.
+fn inherited_beat_len(
+    map: &Beatmap,
+    time: f64,
+    difficulty: &DifficultyPoint,
+    effect: &EffectPoint,
+) -> f64 {
+    if !difficulty.generate_ticks {
+        return f64::NAN;
+    }
+
+    let is_exact = |time_a: f64, time_b: f64| time_a.total_cmp(&time_b).is_eq();
+
+    // In osu!taiko and osu!mania both the slider velocity and the scroll
+    // speed are derived from the same value so we prefer whichever point
+    // actually starts at this time.
+    if uses_scroll_speed(map.mode)
+        && !is_exact(difficulty.time, time)
+        && is_exact(effect.time, time)
+    {
+        return -100.0 / effect.scroll_speed;
+    }
+
+    // The candidate that matches both the slider velocity and the bpm
+    // multiplier is preferred but since both values are clamped and the
+    // multiplier is additionally rounded to `f32`, there might be none.
+    let from_bpm_multiplier = -100.0 * difficulty.bpm_multiplier;
+    let from_slider_velocity = -100.0 / difficulty.slider_velocity;
+
+    let candidates = [
+        from_bpm_multiplier,
+        from_slider_velocity,
+        f64::from_bits(from_slider_velocity.to_bits() + 1),
+        f64::from_bits(from_slider_velocity.to_bits() - 1),
+    ];
+
+    let decodes_to = |beat_len: f64| DifficultyPoint::new(time, beat_len, 100.0 / -beat_len);
+
+    let matches_velocity = |point: &DifficultyPoint| {
+        point
+            .slider_velocity
+            .total_cmp(&difficulty.slider_velocity)
+            .is_eq()
+    };
+
+    candidates
+        .iter()
+        .copied()
+        .find(|&beat_len| {
+            let point = decodes_to(beat_len);
+
+            matches_velocity(&point)
+                && point
+                    .bpm_multiplier
+                    .total_cmp(&difficulty.bpm_multiplier)
+                    .is_eq()
+        })
+        .or_else(|| {
+            candidates
+                .iter()
+                .copied()
+                .find(|&beat_len| matches_velocity(&decodes_to(beat_len)))
+        })
+        .unwrap_or(from_slider_velocity)
+}
+
+fn encode_hit_objects<W: Write>(map: &Beatmap, writer: &mut W) -> IoResult<()> {
+    writer.write_all(b"[HitObjects]\n")?;
+
+    for (i, idx) in encode_order(map).into_iter().enumerate() {
+        let h = &map.hit_objects[idx];
+
+        // Converted osu!mania maps don't store hitsounds
+        let sound = map.hit_sounds.get(i).copied().unwrap_or_default();
+
+        let kind = match h.kind {
+            HitObjectKind::Circle => HitObjectType::CIRCLE,
+            HitObjectKind::Slider(_) => HitObjectType::SLIDER,
+            HitObjectKind::Spinner(_) => HitObjectType::SPINNER,
+            HitObjectKind::Hold(_) => HitObjectType::HOLD,
+        };
+
+        write!(
+            writer,
+            "{x},{y},{start_time},{kind},{sound},",
+            x = h.pos.x,
+            y = h.pos.y,
+            start_time = h.start_time,
+            sound = u8::from(sound),
+        )?;
+
+        match h.kind {
+            HitObjectKind::Circle => {}
+            HitObjectKind::Slider(ref slider) => add_path_data(writer, h, slider, sound)?,
+            HitObjectKind::Spinner(Spinner { duration }) => {
+                write!(writer, "{},", h.start_time + duration)?;
+            }
+            HitObjectKind::Hold(HoldNote { duration }) => {
+                write!(writer, "{}:", h.start_time + duration)?;
+            }
+        }
+
+        writer.write_all(b"0:0:0:0:\n")?;
+    }
+
+    Ok(())
+}
+
+/// The indices of hit objects in the order in which they should be written.
+///
+/// Decoding an osu!mania map applies osu!'s legacy sorting which reorders
+/// simultaneous hit objects so we need to write them in an order for which
+/// the sorting results in the current order. Hitsounds are not affected by
+/// the sorting so they are written in their given order.
+fn encode_order(map: &Beatmap) -> Vec<usize> {
+    let mut sorted: Vec<_> = map
+        .hit_objects
+        .iter()
+        .map(|h| h.start_time)
+        .enumerate()
+        .collect();
+
+    if map.mode == GameMode::Mania {
+        sort::osu_legacy_by_key(&mut sorted, |(_, start_time)| *start_time);
+    }
+
+    let mut order = vec![0; sorted.len()];
+
+    for (idx, (written_idx, _)) in sorted.into_iter().enumerate() {
+        order[written_idx] = idx;
+    }
+
+    order
+}
+
+fn add_path_data<W: Write>(
+    writer: &mut W,
+    h: &HitObject,
+    slider: &Slider,
+    sound: HitSoundType,
+) -> IoResult<()> {
+    let HitObject { pos, .. } = h;
+    let control_points = &slider.control_points;
+    let mut last_type = None;
+
+    let separator = |i: usize| {
+        if i == control_points.len() - 1 {
+            ','
+        } else {
+            '|'
+        }
+    };
+
+    for (i, point) in control_points.iter().enumerate() {
+        let PathControlPoint {
+            pos: point_pos,
+            path_type,
+        } = point;
+
+        if let Some(path_type) = path_type {
+            let mut needs_explicit_segment =
+                Some(*path_type) != last_type || *path_type == PathType::PERFECT_CURVE;
+
+            // Two consecutive points at the same position start a new segment
+            // so we need to be explicit to not merge segments.
+            if i > 1 {
+                let p1 = *pos + control_points[i - 1].pos;
+                let p2 = *pos + control_points[i - 2].pos;
+
+                if p1.x as i32 == p2.x as i32 && p1.y as i32 == p2.y as i32 {
+                    needs_explicit_segment = true;
+                }
+            }
+
+            if needs_explicit_segment {
+                match path_type.kind {
+                    SplineType::BSpline => match path_type.degree {
+                        Some(degree) => write!(writer, "B{degree}")?,
+                        None => writer.write_all(b"B")?,
+                    },
+                    SplineType::Catmull => writer.write_all(b"C")?,
+                    SplineType::PerfectCurve => writer.write_all(b"P")?,
+                    SplineType::Linear => writer.write_all(b"L")?,
+                }
+
+                // Only sliders without further control points need the path
+                // type to be followed by `,` instead of `|`.
+                let type_separator = if control_points.len() == 1 { ',' } else { '|' };
+                write!(writer, "{type_separator}")?;
+
+                last_type = Some(*path_type);
+            } else {
+                write!(writer, "{}:{}|", pos.x + point_pos.x, pos.y + point_pos.y)?;
+            }
+        }
+
+        if i != 0 {
+            write!(
+                writer,
+                "{}:{}{}",
+                pos.x + point_pos.x,
+                pos.y + point_pos.y,
+                separator(i)
+            )?;
+        }
+    }
+
+    // Decoding a length of `0` results in `None`
+    let dist = slider.expected_dist.unwrap_or(0.0);
+    write!(writer, "{},{dist},", slider.span_count())?;
+
+    for i in 0..=slider.span_count() {
+        let node_sound = slider.node_sounds.get(i).copied().unwrap_or(sound);
+        let suffix = if i == slider.span_count() { ',' } else { '|' };
+
+        write!(writer, "{}{suffix}", u8::from(node_sound))?;
+    }
+
+    for i in 0..=slider.span_count() {
+        let suffix = if i == slider.span_count() { ',' } else { '|' };
+
+        write!(writer, "0:0{suffix}")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::control_point::TimingPoint;
+
+    use super::*;
+
+    fn roundtrip(map: &Beatmap) -> Beatmap {
+        let mut bytes = Vec::new();
+        encode(map, &mut bytes).unwrap();
+
+        Beatmap::from_bytes(&bytes).unwrap()
+    }
+
+    #[test]
+    fn inherited_points() {
+        let map = Beatmap {
+            mode: GameMode::Taiko,
+            timing_points: vec![
+                TimingPoint::new(0.0, 300.0),
+                TimingPoint::new(5000.0, 400.0),
+            ],
+            difficulty_points: vec![
+                DifficultyPoint::new(1000.0, -66.666_666_666_666_7, 1.5),
+                DifficultyPoint::new(5000.0, -50.0, 2.0),
+                DifficultyPoint::new(6000.0, f64::NAN, 1.0),
+            ],
+            effect_points: vec![
+                EffectPoint {
+                    scroll_speed: 1.5,
+                    ..EffectPoint::new(1000.0, true)
+                },
+                EffectPoint {
+                    scroll_speed: 2.0,
+                    ..EffectPoint::new(5000.0, true)
+                },
+                EffectPoint::new(6000.0, false),
+            ],
+            ..Default::default()
+        };
+
+        let decoded = roundtrip(&map);
+
+        assert_eq!(decoded.timing_points, map.timing_points);
+        assert_eq!(decoded.difficulty_points, map.difficulty_points);
+        assert_eq!(decoded.effect_points, map.effect_points);
+    }
+}
@@ -0,0 +1,403 @@
+use rosu_map::{
+    section::{
+        difficulty::Difficulty,
+        hit_objects::{
+            hit_samples::{HitSampleInfo, HitSoundType, SampleBankInfo},
+            HitObject as MapHitObject, HitObjectCircle, HitObjectHold,
+            HitObjectKind as MapHitObjectKind, HitObjectSlider, HitObjectSpinner, SliderPath,
+        },
+        timing_points::{
+            ControlPoints, DifficultyPoint as MapDifficultyPoint, EffectPoint as MapEffectPoint,
+            TimingPoint as MapTimingPoint,
+        },
+    },
+    util::Pos,
+    DecodeState,
+};
+
+use crate::{
+    model::{
+        control_point::{DifficultyPoint, EffectPoint, TimingPoint},
+        hit_object::{HitObject, HitObjectKind, HoldNote, Slider, Spinner},
+    },
+    util::get_precision_adjusted_beat_len,
+};
+
+use super::{decode::BeatmapState, Beatmap, BeatmapMetadata};
+
+/// [`rosu_map`] does not store the y-coordinate of hold notes so this value
+/// is used instead. It is irrelevant for calculations.
+const HOLD_POS_Y: f32 = 192.0;
+
+/// Converts a [`rosu_map::Beatmap`] without having to re-parse its content.
+///
+/// The [`Beatmap`] is processed the same way as when decoding it directly,
+/// i.e. difficulty values are clamped, redundant control points are skipped,
+/// and hitobjects are sorted.
+///
+/// Note that [`HitSoundType::NORMAL`] is not stored in
+/// [`rosu_map::Beatmap`] so it will be missing from all hitsounds.
+///
+/// Similarly, [`rosu_map::Beatmap`] only stores the clamped slider velocity of
+/// difficulty points. Slider velocities below `0.1` thus lose their
+/// [`DifficultyPoint::bpm_multiplier`] which may change slider ticks.
+impl From<&rosu_map::Beatmap> for Beatmap {
+    fn from(map: &rosu_map::Beatmap) -> Self {
+        let mut state = BeatmapState::create(map.format_version);
+
+        state.stack_leniency = map.stack_leniency;
+        state.mode = map.mode;
+
+        let metadata = BeatmapMetadata {
+            audio_file: map.audio_file.clone(),
+            audio_lead_in: map.audio_lead_in,
+            preview_time: map.preview_time,
+            title: map.title.clone(),
+            title_unicode: map.title_unicode.clone(),
+            artist: map.artist.clone(),
+            artist_unicode: map.artist_unicode.clone(),
+            creator: map.creator.clone(),
+            version: map.version.clone(),
+            source: map.source.clone(),
+            tags: map.tags.clone(),
+            beatmap_id: map.beatmap_id,
+            beatmap_set_id: map.beatmap_set_id,
+        };
+
+        if metadata != BeatmapMetadata::default() {
+            state.metadata = Some(Box::new(metadata));
+        }
+
+        state.difficulty = Difficulty {
+            hp_drain_rate: map.hp_drain_rate,
+            circle_size: map.circle_size,
+            overall_difficulty: map.overall_difficulty,
+            approach_rate: map.approach_rate,
+            slider_multiplier: map.slider_multiplier,
+            slider_tick_rate: map.slider_tick_rate,
+        };
+
+        state.breaks.clone_from(&map.breaks);
+
+        let ControlPoints {
+            timing_points,
+            difficulty_points,
+            effect_points,
+            sample_points: _,
+        } = &map.control_points;
+
+        for point in timing_points {
+            state.add_control_point(TimingPoint {
+                time_signature: point.time_signature,
+                ..TimingPoint::new(point.time, point.beat_len)
+            });
+        }
+
+        for point in difficulty_points {
+            // Restore the beat length so that the bpm multiplier is
+            // calculated the same way as during decoding. This only works
+            // for slider velocities that were not clamped.
+            let beat_len = if point.generate_ticks {
+                -100.0 / point.slider_velocity
+            } else {
+                f64::NAN
+            };
+
+            state.add_control_point(DifficultyPoint::new(
+                point.time,
+                beat_len,
+                point.slider_velocity,
+            ));
+        }
+
+        for point in effect_points {
+            state.add_control_point(EffectPoint {
+                time: point.time,
+                kiai: point.kiai,
+                scroll_speed: point.scroll_speed,
+            });
+        }
+
+        state.hit_objects.reserve_exact(map.hit_objects.len());
+        state.hit_sounds.reserve_exact(map.hit_objects.len());
+
+        for h in map.hit_objects.iter() {
+            let (h, sound) = convert_hit_object(h);
+            state.hit_objects.push(h);
+            state.hit_sounds.push(sound);
+        }
+
+        Self::from(state)
+    }
+}
+
+fn convert_hit_object(h: &MapHitObject) -> (HitObject, HitSoundType) {
+    let sound = HitSoundType::from(h.samples.as_slice());
+
+    let (pos, kind) = match h.kind {
+        MapHitObjectKind::Circle(ref circle) => (circle.pos, HitObjectKind::Circle),
+        MapHitObjectKind::Slider(ref slider) => {
+            let repeats = usize::try_from(slider.repeat_count).unwrap_or(0);
+
+            let mut node_sounds = vec![sound; repeats + 2].into_boxed_slice();
+
+            slider
+                .node_samples
+                .iter()
+                .map(|samples| HitSoundType::from(samples.as_slice()))
+                .zip(node_sounds.iter_mut())
+                .for_each(|(converted, sound)| *sound = converted);
+
+            let pos = slider.pos;
+
+            let slider = Slider {
+                expected_dist: slider.path.expected_dist(),
+                repeats,
+                control_points: slider.path.control_points().into(),
+                node_sounds,
+            };
+
+            (pos, HitObjectKind::Slider(slider))
+        }
+        MapHitObjectKind::Spinner(ref spinner) => (
+            spinner.pos,
+            HitObjectKind::Spinner(Spinner {
+                duration: spinner.duration,
+            }),
+        ),
+        MapHitObjectKind::Hold(ref hold) => (
+            Pos::new(hold.pos_x, HOLD_POS_Y),
+            HitObjectKind::Hold(HoldNote {
+                duration: hold.duration,
+            }),
+        ),
+    };
+
+    let h = HitObject {
+        pos,
+        start_time: h.start_time,
+        kind,
+    };
+
+    (h, sound)
+}
+
+/// Converts a [`Beatmap`] into a [`rosu_map::Beatmap`].
+///
+/// Content that is not stored in a [`Beatmap`] such as colors, combo info,
+/// or sample banks will have default values. Hold notes lose their
+/// y-coordinate.
+impl From<&Beatmap> for rosu_map::Beatmap {
+    fn from(map: &Beatmap) -> Self {
+        let mut converted = Self {
+            format_version: map.version,
+            stack_leniency: map.stack_leniency,
+            mode: map.mode,
+            hp_drain_rate: map.hp,
+            circle_size: map.cs,
+            overall_difficulty: map.od,
+            approach_rate: map.ar,
+            slider_multiplier: map.slider_multiplier,
+            slider_tick_rate: map.slider_tick_rate,
+            breaks: map.breaks.clone(),
+            control_points: ControlPoints {
+                timing_points: map
+                    .timing_points
+                    .iter()
+                    .map(|point| MapTimingPoint {
+                        time: point.time,
+                        beat_len: point.beat_len,
+                        omit_first_bar_line: MapTimingPoint::DEFAULT_OMIT_FIRST_BAR_LINE,
+                        time_signature: point.time_signature,
+                    })
+                    .collect(),
+                difficulty_points: map
+                    .difficulty_points
+                    .iter()
+                    .map(|point| MapDifficultyPoint {
+                        time: point.time,
+                        slider_velocity: point.slider_velocity,
+                        generate_ticks: point.generate_ticks,
+                    })
+                    .collect(),
+                effect_points: map
+                    .effect_points
+                    .iter()
+                    .map(|point| MapEffectPoint {
+                        time: point.time,
+                        kiai: point.kiai,
+                        scroll_speed: point.scroll_speed,
+                    })
+                    .collect(),
+                sample_points: Vec::new(),
+            },
+            hit_objects: Vec::with_capacity(map.hit_objects.len()),
+            ..Self::default()
+        };
+
+        if let Some(ref metadata) = map.metadata {
+            converted.audio_file.clone_from(&metadata.audio_file);
+            converted.audio_lead_in = metadata.audio_lead_in;
+            converted.preview_time = metadata.preview_time;
+            converted.title.clone_from(&metadata.title);
+            converted.title_unicode.clone_from(&metadata.title_unicode);
+            converted.artist.clone_from(&metadata.artist);
+            converted
+                .artist_unicode
+                .clone_from(&metadata.artist_unicode);
+            converted.creator.clone_from(&metadata.creator);
+            converted.version.clone_from(&metadata.version);
+            converted.source.clone_from(&metadata.source);
+            converted.tags.clone_from(&metadata.tags);
+            converted.beatmap_id = metadata.beatmap_id;
+            converted.beatmap_set_id = metadata.beatmap_set_id;
+        }
+
+        // Converted mania maps don't have hitsounds
+        let has_sounds = map.hit_sounds.len() == map.hit_objects.len();
+
+        for (i, h) in map.hit_objects.iter().enumerate() {
+            let sound = if has_sounds {
+                map.hit_sounds[i]
+            } else {
+                HitSoundType::default()
+            };
+
+            converted.hit_objects.push(hit_object(map, h, sound));
+        }
+
+        converted
+    }
+}
+
+fn hit_object(map: &Beatmap, h: &HitObject, sound: HitSoundType) -> MapHitObject {
+    let kind = match h.kind {
+        HitObjectKind::Circle => MapHitObjectKind::Circle(HitObjectCircle {
+            pos: h.pos,
+            new_combo: false,
+            combo_offset: 0,
+        }),
+        HitObjectKind::Slider(ref slider) => MapHitObjectKind::Slider(HitObjectSlider {
+            pos: h.pos,
+            new_combo: false,
+            combo_offset: 0,
+            path: SliderPath::new(
+                map.mode,
+                slider.control_points.to_vec(),
+                slider.expected_dist,
+            ),
+            node_samples: slider.node_sounds.iter().copied().map(samples).collect(),
+            repeat_count: slider.repeats as i32,
+            velocity: slider_velocity(map, h.start_time),
+        }),
+        HitObjectKind::Spinner(ref spinner) => MapHitObjectKind::Spinner(HitObjectSpinner {
+            pos: h.pos,
+            duration: spinner.duration,
+            new_combo: false,
+        }),
+        HitObjectKind::Hold(ref hold) => MapHitObjectKind::Hold(HitObjectHold {
+            pos_x: h.pos.x,
+            duration: hold.duration,
+        }),
+    };
+
+    MapHitObject {
+        start_time: h.start_time,
+        kind,
+        samples: samples(sound),
+    }
+}
+
+fn samples(sound: HitSoundType) -> Vec<HitSampleInfo> {
+    SampleBankInfo::default().convert_sound_type(sound)
+}
+
+fn slider_velocity(map: &Beatmap, start_time: f64) -> f64 {
+    const BASE_SCORING_DIST: f64 = 100.0;
+
+    let beat_len = map
+        .timing_point_at(start_time)
+        .map_or(TimingPoint::DEFAULT_BEAT_LEN, |point| point.beat_len);
+
+    let slider_velocity = map
+        .difficulty_point_at(start_time)
+        .map_or(DifficultyPoint::DEFAULT_SLIDER_VELOCITY, |point| {
+            point.slider_velocity
+        });
+
+    BASE_SCORING_DIST * map.slider_multiplier
+        / get_precision_adjusted_beat_len(slider_velocity, beat_len)
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::section::general::GameMode;
+
+    use super::*;
+
+    #[test]
+    fn from_rosu_map() {
+        for path in [
+            "./resources/2785319.osu",
+            "./resources/1028484.osu",
+            "./resources/2118524.osu",
+            "./resources/1638954.osu",
+        ] {
+            let decoded = Beatmap::from_path(path).unwrap();
+            let map = rosu_map::Beatmap::from_path(path).unwrap();
+
+            let mut expected = decoded.clone();
+
+            // `rosu_map` does not store the normal hitsound flag
+            let without_normal = |sound: &mut HitSoundType| {
+                *sound &= !HitSoundType::NORMAL;
+            };
+
+            for h in expected.hit_objects.iter_mut() {
+                if let HitObjectKind::Slider(ref mut slider) = h.kind {
+                    slider.node_sounds.iter_mut().for_each(without_normal);
+                }
+            }
+
+            expected.hit_sounds.iter_mut().for_each(without_normal);
+
+            assert_eq!(Beatmap::from(&map), expected, "{path}");
+
+            // Sorting osu!mania hitobjects again may change their order
+            if decoded.mode != GameMode::Mania {
+                let roundtrip = Beatmap::from(&rosu_map::Beatmap::from(&decoded));
+                assert_eq!(roundtrip, expected, "{path}");
+            }
+        }
+    }
+    #[test]
+    #[allow(clippy::float_cmp)]
+    fn slow_slider_velocity() {
+        let bytes = b"osu file format v14
+
+[TimingPoints]
+0,500,4,2,0,100,1,0
+1000,-500,4,2,0,100,0,0
+2000,-2000,4,2,0,100,0,0
+";
+
+        let decoded = Beatmap::from_bytes(bytes).unwrap();
+        let map = Beatmap::from(&rosu_map::Beatmap::from_bytes(bytes).unwrap());
+
+        let [ref slow, ref slower] = decoded.difficulty_points[..] else {
+            panic!("expected two difficulty points");
+        };
+
+        assert_eq!(map.difficulty_points[0], *slow);
+        assert_eq!(slow.bpm_multiplier, 5.0);
+
+        // The slider velocity is clamped to `0.1` which loses the bpm
+        // multiplier of `20.0`
+        assert_eq!(
+            map.difficulty_points[1].slider_velocity,
+            slower.slider_velocity
+        );
+        assert_eq!(slower.bpm_multiplier, 20.0);
+        assert_eq!(map.difficulty_points[1].bpm_multiplier, 10.0);
+    }
+}
@@ -0,0 +1,43 @@
+/// Metadata of a [`Beatmap`] that is not relevant for difficulty and
+/// performance calculation.
+///
+/// [`Beatmap`]: super::Beatmap
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeatmapMetadata {
+    // General
+    pub audio_file: String,
+    pub audio_lead_in: f64,
+    pub preview_time: i32,
+
+    // Metadata
+    pub title: String,
+    pub title_unicode: String,
+    pub artist: String,
+    pub artist_unicode: String,
+    pub creator: String,
+    pub version: String,
+    pub source: String,
+    pub tags: String,
+    pub beatmap_id: i32,
+    pub beatmap_set_id: i32,
+}
+
+impl Default for BeatmapMetadata {
+    fn default() -> Self {
+        Self {
+            audio_file: String::new(),
+            audio_lead_in: 0.0,
+            preview_time: -1,
+            title: String::new(),
+            title_unicode: String::new(),
+            artist: String::new(),
+            artist_unicode: String::new(),
+            creator: String::new(),
+            version: String::new(),
+            source: String::new(),
+            tags: String::new(),
+            beatmap_id: -1,
+            beatmap_set_id: 0,
+        }
+    }
+}
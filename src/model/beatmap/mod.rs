@@ -1,11 +1,17 @@
-use std::{borrow::Cow, io, path::Path, str::FromStr};
+use std::{
+    borrow::Cow,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+    str::FromStr,
+};
 
 use rosu_map::{
     section::{general::GameMode, hit_objects::hit_samples::HitSoundType},
     LATEST_FORMAT_VERSION,
 };
 
-pub use rosu_map::section::events::BreakPeriod;
+pub use rosu_map::section::{events::BreakPeriod, Section};
 
 use crate::{
     catch::Catch, mania::Mania, taiko::Taiko, Difficulty, GameMods, GradualDifficulty,
@@ -14,10 +20,22 @@ use crate::{
 
 pub use self::{
     attributes::{BeatmapAttributes, BeatmapAttributesBuilder, HitWindows},
+    builder::{BeatmapBuilder, BuildBeatmapError, InvalidHitObject},
+    cache::{CacheError, CACHE_VERSION},
+    checksum::Md5Hash,
     decode::{BeatmapState, ParseBeatmapError},
-    suspicious::TooSuspicious,
+    metadata::BeatmapMetadata,
+    osz::{OszBeatmap, OszDecode, OszError},
+    sanitize::SanitizeReport,
+    statistics::{BeatmapStatistics, ValueStatistics},
+    strict::{LineError, StrictDecode, StrictDecodeError},
+    summary::{BeatmapSummary, BeatmapSummaryState},
+    suspicious::{DensityLimit, SuspicionConfig, SuspicionReport, TooSuspicious},
+    timing::{BpmSection, TimingReport},
 };
 
+pub(crate) use self::attributes::{MANIA_MEH, MANIA_MISS};
+
 use super::{
     control_point::{
         difficulty_point_at, effect_point_at, timing_point_at, DifficultyPoint, EffectPoint,
@@ -29,8 +47,21 @@ use super::{
 
 mod attributes;
 mod bpm;
+mod builder;
+mod cache;
+mod checksum;
 mod decode;
+mod encode;
+mod interop;
+mod metadata;
+mod osz;
+mod sanitize;
+mod slice;
+mod statistics;
+mod strict;
+mod summary;
 mod suspicious;
+mod timing;
 
 /// All beatmap data that is relevant for difficulty and performance
 /// calculation.
@@ -38,11 +69,20 @@ mod suspicious;
 pub struct Beatmap {
     pub version: i32,
     pub is_convert: bool,
+    /// The MD5 hash of the `.osu` file.
+    ///
+    /// Only available if the map was decoded through
+    /// [`Beatmap::from_path_with_checksum`],
+    /// [`Beatmap::from_bytes_with_checksum`], or from an `.osz` archive.
+    pub checksum: Option<Md5Hash>,
 
     // General
     pub stack_leniency: f32,
     pub mode: GameMode,
 
+    // General & Metadata
+    pub metadata: Option<Box<BeatmapMetadata>>,
+
     // Difficulty
     pub ar: f32,
     pub cs: f32,
@@ -76,6 +116,125 @@ impl Beatmap {
         rosu_map::from_bytes(bytes)
     }
 
+    /// Parse a [`Beatmap`] by providing a path to a `.osu` file and store the
+    /// file's MD5 hash in [`Beatmap::checksum`].
+    pub fn from_path_with_checksum<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        fs::read(path).and_then(|bytes| Self::from_bytes_with_checksum(&bytes))
+    }
+
+    /// Parse a [`Beatmap`] by providing the content of a `.osu` file as a
+    /// slice of bytes and store its MD5 hash in [`Beatmap::checksum`].
+    pub fn from_bytes_with_checksum(bytes: &[u8]) -> Result<Self, io::Error> {
+        let mut map = Self::from_bytes(bytes)?;
+        map.checksum = Some(Md5Hash::new(bytes));
+
+        Ok(map)
+    }
+
+    /// Parse a [`Beatmap`] by providing a path to a `.osu` file while
+    /// reporting all encountered errors.
+    ///
+    /// See [`Beatmap::from_bytes_strict`] for more info.
+    pub fn from_path_strict<P: AsRef<Path>>(path: P) -> Result<StrictDecode, StrictDecodeError> {
+        StrictDecode::from_path(path)
+    }
+
+    /// Parse a [`Beatmap`] by providing the content of a `.osu` file as a
+    /// slice of bytes while reporting all encountered errors.
+    ///
+    /// Unlike [`Beatmap::from_bytes`], which silently skips lines it fails to
+    /// parse, this fails if the format version is missing or if a line of the
+    /// `[General]` or `[Difficulty]` section is invalid since those affect
+    /// the whole map. Lines of other sections, e.g. hitobjects or timing
+    /// points, that fail to parse are still skipped but they will be
+    /// collected in [`StrictDecode::warnings`].
+    pub fn from_bytes_strict(bytes: &[u8]) -> Result<StrictDecode, StrictDecodeError> {
+        StrictDecode::from_bytes(bytes)
+    }
+
+    /// Parse all [`Beatmap`]s of an `.osz` archive by providing its path.
+    ///
+    /// See [`Beatmap::from_osz_bytes`] for more info.
+    pub fn from_osz_path<P: AsRef<Path>>(path: P) -> Result<OszDecode, OszError> {
+        osz::from_path(path)
+    }
+
+    /// Parse all [`Beatmap`]s of an `.osz` archive by providing its content
+    /// as a slice of bytes.
+    ///
+    /// Every `.osu` file of the archive is decoded and returned alongside its
+    /// file name. Other files such as audio or images are ignored. Files
+    /// must either be stored or compressed through deflate.
+    ///
+    /// An `.osu` file that can't be decompressed or decoded is skipped and
+    /// its error is collected in [`OszDecode::errors`]. Only an invalid
+    /// archive fails entirely.
+    pub fn from_osz_bytes(bytes: &[u8]) -> Result<OszDecode, OszError> {
+        osz::from_bytes(bytes)
+    }
+
+    /// Encode a [`Beatmap`] into content of a `.osu` file.
+    ///
+    /// Only data that is stored in the [`Beatmap`] is written so information
+    /// such as colors or storyboard events will be missing. Decoding the
+    /// encoded content results in an equivalent [`Beatmap`].
+    ///
+    /// Converted maps are written as maps of their new mode. Note that the
+    /// `.osu` format has no way of storing [`Beatmap::is_convert`] so it will
+    /// be `false` after decoding.
+    ///
+    /// In case of writing directly to a file, it is recommended to pass the
+    /// file wrapped in a [`BufWriter`] or just use [`encode_to_path`].
+    ///
+    /// [`encode_to_path`]: Beatmap::encode_to_path
+    pub fn encode<W: Write>(&self, writer: W) -> Result<(), io::Error> {
+        encode::encode(self, writer)
+    }
+
+    /// Encode a [`Beatmap`] into content of a `.osu` file and store it at the
+    /// given path.
+    pub fn encode_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let file = File::create(path)?;
+
+        self.encode(BufWriter::new(file))
+    }
+
+    /// Encode a [`Beatmap`] into content of a `.osu` file and store it into a
+    /// [`String`].
+    pub fn encode_to_string(&self) -> Result<String, io::Error> {
+        let mut bytes = Vec::with_capacity(4096);
+        self.encode(&mut bytes)?;
+
+        String::from_utf8(bytes).map_err(io::Error::other)
+    }
+
+    /// Encode a [`Beatmap`] into a compact binary format.
+    ///
+    /// Loading the bytes through [`Beatmap::from_cache`] is considerably
+    /// faster than parsing the `.osu` file again. The format starts with
+    /// magic bytes and the [`CACHE_VERSION`] so that caches of other versions
+    /// are rejected.
+    pub fn to_cache(&self) -> Vec<u8> {
+        cache::encode(self)
+    }
+
+    /// Load a [`Beatmap`] from bytes created through [`Beatmap::to_cache`].
+    pub fn from_cache(bytes: &[u8]) -> Result<Self, CacheError> {
+        cache::decode(bytes)
+    }
+
+    /// Hash all fields that are relevant for difficulty and performance
+    /// calculation.
+    ///
+    /// Unlike [`Beatmap::checksum`], the fingerprint does not depend on the
+    /// file's formatting or on data such as [`Beatmap::metadata`] so it is
+    /// suited as key for caching calculated attributes. The fingerprint is
+    /// only guaranteed to stay the same within the same version of this
+    /// crate.
+    pub fn fingerprint(&self) -> Md5Hash {
+        checksum::fingerprint(self)
+    }
+
     /// Returns a [`BeatmapAttributesBuilder`] to calculate modified beatmap
     /// attributes.
     pub fn attributes(&self) -> BeatmapAttributesBuilder {
@@ -84,7 +243,23 @@ impl Beatmap {
 
     /// The beats per minute of the map.
     pub fn bpm(&self) -> f64 {
-        bpm::bpm(self.hit_objects.last(), &self.timing_points)
+        let last_time = if self.hit_objects.is_empty() {
+            self.timing_points.last().map_or(0.0, |point| point.time)
+        } else {
+            timing::last_object_end_time(self)
+        };
+
+        bpm::bpm(last_time, &self.timing_points)
+    }
+
+    /// Summarize the timing of the map i.e. its bpm values, a bpm timeline,
+    /// and its timing points.
+    ///
+    /// All values are adjusted to the given clock rate, e.g. `1.5` for
+    /// `DoubleTime`. Use [`GameMods::clock_rate`] to get the clock rate of
+    /// mods.
+    pub fn timing_report(&self, clock_rate: f64) -> TimingReport {
+        timing::timing_report(self, clock_rate)
     }
 
     /// Create a performance calculator for this [`Beatmap`].
@@ -117,6 +292,29 @@ impl Beatmap {
         effect_point_at(&self.effect_points, time)
     }
 
+    /// Create a new [`Beatmap`] that only contains the section between
+    /// `start_time` and `end_time` (in milliseconds).
+    ///
+    /// Hitobjects are kept if their start time is within the range,
+    /// inclusively. Control points within the range are kept as well as those
+    /// that are active at `start_time`. Break periods are cut to the range.
+    ///
+    /// This allows calculating difficulty and performance for any section of
+    /// a map, as if the map started with the section.
+    #[must_use]
+    pub fn slice(&self, start_time: f64, end_time: f64) -> Self {
+        slice::slice(self, start_time, end_time)
+    }
+
+    /// Calculate length and object statistics of the map such as its drain
+    /// length, object counts, and notes per second.
+    ///
+    /// All values are adjusted to the given clock rate, e.g. `1.5` for
+    /// `DoubleTime`.
+    pub fn statistics(&self, clock_rate: f64) -> BeatmapStatistics {
+        statistics::statistics(self, clock_rate)
+    }
+
     /// Sum up the duration of all breaks (in milliseconds).
     pub fn total_break_time(&self) -> f64 {
         self.breaks.iter().map(BreakPeriod::duration).sum()
@@ -192,11 +390,40 @@ impl Beatmap {
     /// should likely be avoided on these maps due to potential performance
     /// issues.
     pub fn check_suspicion(&self) -> Result<(), TooSuspicious> {
-        match TooSuspicious::new(self) {
+        self.check_suspicion_with(&SuspicionConfig::new(self.mode))
+            .map_err(TooSuspicious::from)
+    }
+
+    /// Check whether hitobjects appear too suspicious based on the given
+    /// thresholds.
+    ///
+    /// On failure, the [`SuspicionReport`] contains the measured value, the
+    /// exceeded threshold, and the offending hitobject, if any.
+    pub fn check_suspicion_with(&self, config: &SuspicionConfig) -> Result<(), SuspicionReport> {
+        match SuspicionReport::check(self, config) {
             None => Ok(()),
-            Some(err) => Err(err),
+            Some(report) => Err(report),
         }
     }
+
+    /// Modify hitobjects that cause [`Beatmap::check_suspicion`] to fail.
+    ///
+    /// Hitobjects with non-finite values are removed and hitobjects with the
+    /// same start time and position as the previous one are merged. If
+    /// [`SuspicionConfig::check_sliders`] is set, sliders' repeats are also
+    /// clamped and far off-screen sliders are removed.
+    ///
+    /// Note that a sanitized map may still be suspicious, e.g. if its notes
+    /// are too dense even without duplicates.
+    pub fn sanitize(&mut self) -> SanitizeReport {
+        self.sanitize_with(&SuspicionConfig::new(self.mode))
+    }
+
+    /// Same as [`Beatmap::sanitize`] but using the thresholds of the given
+    /// [`SuspicionConfig`].
+    pub fn sanitize_with(&mut self, config: &SuspicionConfig) -> SanitizeReport {
+        sanitize::sanitize(self, config)
+    }
 }
 
 impl FromStr for Beatmap {
@@ -216,8 +443,10 @@ impl Default for Beatmap {
         Self {
             version: LATEST_FORMAT_VERSION,
             is_convert: false,
+            checksum: None,
             stack_leniency: DEFAULT_SLIDER_LENIENCY,
             mode: GameMode::default(),
+            metadata: None,
             ar: 5.0,
             cs: 5.0,
             hp: 5.0,
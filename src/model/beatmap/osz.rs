@@ -0,0 +1,388 @@
+use std::{borrow::Cow, error, fmt, fs, io, path::Path};
+
+use crate::util::inflate::{inflate, InflateError};
+
+use super::Beatmap;
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x0403_4B50;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x0201_4B50;
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x0605_4B50;
+
+const LOCAL_HEADER_LEN: usize = 30;
+const CENTRAL_HEADER_LEN: usize = 46;
+const END_OF_CENTRAL_DIR_LEN: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATE: u16 = 8;
+
+const FLAG_ENCRYPTED: u16 = 1 << 0;
+
+/// Decompressed files may not exceed this many bytes.
+const MAX_DECOMPRESSED_LEN: usize = 1 << 26;
+
+/// All [`Beatmap`]s of an `.osz` archive alongside the errors of `.osu`
+/// files that could not be loaded.
+#[derive(Debug)]
+pub struct OszDecode {
+    pub maps: Vec<OszBeatmap>,
+    /// Errors of `.osu` files that were skipped, e.g. because they could not
+    /// be decompressed or decoded.
+    pub errors: Vec<OszError>,
+}
+
+/// A [`Beatmap`] that was loaded from an `.osz` archive.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OszBeatmap {
+    /// The name of the `.osu` file within the archive.
+    pub file_name: String,
+    pub map: Beatmap,
+}
+
+pub(super) fn from_path<P: AsRef<Path>>(path: P) -> Result<OszDecode, OszError> {
+    let bytes = fs::read(path).map_err(OszError::Io)?;
+
+    from_bytes(&bytes)
+}
+
+pub(super) fn from_bytes(bytes: &[u8]) -> Result<OszDecode, OszError> {
+    let (mut pos, count) = central_directory(bytes)?;
+    let mut maps = Vec::new();
+    let mut errors = Vec::new();
+
+    for _ in 0..count {
+        let entry = Entry::parse(bytes, pos)?;
+        pos = entry.next;
+
+        if !is_osu_file(&entry.file_name) {
+            continue;
+        }
+
+        let map = entry.content(bytes).and_then(|content| {
+            Beatmap::from_bytes_with_checksum(&content).map_err(|source| OszError::Decode {
+                file_name: entry.file_name.to_string(),
+                source,
+            })
+        });
+
+        match map {
+            Ok(map) => maps.push(OszBeatmap {
+                file_name: entry.file_name.into_owned(),
+                map,
+            }),
+            // The archive itself is broken so other files can't be trusted
+            Err(err @ (OszError::Archive(_) | OszError::Io(_))) => return Err(err),
+            Err(err) => errors.push(err),
+        }
+    }
+
+    Ok(OszDecode { maps, errors })
+}
+
+fn is_osu_file(file_name: &str) -> bool {
+    let Some((_, ext)) = file_name.rsplit_once('.') else {
+        return false;
+    };
+
+    ext.eq_ignore_ascii_case("osu")
+}
+
+/// Returns the offset of the central directory and its amount of entries.
+fn central_directory(bytes: &[u8]) -> Result<(usize, usize), OszError> {
+    // The end of central directory record is followed by a comment of up to
+    // `u16::MAX` bytes so we search backwards for its signature.
+    let min_start = bytes
+        .len()
+        .saturating_sub(END_OF_CENTRAL_DIR_LEN + usize::from(u16::MAX));
+
+    let start = (min_start..=bytes.len().saturating_sub(END_OF_CENTRAL_DIR_LEN))
+        .rev()
+        .find(|&pos| read_u32(bytes, pos) == Some(END_OF_CENTRAL_DIR_SIGNATURE))
+        .ok_or(OszError::Archive("missing end of central directory"))?;
+
+    let record = &bytes[start..];
+    let count = read_u16(record, 10);
+    let offset = read_u32(record, 16);
+
+    match (count, offset) {
+        (Some(u16::MAX), _) | (_, Some(u32::MAX)) => {
+            Err(OszError::Archive("zip64 archives are not supported"))
+        }
+        (Some(count), Some(offset)) => Ok((offset as usize, usize::from(count))),
+        _ => Err(OszError::Archive("truncated end of central directory")),
+    }
+}
+
+/// An entry of the central directory.
+struct Entry<'a> {
+    file_name: Cow<'a, str>,
+    flags: u16,
+    method: u16,
+    crc: u32,
+    compressed_len: usize,
+    len: usize,
+    local_header: usize,
+    /// Offset of the next entry.
+    next: usize,
+}
+
+impl<'a> Entry<'a> {
+    fn parse(bytes: &'a [u8], pos: usize) -> Result<Self, OszError> {
+        const TRUNCATED: OszError = OszError::Archive("truncated central directory");
+
+        if read_u32(bytes, pos) != Some(CENTRAL_HEADER_SIGNATURE) {
+            return Err(OszError::Archive("invalid central directory signature"));
+        }
+
+        let header = bytes.get(pos..pos + CENTRAL_HEADER_LEN).ok_or(TRUNCATED)?;
+
+        let u16_at = |offset| read_u16(header, offset).ok_or(TRUNCATED);
+        let u32_at = |offset| read_u32(header, offset).ok_or(TRUNCATED);
+
+        let flags = u16_at(8)?;
+        let method = u16_at(10)?;
+        let crc = u32_at(16)?;
+        let compressed_len = u32_at(20)?;
+        let len = u32_at(24)?;
+        let name_len = usize::from(u16_at(28)?);
+        let extra_len = usize::from(u16_at(30)?);
+        let comment_len = usize::from(u16_at(32)?);
+        let local_header = u32_at(42)?;
+
+        if [compressed_len, len, local_header].contains(&u32::MAX) {
+            return Err(OszError::Archive("zip64 archives are not supported"));
+        }
+
+        let name_start = pos + CENTRAL_HEADER_LEN;
+
+        let file_name = bytes
+            .get(name_start..name_start + name_len)
+            .map(String::from_utf8_lossy)
+            .ok_or(TRUNCATED)?;
+
+        Ok(Self {
+            file_name,
+            flags,
+            method,
+            crc,
+            compressed_len: compressed_len as usize,
+            len: len as usize,
+            local_header: local_header as usize,
+            next: name_start + name_len + extra_len + comment_len,
+        })
+    }
+
+    fn content<'b>(&self, bytes: &'b [u8]) -> Result<Cow<'b, [u8]>, OszError> {
+        const TRUNCATED: OszError = OszError::Archive("truncated local file header");
+
+        if self.flags & FLAG_ENCRYPTED != 0 {
+            return Err(OszError::Encrypted {
+                file_name: self.file_name.to_string(),
+            });
+        }
+
+        if self.len > MAX_DECOMPRESSED_LEN {
+            return Err(OszError::TooLarge {
+                file_name: self.file_name.to_string(),
+                len: self.len,
+            });
+        }
+
+        let pos = self.local_header;
+
+        if read_u32(bytes, pos) != Some(LOCAL_HEADER_SIGNATURE) {
+            return Err(OszError::Archive("invalid local file header signature"));
+        }
+
+        // The local header's lengths may be zero if a data descriptor is used
+        // so only the name and extra field lengths are taken from it.
+        let name_len = read_u16(bytes, pos + 26).ok_or(TRUNCATED)?;
+        let extra_len = read_u16(bytes, pos + 28).ok_or(TRUNCATED)?;
+        let start = pos + LOCAL_HEADER_LEN + usize::from(name_len) + usize::from(extra_len);
+
+        let data = bytes
+            .get(start..start + self.compressed_len)
+            .ok_or(OszError::Archive("truncated file data"))?;
+
+        let content = match self.method {
+            METHOD_STORED => Cow::Borrowed(data),
+            METHOD_DEFLATE => {
+                inflate(data, self.len)
+                    .map(Cow::Owned)
+                    .map_err(|source| OszError::Inflate {
+                        file_name: self.file_name.to_string(),
+                        source,
+                    })?
+            }
+            method => {
+                return Err(OszError::Compression {
+                    file_name: self.file_name.to_string(),
+                    method,
+                })
+            }
+        };
+
+        if content.len() != self.len || crc32(&content) != self.crc {
+            return Err(OszError::Checksum {
+                file_name: self.file_name.to_string(),
+            });
+        }
+
+        Ok(content)
+    }
+}
+
+fn read_u16(bytes: &[u8], pos: usize) -> Option<u16> {
+    let bytes = bytes.get(pos..pos + 2)?;
+
+    Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn read_u32(bytes: &[u8], pos: usize) -> Option<u32> {
+    let bytes = bytes.get(pos..pos + 4)?;
+
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+fn crc32(bytes: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0; 256];
+        let mut i = 0;
+
+        while i < 256 {
+            let mut crc = i as u32;
+            let mut j = 0;
+
+            while j < 8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xEDB8_8320
+                } else {
+                    crc >> 1
+                };
+
+                j += 1;
+            }
+
+            table[i] = crc;
+            i += 1;
+        }
+
+        table
+    };
+
+    let crc = bytes.iter().fold(u32::MAX, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    });
+
+    !crc
+}
+
+/// All the ways that loading beatmaps from an `.osz` archive can fail.
+#[derive(Debug)]
+pub enum OszError {
+    /// The archive is malformed or uses unsupported zip features.
+    Archive(&'static str),
+    /// The decompressed content of a file did not match its checksum.
+    Checksum { file_name: String },
+    /// A file uses a compression method other than stored or deflate.
+    Compression { file_name: String, method: u16 },
+    /// A `.osu` file could not be decoded.
+    Decode {
+        file_name: String,
+        source: io::Error,
+    },
+    /// A file is encrypted.
+    Encrypted { file_name: String },
+    /// A file could not be decompressed.
+    Inflate {
+        file_name: String,
+        source: InflateError,
+    },
+    /// Failed to read the archive.
+    Io(io::Error),
+    /// A file's declared decompressed size exceeds the limit of 64 MiB.
+    TooLarge { file_name: String, len: usize },
+}
+
+impl error::Error for OszError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Archive(_)
+            | Self::Checksum { .. }
+            | Self::Compression { .. }
+            | Self::Encrypted { .. }
+            | Self::TooLarge { .. } => None,
+            Self::Decode { source, .. } | Self::Io(source) => Some(source),
+            Self::Inflate { source, .. } => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for OszError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Archive(reason) => write!(f, "invalid archive: {reason}"),
+            Self::Checksum { file_name } => write!(f, "checksum mismatch for `{file_name}`"),
+            Self::Compression { file_name, method } => {
+                write!(
+                    f,
+                    "unsupported compression method {method} for `{file_name}`"
+                )
+            }
+            Self::Decode { file_name, .. } => write!(f, "failed to decode `{file_name}`"),
+            Self::Encrypted { file_name } => write!(f, "`{file_name}` is encrypted"),
+            Self::Inflate { file_name, .. } => write!(f, "failed to decompress `{file_name}`"),
+            Self::Io(_) => f.write_str("failed to read file"),
+            Self::TooLarge { file_name, len } => {
+                write!(f, "`{file_name}` is too large ({len} bytes)")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crc() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+    }
+
+    #[test]
+    fn osu_file() {
+        assert!(is_osu_file("Artist - Title (Mapper) [Diff].osu"));
+        assert!(is_osu_file("sub/dir/diff.OSU"));
+        assert!(!is_osu_file("audio.mp3"));
+        assert!(!is_osu_file("storyboard.osb"));
+        assert!(!is_osu_file("osu"));
+    }
+
+    #[test]
+    fn declared_len_limit() {
+        let mut bytes = fs::read("./resources/mapset.osz").unwrap();
+        let (pos, _) = central_directory(&bytes).unwrap();
+
+        // Declare roughly 4 GiB for the first file
+        bytes[pos + 24..pos + 28].copy_from_slice(&0xFFFF_FFFE_u32.to_le_bytes());
+
+        let decoded = from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.maps.len(), 1);
+        assert!(matches!(
+            decoded.errors.as_slice(),
+            [OszError::TooLarge {
+                len: 0xFFFF_FFFE,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn not_an_archive() {
+        assert!(matches!(
+            from_bytes(b"osu file format v14"),
+            Err(OszError::Archive(_))
+        ));
+    }
+}
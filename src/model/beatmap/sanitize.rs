@@ -0,0 +1,222 @@
+use std::mem;
+
+use crate::model::hit_object::{HitObject, HitObjectKind};
+
+use super::{Beatmap, SuspicionConfig};
+
+/// The changes applied by [`Beatmap::sanitize`].
+///
+/// [`Beatmap::sanitize`]: crate::model::beatmap::Beatmap::sanitize
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SanitizeReport {
+    /// The amount of sliders whose repeats were clamped to
+    /// [`SuspicionConfig::max_slider_repeats`].
+    pub clamped_repeats: usize,
+    /// The amount of hitobjects that were removed because their time or
+    /// position was non-finite or, for sliders, beyond
+    /// [`SuspicionConfig::max_slider_position`].
+    pub removed_invalid: usize,
+    /// The amount of hitobjects that were removed because they had the same
+    /// start time and position as a previous hitobject.
+    pub merged_duplicates: usize,
+}
+
+impl SanitizeReport {
+    /// Whether the [`Beatmap`] was modified.
+    pub const fn is_modified(&self) -> bool {
+        self.clamped_repeats > 0 || self.removed_invalid > 0 || self.merged_duplicates > 0
+    }
+}
+
+pub fn sanitize(map: &mut Beatmap, config: &SuspicionConfig) -> SanitizeReport {
+    let mut report = SanitizeReport::default();
+
+    if config.check_sliders {
+        for h in map.hit_objects.iter_mut() {
+            if let HitObjectKind::Slider(ref mut slider) = h.kind {
+                if slider.repeats > config.max_slider_repeats {
+                    slider.repeats = config.max_slider_repeats;
+
+                    let mut node_sounds = mem::take(&mut slider.node_sounds).into_vec();
+                    node_sounds.truncate(slider.repeats + 2);
+                    slider.node_sounds = node_sounds.into_boxed_slice();
+
+                    report.clamped_repeats += 1;
+                }
+            }
+        }
+    }
+
+    // Converted mania maps don't have hitsounds
+    let has_sounds = map.hit_sounds.len() == map.hit_objects.len();
+    let mut prev: Option<&HitObject> = None;
+
+    let keep: Vec<bool> = map
+        .hit_objects
+        .iter()
+        .map(|h| {
+            if is_invalid(h, config) {
+                report.removed_invalid += 1;
+
+                false
+            } else if prev.is_some_and(|prev| is_duplicate(prev, h)) {
+                report.merged_duplicates += 1;
+
+                false
+            } else {
+                prev = Some(h);
+
+                true
+            }
+        })
+        .collect();
+
+    if report.removed_invalid > 0 || report.merged_duplicates > 0 {
+        let mut keep_iter = keep.iter();
+        map.hit_objects.retain(|_| *keep_iter.next().unwrap());
+
+        if has_sounds {
+            let mut keep_iter = keep.iter();
+            map.hit_sounds.retain(|_| *keep_iter.next().unwrap());
+        }
+    }
+
+    if report.is_modified() {
+        map.checksum = None;
+    }
+
+    report
+}
+
+/// Whether the hitobject has non-finite values or, if sliders are checked,
+/// is a slider beyond [`SuspicionConfig::max_slider_position`].
+fn is_invalid(h: &HitObject, config: &SuspicionConfig) -> bool {
+    let max_pos = if config.check_sliders {
+        config.max_slider_position
+    } else {
+        f32::INFINITY
+    };
+
+    let invalid_pos = |x: f32, y: f32| !(x.is_finite() && y.is_finite());
+    let far_pos = |x: f32, y: f32| invalid_pos(x, y) || x.abs() > max_pos || y.abs() > max_pos;
+
+    if !h.start_time.is_finite() || invalid_pos(h.pos.x, h.pos.y) {
+        return true;
+    }
+
+    match h.kind {
+        HitObjectKind::Circle => false,
+        HitObjectKind::Slider(ref slider) => {
+            far_pos(h.pos.x, h.pos.y)
+                || slider
+                    .control_points
+                    .iter()
+                    .any(|point| far_pos(h.pos.x + point.pos.x, h.pos.y + point.pos.y))
+                || slider.expected_dist.is_some_and(|dist| !dist.is_finite())
+        }
+        HitObjectKind::Spinner(ref spinner) => !spinner.duration.is_finite(),
+        HitObjectKind::Hold(ref hold) => !hold.duration.is_finite(),
+    }
+}
+
+/// Hitobjects are considered duplicates if they start at the same time and
+/// position.
+fn is_duplicate(prev: &HitObject, curr: &HitObject) -> bool {
+    (prev.start_time - curr.start_time).abs() < f64::EPSILON && prev.pos == curr.pos
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::section::general::GameMode;
+
+    use crate::model::{
+        beatmap::{BeatmapBuilder, TooSuspicious},
+        control_point::TimingPoint,
+        hit_object::{PathControlPoint, Pos},
+    };
+
+    use super::*;
+
+    #[test]
+    fn sanitize() {
+        let control_points = vec![
+            PathControlPoint::new(Pos::new(0.0, 0.0)),
+            PathControlPoint::new(Pos::new(100.0, 0.0)),
+        ];
+
+        let mut builder = BeatmapBuilder::new()
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .circle(Pos::new(100.0, 100.0), 1000.0)
+            .circle(Pos::new(100.0, 100.0), 1000.0)
+            .circle(Pos::new(200.0, 100.0), 1000.0)
+            .circle(Pos::new(300.0, 100.0), 2000.0);
+
+        for i in 0..300 {
+            let pos = Pos::new(256.0, 192.0);
+            let start_time = f64::from(3000 + 100 * i);
+            builder = builder.slider(pos, start_time, control_points.clone(), 2000, None);
+        }
+
+        let mut map = builder.build().unwrap();
+        map.hit_objects[3].pos = Pos::new(f32::NAN, 0.0);
+
+        assert_eq!(map.check_suspicion(), Err(TooSuspicious::SliderRepeats));
+
+        let report = map.sanitize();
+
+        assert_eq!(
+            report,
+            SanitizeReport {
+                clamped_repeats: 300,
+                removed_invalid: 1,
+                merged_duplicates: 1,
+            }
+        );
+
+        assert_eq!(map.hit_objects.len(), 302);
+        assert_eq!(map.hit_sounds.len(), 302);
+        assert!(map.check_suspicion().is_ok());
+
+        let HitObjectKind::Slider(ref slider) = map.hit_objects[2].kind else {
+            panic!("expected slider");
+        };
+
+        assert_eq!(slider.repeats, 1000);
+        assert_eq!(slider.node_sounds.len(), 1002);
+
+        assert!(!map.sanitize().is_modified());
+    }
+
+    #[test]
+    fn unchecked_sliders() {
+        let control_points = vec![
+            PathControlPoint::new(Pos::new(0.0, 0.0)),
+            PathControlPoint::new(Pos::new(20_000.0, 0.0)),
+        ];
+
+        let mut map = BeatmapBuilder::new()
+            .mode(GameMode::Taiko)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .circle(Pos::new(100.0, 100.0), 1000.0)
+            .slider(Pos::new(256.0, 192.0), 2000.0, control_points, 2000, None)
+            .build()
+            .unwrap();
+
+        let expected = map.clone();
+
+        assert!(!SuspicionConfig::new(GameMode::Taiko).check_sliders);
+        assert!(map.check_suspicion().is_ok());
+        assert!(!map.sanitize().is_modified());
+        assert_eq!(map, expected);
+    }
+
+    #[test]
+    fn unmodified() {
+        let mut map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let expected = map.clone();
+
+        assert_eq!(map.mode, GameMode::Osu);
+        assert!(!map.sanitize().is_modified());
+        assert_eq!(map, expected);
+    }
+}
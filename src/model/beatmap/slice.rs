@@ -0,0 +1,130 @@
+use super::{Beatmap, BreakPeriod};
+
+pub fn slice(map: &Beatmap, start_time: f64, end_time: f64) -> Beatmap {
+    let start_idx = map
+        .hit_objects
+        .partition_point(|h| h.start_time < start_time);
+
+    let end_idx =
+        start_idx + map.hit_objects[start_idx..].partition_point(|h| h.start_time <= end_time);
+
+    let hit_sounds = if map.hit_sounds.len() == map.hit_objects.len() {
+        map.hit_sounds[start_idx..end_idx].to_vec()
+    } else {
+        // Converted mania maps don't have hitsounds
+        Vec::new()
+    };
+
+    let breaks = map
+        .breaks
+        .iter()
+        .filter(|b| b.end_time > start_time && b.start_time < end_time)
+        .map(|b| BreakPeriod {
+            start_time: b.start_time.max(start_time),
+            end_time: b.end_time.min(end_time),
+        })
+        .collect();
+
+    Beatmap {
+        version: map.version,
+        is_convert: map.is_convert,
+        checksum: None,
+        stack_leniency: map.stack_leniency,
+        mode: map.mode,
+        metadata: map.metadata.clone(),
+        ar: map.ar,
+        cs: map.cs,
+        hp: map.hp,
+        od: map.od,
+        slider_multiplier: map.slider_multiplier,
+        slider_tick_rate: map.slider_tick_rate,
+        breaks,
+        timing_points: slice_points(&map.timing_points, |p| p.time, start_time, end_time),
+        difficulty_points: slice_points(&map.difficulty_points, |p| p.time, start_time, end_time),
+        effect_points: slice_points(&map.effect_points, |p| p.time, start_time, end_time),
+        hit_objects: map.hit_objects[start_idx..end_idx].to_vec(),
+        hit_sounds,
+    }
+}
+
+/// Keeps the control points within the range as well as the one that is
+/// active at `start_time`.
+fn slice_points<T: Clone>(
+    points: &[T],
+    time: fn(&T) -> f64,
+    start_time: f64,
+    end_time: f64,
+) -> Vec<T> {
+    let start_idx = points
+        .partition_point(|p| time(p) <= start_time)
+        .saturating_sub(1);
+
+    let end_idx = start_idx + points[start_idx..].partition_point(|p| time(p) <= end_time);
+
+    points[start_idx..end_idx].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{model::mode::GameMode, Difficulty};
+
+    use super::*;
+
+    #[test]
+    fn whole_map() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let sliced = map.slice(f64::NEG_INFINITY, f64::INFINITY);
+
+        assert_eq!(sliced, map);
+    }
+
+    #[test]
+    fn section() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let sliced = map.slice(20_000.0, 40_000.0);
+
+        let expected = map
+            .hit_objects
+            .iter()
+            .filter(|h| (20_000.0..=40_000.0).contains(&h.start_time))
+            .count();
+
+        assert_eq!(sliced.hit_objects.len(), expected);
+        assert_eq!(sliced.hit_sounds.len(), expected);
+        assert!(sliced.timing_points[0].time <= 20_000.0);
+        assert!(sliced.timing_points.iter().all(|p| p.time <= 40_000.0));
+        assert_eq!(
+            sliced.timing_point_at(20_000.0),
+            map.timing_point_at(20_000.0)
+        );
+        assert_eq!(
+            sliced.difficulty_point_at(30_000.0),
+            map.difficulty_point_at(30_000.0)
+        );
+
+        let stars = Difficulty::new().calculate(&sliced).stars();
+
+        assert!(stars > 0.0);
+    }
+
+    #[test]
+    fn same_as_passed_objects() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        assert_eq!(map.mode, GameMode::Osu);
+
+        let passed_objects = 200;
+        let end_time = map.hit_objects[passed_objects - 1].start_time;
+        let sliced = map.slice(0.0, end_time);
+
+        assert_eq!(sliced.hit_objects.len(), passed_objects);
+
+        let expected = Difficulty::new()
+            .passed_objects(passed_objects as u32)
+            .calculate(&map)
+            .stars();
+
+        let stars = Difficulty::new().calculate(&sliced).stars();
+
+        assert!((stars - expected).abs() < 1e-10, "{stars} != {expected}");
+    }
+}
@@ -0,0 +1,231 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+
+use crate::model::hit_object::HitObjectKind;
+
+use super::{
+    timing::{object_end_time, slider_duration},
+    Beatmap,
+};
+
+/// Length and object statistics of a [`Beatmap`].
+///
+/// All durations are in milliseconds and adjusted to the clock rate. Slider
+/// durations are based on the map's mode so to get statistics of a converted
+/// map, the [`Beatmap`] should be converted first.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeatmapStatistics {
+    /// The clock rate that the values were adjusted to.
+    pub clock_rate: f64,
+    /// The duration between the first hitobject's start and the last
+    /// hitobject's end.
+    pub total_length: f64,
+    /// The total length without breaks.
+    pub drain_length: f64,
+    pub n_circles: u32,
+    pub n_sliders: u32,
+    pub n_spinners: u32,
+    pub n_holds: u32,
+    /// The average amount of hitobjects per second of drain length.
+    pub avg_nps: f64,
+    /// The highest amount of hitobjects whose start time is within a window
+    /// of [`BeatmapStatistics::PEAK_WINDOW`] milliseconds.
+    pub peak_nps: f64,
+    /// Durations of all sliders.
+    pub slider_durations: ValueStatistics,
+    /// Path lengths of all sliders in osu!pixels, including repeats.
+    pub slider_distances: ValueStatistics,
+    /// Durations of all spinners.
+    pub spinner_durations: ValueStatistics,
+}
+
+impl BeatmapStatistics {
+    /// The size of the sliding window for [`BeatmapStatistics::peak_nps`] in
+    /// milliseconds.
+    pub const PEAK_WINDOW: f64 = 1000.0;
+
+    /// The total amount of hitobjects.
+    pub const fn n_objects(&self) -> u32 {
+        self.n_circles + self.n_sliders + self.n_spinners + self.n_holds
+    }
+}
+
+/// Minimum, maximum, and average of a set of values.
+///
+/// All values are `0.0` if the set is empty.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct ValueStatistics {
+    pub min: f64,
+    pub max: f64,
+    pub avg: f64,
+}
+
+impl ValueStatistics {
+    fn new(values: &[f64]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        let mut min = f64::INFINITY;
+        let mut max = f64::NEG_INFINITY;
+        let mut sum = 0.0;
+
+        for &value in values {
+            min = min.min(value);
+            max = max.max(value);
+            sum += value;
+        }
+
+        Self {
+            min,
+            max,
+            avg: sum / values.len() as f64,
+        }
+    }
+}
+
+pub fn statistics(map: &Beatmap, clock_rate: f64) -> BeatmapStatistics {
+    let mut bufs = CurveBuffers::default();
+
+    let mut n_circles = 0;
+    let mut n_sliders = 0;
+    let mut n_spinners = 0;
+    let mut n_holds = 0;
+
+    let mut slider_durations = Vec::new();
+    let mut slider_distances = Vec::new();
+    let mut spinner_durations = Vec::new();
+
+    let mut last_end_time = f64::NEG_INFINITY;
+
+    for h in map.hit_objects.iter() {
+        match h.kind {
+            HitObjectKind::Circle => n_circles += 1,
+            HitObjectKind::Slider(ref slider) => {
+                n_sliders += 1;
+
+                let duration = slider_duration(map, h.start_time, slider, &mut bufs);
+                let dist = slider.span_count() as f64 * slider.curve(map.mode, &mut bufs).dist();
+
+                slider_durations.push(duration / clock_rate);
+                slider_distances.push(dist);
+            }
+            HitObjectKind::Spinner(ref spinner) => {
+                n_spinners += 1;
+                spinner_durations.push(spinner.duration / clock_rate);
+            }
+            HitObjectKind::Hold(_) => n_holds += 1,
+        }
+
+        last_end_time = last_end_time.max(object_end_time(map, h, &mut bufs));
+    }
+
+    let total_length = map
+        .hit_objects
+        .first()
+        .map_or(0.0, |first| (last_end_time - first.start_time) / clock_rate);
+
+    let drain_length = (total_length - map.total_break_time() / clock_rate).max(0.0);
+
+    let n_objects = map.hit_objects.len();
+
+    let avg_nps = if drain_length > 0.0 {
+        n_objects as f64 / (drain_length / 1000.0)
+    } else {
+        0.0
+    };
+
+    BeatmapStatistics {
+        clock_rate,
+        total_length,
+        drain_length,
+        n_circles,
+        n_sliders,
+        n_spinners,
+        n_holds,
+        avg_nps,
+        peak_nps: peak_nps(map, clock_rate),
+        slider_durations: ValueStatistics::new(&slider_durations),
+        slider_distances: ValueStatistics::new(&slider_distances),
+        spinner_durations: ValueStatistics::new(&spinner_durations),
+    }
+}
+
+fn peak_nps(map: &Beatmap, clock_rate: f64) -> f64 {
+    // Hitobjects are sorted by start time
+    let window = BeatmapStatistics::PEAK_WINDOW * clock_rate;
+    let mut start_idx = 0;
+    let mut peak = 0;
+
+    for (end_idx, h) in map.hit_objects.iter().enumerate() {
+        while h.start_time - map.hit_objects[start_idx].start_time >= window {
+            start_idx += 1;
+        }
+
+        peak = peak.max(end_idx + 1 - start_idx);
+    }
+
+    peak as f64 * 1000.0 / BeatmapStatistics::PEAK_WINDOW
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::{
+            beatmap::{BeatmapBuilder, BreakPeriod},
+            control_point::TimingPoint,
+            hit_object::Pos,
+        },
+        GameMods,
+    };
+
+    use super::*;
+
+    #[test]
+    fn counts() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let stats = map.statistics(1.0);
+
+        assert_eq!(stats.n_objects() as usize, map.hit_objects.len());
+        assert_eq!(
+            stats.n_sliders as usize,
+            map.hit_objects.iter().filter(|h| h.is_slider()).count()
+        );
+        assert!(stats.drain_length <= stats.total_length);
+        assert!(stats.avg_nps <= stats.peak_nps);
+        assert!(stats.slider_durations.min <= stats.slider_durations.avg);
+        assert!(stats.slider_durations.avg <= stats.slider_durations.max);
+
+        let clock_rate = GameMods::from(64).clock_rate();
+        let dt = map.statistics(clock_rate);
+
+        assert!((dt.total_length - stats.total_length / 1.5).abs() < 1e-7);
+        assert!((dt.avg_nps - stats.avg_nps * 1.5).abs() < 1e-7);
+        assert!(dt.peak_nps >= stats.peak_nps);
+        assert_eq!(dt.slider_distances, stats.slider_distances);
+    }
+
+    #[test]
+    fn lengths() {
+        let map = BeatmapBuilder::new()
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .circle(Pos::new(0.0, 0.0), 1000.0)
+            .circle(Pos::new(0.0, 0.0), 1500.0)
+            .circle(Pos::new(0.0, 0.0), 1600.0)
+            .spinner(Pos::new(256.0, 192.0), 5000.0, 1000.0)
+            .break_period(BreakPeriod {
+                start_time: 2000.0,
+                end_time: 4000.0,
+            })
+            .build()
+            .unwrap();
+
+        let stats = map.statistics(1.0);
+
+        assert!((stats.total_length - 5000.0).abs() < f64::EPSILON);
+        assert!((stats.drain_length - 3000.0).abs() < f64::EPSILON);
+        assert!((stats.avg_nps - 4.0 / 3.0).abs() < 1e-10);
+        assert!((stats.peak_nps - 3.0).abs() < f64::EPSILON);
+        assert!((stats.spinner_durations.max - 1000.0).abs() < f64::EPSILON);
+        assert_eq!(stats.slider_durations, ValueStatistics::default());
+    }
+}
@@ -0,0 +1,209 @@
+use std::{borrow::Cow, error, fmt, fs, io, path::Path, str::from_utf8 as str_from_utf8};
+
+use rosu_map::{section::Section, util::ParseNumber, DecodeBeatmap, DecodeState};
+
+use super::{Beatmap, BeatmapState, ParseBeatmapError};
+
+/// A [`Beatmap`] that was decoded through [`Beatmap::from_bytes_strict`]
+/// alongside all non-fatal errors that were encountered.
+#[derive(Debug)]
+pub struct StrictDecode {
+    pub map: Beatmap,
+    /// Lines that could not be parsed and were skipped, e.g. invalid
+    /// hitobjects or timing points.
+    pub warnings: Vec<LineError>,
+}
+
+impl StrictDecode {
+    pub(super) fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, StrictDecodeError> {
+        let bytes = fs::read(path).map_err(StrictDecodeError::Io)?;
+
+        Self::from_bytes(&bytes)
+    }
+
+    pub(super) fn from_bytes(bytes: &[u8]) -> Result<Self, StrictDecodeError> {
+        // `rosu_map`'s `DecodeBeatmap::decode` neither tracks line numbers
+        // nor rejects a missing format version so the lines are iterated
+        // here while still deferring to `rosu_map` for skipping lines,
+        // detecting sections, and parsing each line.
+        let content = decode_text(bytes);
+
+        let mut lines = content
+            .lines()
+            .map(str::trim_end)
+            .enumerate()
+            .map(|(i, line)| (i + 1, line));
+
+        let Some((line, version)) = lines.find(|(_, line)| !line.is_empty()) else {
+            return Err(StrictDecodeError::FileFormat { line: 0 });
+        };
+
+        let version = parse_version(version).ok_or(StrictDecodeError::FileFormat { line })?;
+
+        let mut state = BeatmapState::create(version);
+        let mut section = None;
+        let mut warnings = Vec::new();
+
+        for (line, content) in lines {
+            if <Beatmap as DecodeBeatmap>::should_skip_line(content) {
+                continue;
+            }
+
+            if let Some(next) = Section::try_from_line(content) {
+                section = Some(next);
+
+                continue;
+            }
+
+            // Lines before the first section are ignored
+            let Some(section) = section else { continue };
+
+            let Err(error) = parse_line(&mut state, section, content) else {
+                continue;
+            };
+
+            match section {
+                Section::General | Section::Difficulty => {
+                    return Err(StrictDecodeError::Line {
+                        section,
+                        line,
+                        error,
+                    })
+                }
+                _ => warnings.push(LineError {
+                    section,
+                    line,
+                    error,
+                }),
+            }
+        }
+
+        Ok(Self {
+            map: state.into(),
+            warnings,
+        })
+    }
+}
+
+fn parse_line(
+    state: &mut BeatmapState,
+    section: Section,
+    line: &str,
+) -> Result<(), ParseBeatmapError> {
+    match section {
+        Section::General => Beatmap::parse_general(state, line),
+        Section::Editor => Beatmap::parse_editor(state, line),
+        Section::Metadata => Beatmap::parse_metadata(state, line),
+        Section::Difficulty => Beatmap::parse_difficulty(state, line),
+        Section::Events => Beatmap::parse_events(state, line),
+        Section::TimingPoints => Beatmap::parse_timing_points(state, line),
+        Section::Colors => Beatmap::parse_colors(state, line),
+        Section::HitObjects => Beatmap::parse_hit_objects(state, line),
+        Section::Variables => Beatmap::parse_variables(state, line),
+        Section::CatchTheBeat => Beatmap::parse_catch_the_beat(state, line),
+        Section::Mania => Beatmap::parse_mania(state, line),
+    }
+}
+
+fn parse_version(line: &str) -> Option<i32> {
+    const VERSION_PREFIX: &str = "osu file format v";
+
+    line.strip_prefix(VERSION_PREFIX)
+        .map(i32::parse)
+        .and_then(Result::ok)
+}
+
+/// Decodes the content of a `.osu` file based on its byte order mark.
+///
+/// Invalid characters are replaced with U+FFFD.
+fn decode_text(bytes: &[u8]) -> Cow<'_, str> {
+    fn decode_utf16(bytes: &[u8], f: fn([u8; 2]) -> u16) -> Cow<'static, str> {
+        let units = bytes.chunks_exact(2).map(|chunk| f([chunk[0], chunk[1]]));
+
+        char::decode_utf16(units)
+            .map(|res| res.unwrap_or(char::REPLACEMENT_CHARACTER))
+            .collect::<String>()
+            .into()
+    }
+
+    match bytes {
+        [0xEF, 0xBB, 0xBF, rest @ ..] => String::from_utf8_lossy(rest),
+        [0xFF, 0xFE, rest @ ..] => decode_utf16(rest, u16::from_le_bytes),
+        [0xFE, 0xFF, rest @ ..] => decode_utf16(rest, u16::from_be_bytes),
+        _ => match str_from_utf8(bytes) {
+            Ok(s) => Cow::Borrowed(s),
+            Err(_) => String::from_utf8_lossy(bytes),
+        },
+    }
+}
+
+/// A line of a `.osu` file that could not be parsed.
+#[derive(Debug)]
+pub struct LineError {
+    /// The section in which the line is located.
+    pub section: Section,
+    /// The 1-based line number.
+    pub line: usize,
+    /// The reason why parsing failed.
+    pub error: ParseBeatmapError,
+}
+
+impl error::Error for LineError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse line {} in section {:?}",
+            self.line, self.section
+        )
+    }
+}
+
+/// All the ways that strictly decoding a [`Beatmap`] can fail.
+#[derive(Debug)]
+pub enum StrictDecodeError {
+    /// The content did not start with a valid `osu file format v[version]`
+    /// line.
+    ///
+    /// If the content has no non-empty line, `line` will be `0`.
+    FileFormat { line: usize },
+    /// Failed to read the file.
+    Io(io::Error),
+    /// A line of the `[General]` or `[Difficulty]` section could not be
+    /// parsed.
+    Line {
+        /// The section in which the line is located.
+        section: Section,
+        /// The 1-based line number.
+        line: usize,
+        /// The reason why parsing failed.
+        error: ParseBeatmapError,
+    },
+}
+
+impl error::Error for StrictDecodeError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            StrictDecodeError::FileFormat { .. } => None,
+            StrictDecodeError::Io(err) => Some(err),
+            StrictDecodeError::Line { error, .. } => Some(error),
+        }
+    }
+}
+
+impl fmt::Display for StrictDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::FileFormat { line } => write!(f, "invalid file format in line {line}"),
+            Self::Io(_) => f.write_str("failed to read file"),
+            Self::Line { section, line, .. } => {
+                write!(f, "failed to parse line {line} in section {section:?}")
+            }
+        }
+    }
+}
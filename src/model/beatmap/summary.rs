@@ -0,0 +1,244 @@
+use std::{io, path::Path};
+
+use rosu_map::{
+    section::{
+        difficulty::Difficulty,
+        general::{GameMode, GeneralKey},
+        hit_objects::HitObjectType,
+    },
+    util::{KeyValue, ParseNumber, StrExt},
+    DecodeBeatmap, DecodeState,
+};
+
+use super::{
+    decode::{clamp_difficulty, parse_difficulty},
+    Beatmap, ParseBeatmapError,
+};
+
+/// A lightweight summary of a [`Beatmap`].
+///
+/// Decoding a summary only considers the `[General]` and `[Difficulty]`
+/// sections as well as the start time and type of each hitobject. Notably,
+/// sliders' control points, hitsounds, and timing points are skipped which
+/// makes it considerably faster than decoding a full [`Beatmap`].
+///
+/// Since hitobject lines are only partially parsed, invalid lines may still be
+/// counted towards the summary even though they would be skipped when decoding
+/// a full [`Beatmap`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct BeatmapSummary {
+    pub version: i32,
+    pub mode: GameMode,
+
+    // Difficulty
+    pub ar: f32,
+    pub cs: f32,
+    pub hp: f32,
+    pub od: f32,
+    pub slider_multiplier: f64,
+    pub slider_tick_rate: f64,
+
+    // HitObjects
+    pub n_circles: u32,
+    pub n_sliders: u32,
+    pub n_spinners: u32,
+    pub n_holds: u32,
+    /// Start time of the earliest hitobject or `0.0` if there are none.
+    pub first_object_time: f64,
+    /// Start time of the latest hitobject or `0.0` if there are none.
+    pub last_object_time: f64,
+}
+
+impl BeatmapSummary {
+    /// Decode a [`BeatmapSummary`] by providing a path to a `.osu` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, io::Error> {
+        rosu_map::from_path(path)
+    }
+
+    /// Decode a [`BeatmapSummary`] by providing the content of a `.osu` file
+    /// as a slice of bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, io::Error> {
+        rosu_map::from_bytes(bytes)
+    }
+
+    /// The total amount of hitobjects.
+    pub const fn n_objects(&self) -> u32 {
+        self.n_circles + self.n_sliders + self.n_spinners + self.n_holds
+    }
+
+    /// Decode the full [`Beatmap`] of the `.osu` file at the given path.
+    ///
+    /// The path should be the one that this summary was decoded from.
+    pub fn upgrade_from_path<P: AsRef<Path>>(&self, path: P) -> Result<Beatmap, io::Error> {
+        Beatmap::from_path(path).and_then(|map| self.check_upgrade(map))
+    }
+
+    /// Decode the full [`Beatmap`] of the given `.osu` file content.
+    ///
+    /// The content should be the one that this summary was decoded from.
+    pub fn upgrade_from_bytes(&self, bytes: &[u8]) -> Result<Beatmap, io::Error> {
+        Beatmap::from_bytes(bytes).and_then(|map| self.check_upgrade(map))
+    }
+
+    fn check_upgrade(&self, map: Beatmap) -> Result<Beatmap, io::Error> {
+        if map.version == self.version && map.mode == self.mode {
+            Ok(map)
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "content does not match the summary",
+            ))
+        }
+    }
+}
+
+/// The state of a [`BeatmapSummary`] for [`DecodeBeatmap`].
+pub struct BeatmapSummaryState {
+    version: i32,
+    mode: GameMode,
+    has_approach_rate: bool,
+    difficulty: Difficulty,
+    n_circles: u32,
+    n_sliders: u32,
+    n_spinners: u32,
+    n_holds: u32,
+    first_object_time: f64,
+    last_object_time: f64,
+}
+
+impl DecodeState for BeatmapSummaryState {
+    fn create(version: i32) -> Self {
+        Self {
+            version,
+            mode: GameMode::Osu,
+            has_approach_rate: false,
+            difficulty: Difficulty::default(),
+            n_circles: 0,
+            n_sliders: 0,
+            n_spinners: 0,
+            n_holds: 0,
+            first_object_time: f64::INFINITY,
+            last_object_time: f64::NEG_INFINITY,
+        }
+    }
+}
+
+impl From<BeatmapSummaryState> for BeatmapSummary {
+    fn from(state: BeatmapSummaryState) -> Self {
+        let Difficulty {
+            hp_drain_rate,
+            circle_size,
+            overall_difficulty,
+            approach_rate,
+            slider_multiplier,
+            slider_tick_rate,
+        } = clamp_difficulty(&state.difficulty, state.mode);
+
+        let (first_object_time, last_object_time) =
+            if state.first_object_time <= state.last_object_time {
+                (state.first_object_time, state.last_object_time)
+            } else {
+                (0.0, 0.0)
+            };
+
+        Self {
+            version: state.version,
+            mode: state.mode,
+            ar: approach_rate,
+            cs: circle_size,
+            hp: hp_drain_rate,
+            od: overall_difficulty,
+            slider_multiplier,
+            slider_tick_rate,
+            n_circles: state.n_circles,
+            n_sliders: state.n_sliders,
+            n_spinners: state.n_spinners,
+            n_holds: state.n_holds,
+            first_object_time,
+            last_object_time,
+        }
+    }
+}
+
+impl DecodeBeatmap for BeatmapSummary {
+    type Error = ParseBeatmapError;
+    type State = BeatmapSummaryState;
+
+    fn parse_general(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        let Ok(KeyValue { key, value }) = KeyValue::parse(line.trim_comment()) else {
+            return Ok(());
+        };
+
+        if let GeneralKey::Mode = key {
+            state.mode = value.parse()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse_editor(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_metadata(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_difficulty(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        parse_difficulty(&mut state.difficulty, &mut state.has_approach_rate, line)
+    }
+
+    fn parse_events(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_timing_points(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_colors(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_hit_objects(state: &mut Self::State, line: &str) -> Result<(), Self::Error> {
+        let mut split = line.trim_comment().split(',');
+
+        let (Some(_x), Some(_y), Some(start_time), Some(kind)) =
+            (split.next(), split.next(), split.next(), split.next())
+        else {
+            return Err(ParseBeatmapError::InvalidHitObjectLine);
+        };
+
+        let start_time = f64::parse(start_time)?;
+        let hit_object_type: HitObjectType = kind.parse()?;
+
+        if hit_object_type.has_flag(HitObjectType::CIRCLE) {
+            state.n_circles += 1;
+        } else if hit_object_type.has_flag(HitObjectType::SLIDER) {
+            state.n_sliders += 1;
+        } else if hit_object_type.has_flag(HitObjectType::SPINNER) {
+            state.n_spinners += 1;
+        } else if hit_object_type.has_flag(HitObjectType::HOLD) {
+            state.n_holds += 1;
+        } else {
+            return Err(ParseBeatmapError::UnknownHitObjectType);
+        }
+
+        state.first_object_time = state.first_object_time.min(start_time);
+        state.last_object_time = state.last_object_time.max(start_time);
+
+        Ok(())
+    }
+
+    fn parse_variables(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_catch_the_beat(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn parse_mania(_: &mut Self::State, _: &str) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
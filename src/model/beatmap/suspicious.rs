@@ -11,13 +11,17 @@ use super::Beatmap;
 
 /// Resulting error type of [`Beatmap::check_suspicion`].
 ///
+/// For more details use [`Beatmap::check_suspicion_with`] which returns a
+/// [`SuspicionReport`].
+///
 /// If you feel like a [`Beatmap`] is incorrectly flagged as suspicious or if
 /// a map should be flagged but isn't, please open an issue so the heuristic
 /// can be improved.
 ///
 /// [`Beatmap::check_suspicion`]: crate::model::beatmap::Beatmap::check_suspicion
+/// [`Beatmap::check_suspicion_with`]: crate::model::beatmap::Beatmap::check_suspicion_with
 /// [`Beatmap`]: crate::model::beatmap::Beatmap
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum TooSuspicious {
     /// Notes are too dense time-wise.
@@ -34,124 +38,272 @@ pub enum TooSuspicious {
     SliderRepeats,
 }
 
-impl TooSuspicious {
-    pub(crate) fn new(map: &Beatmap) -> Option<Self> {
-        #[inline]
-        const fn too_long(hit_objects: &[HitObject]) -> bool {
-            const DAY_MS: u32 = 60 * 60 * 24 * 1000;
+/// Thresholds for [`Beatmap::check_suspicion_with`].
+///
+/// [`SuspicionConfig::new`] provides the thresholds that
+/// [`Beatmap::check_suspicion`] uses for each mode.
+///
+/// [`Beatmap::check_suspicion_with`]: crate::model::beatmap::Beatmap::check_suspicion_with
+/// [`Beatmap::check_suspicion`]: crate::model::beatmap::Beatmap::check_suspicion
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SuspicionConfig {
+    /// The maximum amount of hitobjects.
+    pub max_objects: usize,
+    /// The maximum duration between the first and last hitobject's start time
+    /// in milliseconds.
+    pub max_length: f64,
+    /// Limits on how many hitobjects may start within a time window.
+    pub density_limits: Vec<DensityLimit>,
+    /// Whether sliders' positions and repeats should be checked.
+    pub check_sliders: bool,
+    /// The maximum absolute value of a slider's x and y coordinate.
+    pub max_slider_position: f32,
+    /// The maximum amount of repeats of a slider.
+    pub max_slider_repeats: usize,
+    /// The maximum amount of sliders beyond [`SuspicionConfig::max_slider_position`].
+    pub max_suspicious_positions: usize,
+    /// The maximum amount of sliders beyond [`SuspicionConfig::max_slider_repeats`].
+    pub max_suspicious_repeats: usize,
+}
 
-            if unlikely(hit_objects.len() < 2) {
-                return false;
-            }
+/// A limit on the amount of hitobjects within a time window.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DensityLimit {
+    /// The size of the window in milliseconds.
+    pub window: f64,
+    /// The maximum amount of hitobjects within the window.
+    pub max_objects: usize,
+}
 
-            let [first, .., last] = hit_objects else {
-                unreachable!()
-            };
+impl SuspicionConfig {
+    /// The default thresholds for the given mode.
+    pub fn new(mode: GameMode) -> Self {
+        const DAY_MS: f64 = (60 * 60 * 24 * 1000) as f64;
 
-            (last.start_time - first.start_time) > DAY_MS as f64
-        }
+        let max_objects = match mode {
+            // Taiko calculation is especially expensive for high object counts
+            GameMode::Taiko => 20_000,
+            _ => 500_000,
+        };
 
-        #[inline]
-        fn too_many_objects(map: &Beatmap) -> bool {
-            const THRESHOLD: usize = 500_000;
-            /// Taiko calculation is especially expensive for high object counts
-            const THRESHOLD_TAIKO: usize = 20_000;
-
-            match map.mode {
-                GameMode::Taiko => map.hit_objects.len() > THRESHOLD_TAIKO,
-                _ => map.hit_objects.len() > THRESHOLD,
-            }
+        let density_limits = if mode == GameMode::Mania {
+            // In mania it's more common to have a high note density
+            vec![
+                // 200 4K notes per 1s = 3000BPM
+                DensityLimit {
+                    window: 1000.0,
+                    max_objects: 200,
+                },
+                // 500 4K notes per 10s = 750BPM
+                DensityLimit {
+                    window: 10_000.0,
+                    max_objects: 500,
+                },
+            ]
+        } else {
+            vec![
+                // 100 notes per 1s = 6000BPM
+                DensityLimit {
+                    window: 1000.0,
+                    max_objects: 100,
+                },
+                // 250 notes per 10s = 1500BPM
+                DensityLimit {
+                    window: 10_000.0,
+                    max_objects: 250,
+                },
+            ]
+        };
+
+        Self {
+            max_objects,
+            max_length: DAY_MS,
+            density_limits,
+            // Taiko and Mania calculations aren't as susceptible to malicious
+            // slider values
+            check_sliders: matches!(mode, GameMode::Osu | GameMode::Catch),
+            // osu!'s max value is `131_072` and the playfield is `512x384`
+            max_slider_position: 10_000.0,
+            // osu!'s max value is `9000`
+            max_slider_repeats: 1000,
+            max_suspicious_positions: 256,
+            max_suspicious_repeats: 256,
         }
+    }
+}
 
-        #[inline]
-        fn too_dense(i: usize, curr: &HitObject, map: &Beatmap) -> bool {
-            const fn too_dense<const PER_1S: usize, const PER_10S: usize>(
-                i: usize,
-                curr: &HitObject,
-                hit_objects: &[HitObject],
-            ) -> bool {
-                (hit_objects.len() > i + PER_1S
-                    && hit_objects[i + PER_1S].start_time - curr.start_time < 1000.0)
-                    || (hit_objects.len() > i + PER_10S
-                        && hit_objects[i + PER_10S].start_time - curr.start_time < 10_000.0)
-            }
+/// Resulting error type of [`Beatmap::check_suspicion_with`].
+///
+/// [`Beatmap::check_suspicion_with`]: crate::model::beatmap::Beatmap::check_suspicion_with
+#[derive(Clone, Debug, PartialEq)]
+pub struct SuspicionReport {
+    pub reason: TooSuspicious,
+    /// Index of the offending hitobject, if any.
+    ///
+    /// For [`TooSuspicious::SliderPositions`] and
+    /// [`TooSuspicious::SliderRepeats`] this is the first slider beyond the
+    /// threshold.
+    pub object_idx: Option<usize>,
+    /// Start time of the offending hitobject, if any.
+    pub time: Option<f64>,
+    /// The measured value.
+    pub value: f64,
+    /// The threshold that `value` exceeded.
+    pub threshold: f64,
+}
 
-            match map.mode {
-                GameMode::Mania => {
-                    // In mania it's more common to have a high note density
-                    const THRESHOLD_1S: usize = 200; // 200 4K notes per 1s = 3000BPM
-                    const THRESHOLD_10S: usize = 500; // 500 4K notes per 10s = 750BPM
+impl SuspicionReport {
+    const fn new(reason: TooSuspicious, value: f64, threshold: f64) -> Self {
+        Self {
+            reason,
+            object_idx: None,
+            time: None,
+            value,
+            threshold,
+        }
+    }
 
-                    too_dense::<THRESHOLD_1S, THRESHOLD_10S>(i, curr, &map.hit_objects)
-                }
-                _ => {
-                    const THRESHOLD_1S: usize = 100; // 100 notes per 1s = 6000BPM
-                    const THRESHOLD_10S: usize = 250; // 250 notes per 10s = 1500BPM
+    const fn object(mut self, idx: usize, h: &HitObject) -> Self {
+        self.object_idx = Some(idx);
+        self.time = Some(h.start_time);
 
-                    too_dense::<THRESHOLD_1S, THRESHOLD_10S>(i, curr, &map.hit_objects)
-                }
-            }
-        }
+        self
+    }
 
+    pub(crate) fn check(map: &Beatmap, config: &SuspicionConfig) -> Option<Self> {
         #[inline]
-        const fn check_pos(pos: Pos) -> bool {
-            /// osu!'s max value is `131_072` and the playfield is `512x384`
-            const THRESHOLD: f32 = 10_000.0;
-
-            f32::abs(pos.x) > THRESHOLD || f32::abs(pos.y) > THRESHOLD
+        fn check_pos(pos: Pos, threshold: f32) -> bool {
+            f32::abs(pos.x) > threshold || f32::abs(pos.y) > threshold
         }
 
-        #[inline]
-        const fn check_repeats(repeats: usize) -> bool {
-            /// osu!'s max value is `9000`
-            const THRESHOLD: usize = 1000;
+        let hit_objects = &map.hit_objects;
 
-            repeats > THRESHOLD
+        if unlikely(hit_objects.len() > config.max_objects) {
+            return Some(Self::new(
+                TooSuspicious::ObjectCount,
+                hit_objects.len() as f64,
+                config.max_objects as f64,
+            ));
         }
 
-        if unlikely(too_many_objects(map)) {
-            return Some(Self::ObjectCount);
-        } else if unlikely(too_long(&map.hit_objects)) {
-            return Some(Self::Length);
+        if let [first, .., last] = hit_objects.as_slice() {
+            let len = last.start_time - first.start_time;
+
+            if unlikely(len > config.max_length) {
+                return Some(Self::new(TooSuspicious::Length, len, config.max_length));
+            }
         }
 
         let mut pos_beyond_threshold = 0;
+        let mut first_pos_beyond = None;
         let mut repeats_beyond_threshold = 0;
+        let mut first_repeats_beyond = None;
 
-        for (i, h) in map.hit_objects.iter().enumerate() {
-            if unlikely(too_dense(i, h, map)) {
-                return Some(Self::Density);
+        for (i, h) in hit_objects.iter().enumerate() {
+            for limit in config.density_limits.iter() {
+                let too_dense = hit_objects.len() > i + limit.max_objects
+                    && hit_objects[i + limit.max_objects].start_time - h.start_time < limit.window;
+
+                if unlikely(too_dense) {
+                    let n_objects = hit_objects[i..]
+                        .partition_point(|next| next.start_time - h.start_time < limit.window);
+
+                    return Some(
+                        Self::new(
+                            TooSuspicious::Density,
+                            n_objects as f64,
+                            limit.max_objects as f64,
+                        )
+                        .object(i, h),
+                    );
+                }
+            }
+
+            if !config.check_sliders {
+                continue;
             }
 
             if let HitObjectKind::Slider(ref slider) = h.kind {
-                if unlikely(check_repeats(slider.repeats)) {
-                    if unlikely(
-                        check_pos(h.pos) && matches!(map.mode, GameMode::Osu | GameMode::Catch),
-                    ) {
-                        return Some(Self::RedFlag);
+                if unlikely(slider.repeats > config.max_slider_repeats) {
+                    if unlikely(check_pos(h.pos, config.max_slider_position)) {
+                        return Some(
+                            Self::new(
+                                TooSuspicious::RedFlag,
+                                slider.repeats as f64,
+                                config.max_slider_repeats as f64,
+                            )
+                            .object(i, h),
+                        );
                     }
 
                     repeats_beyond_threshold += 1;
-                } else if unlikely(check_pos(h.pos)) {
+                    first_repeats_beyond.get_or_insert(i);
+                } else if unlikely(check_pos(h.pos, config.max_slider_position)) {
                     pos_beyond_threshold += 1;
+                    first_pos_beyond.get_or_insert(i);
                 }
             }
         }
 
-        if matches!(map.mode, GameMode::Taiko | GameMode::Mania) {
-            // Taiko and Mania calculations aren't as susceptible to malicious
-            // slider values
-            None
-        } else if unlikely(pos_beyond_threshold > 256) {
-            Some(Self::SliderPositions)
-        } else if unlikely(repeats_beyond_threshold > 256) {
-            Some(Self::SliderRepeats)
+        let report = |reason, count, threshold, first: Option<usize>| {
+            let report = Self::new(reason, count as f64, threshold as f64);
+
+            match first {
+                Some(i) => report.object(i, &hit_objects[i]),
+                None => report,
+            }
+        };
+
+        if unlikely(pos_beyond_threshold > config.max_suspicious_positions) {
+            Some(report(
+                TooSuspicious::SliderPositions,
+                pos_beyond_threshold,
+                config.max_suspicious_positions,
+                first_pos_beyond,
+            ))
+        } else if unlikely(repeats_beyond_threshold > config.max_suspicious_repeats) {
+            Some(report(
+                TooSuspicious::SliderRepeats,
+                repeats_beyond_threshold,
+                config.max_suspicious_repeats,
+                first_repeats_beyond,
+            ))
         } else {
             None
         }
     }
 }
 
+impl error::Error for SuspicionReport {}
+
+impl fmt::Display for SuspicionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (value={}, threshold={}",
+            self.reason, self.value, self.threshold
+        )?;
+
+        if let Some(idx) = self.object_idx {
+            write!(f, ", object_idx={idx}")?;
+        }
+
+        if let Some(time) = self.time {
+            write!(f, ", time={time}")?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+impl From<SuspicionReport> for TooSuspicious {
+    fn from(report: SuspicionReport) -> Self {
+        report.reason
+    }
+}
+
 impl error::Error for TooSuspicious {}
 
 impl fmt::Display for TooSuspicious {
@@ -162,3 +314,51 @@ impl fmt::Display for TooSuspicious {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_config() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        assert!(map.check_suspicion().is_ok());
+        assert!(map
+            .check_suspicion_with(&SuspicionConfig::new(GameMode::Osu))
+            .is_ok());
+    }
+
+    #[test]
+    fn custom_config() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        let config = SuspicionConfig {
+            max_objects: 100,
+            ..SuspicionConfig::new(map.mode)
+        };
+
+        let report = map.check_suspicion_with(&config).unwrap_err();
+
+        assert_eq!(report.reason, TooSuspicious::ObjectCount);
+        assert!((report.value - map.hit_objects.len() as f64).abs() < f64::EPSILON);
+        assert!((report.threshold - 100.0).abs() < f64::EPSILON);
+        assert_eq!(report.object_idx, None);
+
+        let config = SuspicionConfig {
+            density_limits: vec![DensityLimit {
+                window: 1000.0,
+                max_objects: 2,
+            }],
+            ..SuspicionConfig::new(map.mode)
+        };
+
+        let report = map.check_suspicion_with(&config).unwrap_err();
+        let idx = report.object_idx.unwrap();
+
+        assert_eq!(report.reason, TooSuspicious::Density);
+        assert!(report.value > report.threshold);
+        assert_eq!(report.time, Some(map.hit_objects[idx].start_time));
+        assert!(map.hit_objects[idx + 2].start_time - map.hit_objects[idx].start_time < 1000.0);
+    }
+}
@@ -0,0 +1,268 @@
+use rosu_map::section::{general::GameMode, hit_objects::CurveBuffers};
+
+use crate::{
+    model::{
+        control_point::{DifficultyPoint, TimeSignature, TimingPoint},
+        hit_object::{HitObject, HitObjectKind, Slider},
+    },
+    taiko::drum_roll_duration,
+    util::get_precision_adjusted_beat_len,
+};
+
+use super::{bpm::bpm, Beatmap};
+
+/// Summary of a [`Beatmap`]'s timing.
+///
+/// All times are in milliseconds and, just like the bpm values, already
+/// adjusted to the clock rate.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingReport {
+    /// The clock rate that the values were adjusted to.
+    pub clock_rate: f64,
+    /// The lowest bpm of all timing points or [`TimingPoint::DEFAULT_BPM`] if
+    /// there are none.
+    pub min_bpm: f64,
+    /// The highest bpm of all timing points or [`TimingPoint::DEFAULT_BPM`] if
+    /// there are none.
+    pub max_bpm: f64,
+    /// The bpm that is active for the longest duration.
+    ///
+    /// This is the same value as [`Beatmap::bpm`] adjusted to the clock rate
+    /// and thus infinite if the map has no timing points.
+    pub dominant_bpm: f64,
+    /// The end time of the last hitobject.
+    ///
+    /// Unlike [`HitObject::end_time`], this considers the actual duration of
+    /// sliders in the map's mode.
+    pub last_object_end_time: f64,
+    /// All sections of constant bpm until the end of the last hitobject.
+    pub timeline: Vec<BpmSection>,
+    /// All uninherited timing points.
+    pub timing_points: Vec<TimingPoint>,
+}
+
+/// A section of a [`Beatmap`] with constant bpm.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BpmSection {
+    pub start_time: f64,
+    pub end_time: f64,
+    pub bpm: f64,
+    pub time_signature: TimeSignature,
+}
+
+pub fn timing_report(map: &Beatmap, clock_rate: f64) -> TimingReport {
+    let last_object_end_time = last_object_end_time(map);
+
+    let last_time = if map.hit_objects.is_empty() {
+        map.timing_points.last().map_or(0.0, |point| point.time)
+    } else {
+        last_object_end_time
+    };
+
+    let (min_beat_len, max_beat_len) = map
+        .timing_points
+        .iter()
+        .map(|point| point.beat_len)
+        .fold(None, |minmax, beat_len| match minmax {
+            Some((min, max)) => Some((f64::min(min, beat_len), f64::max(max, beat_len))),
+            None => Some((beat_len, beat_len)),
+        })
+        .unwrap_or((TimingPoint::DEFAULT_BEAT_LEN, TimingPoint::DEFAULT_BEAT_LEN));
+
+    let dominant_bpm = bpm(last_time, &map.timing_points);
+
+    let mut timeline = Vec::with_capacity(map.timing_points.len());
+
+    for (i, point) in map.timing_points.iter().enumerate() {
+        // * osu-stable forced the first control point to start at 0.
+        let start_time = if i == 0 { 0.0 } else { point.time };
+
+        if i > 0 && start_time > last_time {
+            break;
+        }
+
+        let end_time = map
+            .timing_points
+            .get(i + 1)
+            .map_or(last_time, |next| next.time.min(last_time))
+            .max(start_time);
+
+        timeline.push(BpmSection {
+            start_time: start_time / clock_rate,
+            end_time: end_time / clock_rate,
+            bpm: point.bpm() * clock_rate,
+            time_signature: point.time_signature,
+        });
+    }
+
+    let timing_points = map
+        .timing_points
+        .iter()
+        .map(|point| TimingPoint {
+            time: point.time / clock_rate,
+            beat_len: point.beat_len / clock_rate,
+            time_signature: point.time_signature,
+        })
+        .collect();
+
+    TimingReport {
+        clock_rate,
+        min_bpm: 60_000.0 / max_beat_len * clock_rate,
+        max_bpm: 60_000.0 / min_beat_len * clock_rate,
+        dominant_bpm: dominant_bpm * clock_rate,
+        last_object_end_time: last_object_end_time / clock_rate,
+        timeline,
+        timing_points,
+    }
+}
+
+/// The latest end time of all hitobjects or `0.0` if there are none.
+///
+/// Slider durations are calculated based on the map's mode.
+pub fn last_object_end_time(map: &Beatmap) -> f64 {
+    let mut bufs = CurveBuffers::default();
+
+    map.hit_objects
+        .iter()
+        .map(|h| object_end_time(map, h, &mut bufs))
+        .reduce(f64::max)
+        .unwrap_or(0.0)
+}
+
+pub(super) fn object_end_time(map: &Beatmap, h: &HitObject, bufs: &mut CurveBuffers) -> f64 {
+    match h.kind {
+        HitObjectKind::Slider(ref slider) => {
+            h.start_time + slider_duration(map, h.start_time, slider, bufs)
+        }
+        _ => h.end_time(),
+    }
+}
+
+/// The duration of a slider in the map's mode.
+pub(super) fn slider_duration(
+    map: &Beatmap,
+    start_time: f64,
+    slider: &Slider,
+    bufs: &mut CurveBuffers,
+) -> f64 {
+    if let GameMode::Taiko = map.mode {
+        return f64::from(drum_roll_duration(map, start_time, slider));
+    }
+
+    slider_path_duration(map, start_time, slider, bufs)
+}
+
+fn slider_path_duration(
+    map: &Beatmap,
+    start_time: f64,
+    slider: &Slider,
+    bufs: &mut CurveBuffers,
+) -> f64 {
+    const BASE_SCORING_DIST: f64 = 100.0;
+
+    let beat_len = map
+        .timing_point_at(start_time)
+        .map_or(TimingPoint::DEFAULT_BEAT_LEN, |point| point.beat_len);
+
+    let slider_velocity = map
+        .difficulty_point_at(start_time)
+        .map_or(DifficultyPoint::DEFAULT_SLIDER_VELOCITY, |point| {
+            point.slider_velocity
+        });
+
+    let velocity = BASE_SCORING_DIST * map.slider_multiplier
+        / get_precision_adjusted_beat_len(slider_velocity, beat_len);
+
+    slider.span_count() as f64 * slider.curve(map.mode, bufs).dist() / velocity
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::{
+        beatmap::BeatmapBuilder,
+        hit_object::{PathControlPoint, PathType, Pos},
+    };
+
+    use super::*;
+
+    #[test]
+    fn slider_end_time() {
+        let control_points = vec![
+            PathControlPoint {
+                pos: Pos::new(0.0, 0.0),
+                path_type: Some(PathType::LINEAR),
+            },
+            PathControlPoint {
+                pos: Pos::new(100.0, 0.0),
+                path_type: None,
+            },
+        ];
+
+        let map = BeatmapBuilder::new()
+            .slider_multiplier(1.0)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .circle(Pos::new(0.0, 0.0), 500.0)
+            .slider(Pos::new(0.0, 0.0), 1000.0, control_points, 1, Some(100.0))
+            .build()
+            .unwrap();
+
+        // Two spans of 100px with a velocity of 100px per 500ms
+        assert!((last_object_end_time(&map) - 2000.0).abs() < 1e-10);
+
+        // Taiko uses the duration of the converted drum roll
+        let mut map = map;
+        map.mode = GameMode::Taiko;
+
+        let end_time = 1000.0 + f64::from(drum_roll_duration(&map, 1000.0, slider(&map)));
+        assert!((last_object_end_time(&map) - end_time).abs() < f64::EPSILON);
+    }
+
+    fn slider(map: &Beatmap) -> &Slider {
+        match map.hit_objects[1].kind {
+            HitObjectKind::Slider(ref slider) => slider,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn report() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let report = map.timing_report(1.0);
+
+        // The dominant bpm is based on rounded beat lengths
+        assert!(report.min_bpm - 0.01 <= report.dominant_bpm);
+        assert!(report.dominant_bpm <= report.max_bpm + 0.01);
+        assert!((report.dominant_bpm - map.bpm()).abs() < f64::EPSILON);
+        assert_eq!(report.timing_points, map.timing_points);
+
+        let first = report.timeline.first().unwrap();
+        let last = report.timeline.last().unwrap();
+        assert!(first.start_time.abs() < f64::EPSILON);
+        assert!((last.end_time - report.last_object_end_time).abs() < f64::EPSILON);
+
+        let dt = map.timing_report(1.5);
+
+        assert!((dt.max_bpm - report.max_bpm * 1.5).abs() < 1e-10);
+        assert!((dt.last_object_end_time - report.last_object_end_time / 1.5).abs() < 1e-10);
+        assert_eq!(dt.timeline.len(), report.timeline.len());
+    }
+
+    #[test]
+    fn no_timing_points() {
+        let map = BeatmapBuilder::new()
+            .circle(Pos::new(0.0, 0.0), 500.0)
+            .circle(Pos::new(0.0, 0.0), 1000.0)
+            .build()
+            .unwrap();
+
+        let report = map.timing_report(1.5);
+
+        assert!(report.timeline.is_empty());
+        assert!(report.timing_points.is_empty());
+        assert!((report.min_bpm - TimingPoint::DEFAULT_BPM * 1.5).abs() < 1e-10);
+        assert!((report.max_bpm - report.min_bpm).abs() < f64::EPSILON);
+        assert!(report.dominant_bpm.is_infinite());
+        assert!(map.bpm().is_infinite());
+        assert!((report.last_object_end_time - 1000.0 / 1.5).abs() < 1e-10);
+    }
+}
@@ -1,4 +1,8 @@
-pub use self::{difficulty::DifficultyPoint, effect::EffectPoint, timing::TimingPoint};
+pub use self::{
+    difficulty::DifficultyPoint,
+    effect::EffectPoint,
+    timing::{TimeSignature, TimingPoint},
+};
 
 pub(crate) use self::{
     difficulty::difficulty_point_at, effect::effect_point_at, timing::timing_point_at,
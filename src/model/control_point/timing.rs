@@ -1,8 +1,11 @@
+pub use rosu_map::section::timing_points::TimeSignature;
+
 /// Timing-related info about this control point.
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct TimingPoint {
     pub time: f64,
     pub beat_len: f64,
+    pub time_signature: TimeSignature,
 }
 
 impl TimingPoint {
@@ -11,10 +14,14 @@ impl TimingPoint {
 
     pub const DEFAULT_BPM: f64 = 60_000.0 / Self::DEFAULT_BEAT_LEN;
 
+    pub const DEFAULT_TIME_SIGNATURE: TimeSignature =
+        rosu_map::section::timing_points::TimingPoint::DEFAULT_TIME_SIGNATURE;
+
     pub const fn new(time: f64, beat_len: f64) -> Self {
         Self {
             time,
             beat_len: beat_len.clamp(6.0, 60_000.0),
+            time_signature: Self::DEFAULT_TIME_SIGNATURE,
         }
     }
 
@@ -28,6 +35,7 @@ impl Default for TimingPoint {
         Self {
             time: 0.0,
             beat_len: Self::DEFAULT_BEAT_LEN,
+            time_signature: Self::DEFAULT_TIME_SIGNATURE,
         }
     }
 }
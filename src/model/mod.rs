@@ -12,3 +12,6 @@ pub mod mode;
 
 /// Gamemods related types.
 pub mod mods;
+
+/// Replay related types.
+pub mod replay;
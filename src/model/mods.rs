@@ -33,13 +33,28 @@ pub mod rosu_mods {
 /// let lazer = GameMods::from(GameModsLazer::new());
 /// let intermode = GameMods::from(GameModsIntermode::new());
 /// ```
+///
+/// With the `serde` feature enabled, lazer mods are deserialized such that all
+/// mods belong to the same mode.
 #[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameMods {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "deserialize_lazer"))]
     Lazer(GameModsLazer),
     Intermode(GameModsIntermode),
     Legacy(GameModsLegacy),
 }
 
+#[cfg(feature = "serde")]
+fn deserialize_lazer<'de, D: serde::Deserializer<'de>>(d: D) -> Result<GameModsLazer, D::Error> {
+    use serde::de::DeserializeSeed;
+
+    rosu_mods::serde::GameModsSeed::SameModeForEachMod {
+        deny_unknown_fields: false,
+    }
+    .deserialize(d)
+}
+
 impl Debug for GameMods {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         match self {
@@ -57,7 +72,7 @@ impl GameMods {
     ///
     /// In case of variable clock rates like for `WindUp`, this will return
     /// `1.0`.
-    pub(crate) fn clock_rate(&self) -> f64 {
+    pub fn clock_rate(&self) -> f64 {
         match self {
             Self::Lazer(ref mods) => mods
                 .iter()
@@ -154,6 +169,15 @@ impl GameMods {
         }
     }
 
+    /// Bitflags of the mods' legacy counterparts.
+    pub(crate) fn bits(&self) -> u32 {
+        match self {
+            Self::Lazer(ref mods) => mods.bits(),
+            Self::Intermode(ref mods) => mods.bits(),
+            Self::Legacy(mods) => mods.bits(),
+        }
+    }
+
     pub(crate) fn mania_keys(&self) -> Option<f32> {
         match self {
             Self::Lazer(ref mods) => {
@@ -333,6 +357,7 @@ impl_has_mod! {
     hr: + HardRock ["HardRock"],
     rx: + Relax ["Relax"],
     fl: + Flashlight ["Flashlight"],
+    fi: + FadeIn ["FadeIn"],
     so: + SpunOut ["SpunOut"],
     ap: + Autopilot ["Autopilot"],
     bl: - Blinds ["Blinds"],
@@ -0,0 +1,287 @@
+use rosu_map::section::general::GameMode;
+
+use crate::util::lzma;
+
+use super::{lazer, Replay, ReplayError, ReplayFrame};
+
+/// Decompressed data may not exceed this many bytes.
+const MAX_DECOMPRESSED_LEN: usize = 1 << 26;
+
+pub(super) const TARGET_PRACTICE: u32 = 1 << 23;
+
+const INVALID_FRAME: ReplayError = ReplayError::Invalid("replay frame");
+
+/// Time offset of the frame that contains the seed.
+const SEED_FRAME: &str = "-12345";
+
+pub fn decode(bytes: &[u8]) -> Result<Replay, ReplayError> {
+    let mut reader = Reader(bytes);
+
+    let mode = match reader.u8()? {
+        n @ 0..=3 => GameMode::from(n),
+        _ => return Err(ReplayError::Invalid("mode")),
+    };
+
+    let version = reader.i32()?;
+    let beatmap_md5 = reader.str()?;
+    let player_name = reader.str()?;
+    let replay_md5 = reader.str()?;
+    let n300 = reader.u16()?;
+    let n100 = reader.u16()?;
+    let n50 = reader.u16()?;
+    let n_geki = reader.u16()?;
+    let n_katu = reader.u16()?;
+    let misses = reader.u16()?;
+    let score = reader.i32()?;
+    let max_combo = reader.u16()?;
+    let perfect = reader.u8()? != 0;
+    let mods = reader.i32()? as u32;
+    let life_bar = reader.str()?;
+    let timestamp = reader.i64()?;
+
+    let (frames, seed) = match reader.byte_array()? {
+        Some(compressed) => {
+            let data = lzma::decompress(compressed, MAX_DECOMPRESSED_LEN)?;
+
+            parse_frames(&data)?
+        }
+        None => (Vec::new(), None),
+    };
+
+    let online_score_id = if version >= 20_140_721 {
+        reader.i64()?
+    } else if version >= 20_121_008 {
+        i64::from(reader.i32()?)
+    } else {
+        0
+    };
+
+    let target_practice_accuracy = if mods & TARGET_PRACTICE > 0 {
+        Some(reader.f64()?)
+    } else {
+        None
+    };
+
+    let lazer = if version >= Replay::FIRST_LAZER_VERSION {
+        match reader.byte_array()? {
+            Some(compressed) => {
+                let data = lzma::decompress(compressed, MAX_DECOMPRESSED_LEN)?;
+
+                Some(lazer::parse_score_info(&data, mode)?)
+            }
+            None => None,
+        }
+    } else {
+        None
+    };
+
+    Ok(Replay {
+        mode,
+        version,
+        beatmap_md5,
+        player_name,
+        replay_md5,
+        n300,
+        n100,
+        n50,
+        n_geki,
+        n_katu,
+        misses,
+        score,
+        max_combo,
+        perfect,
+        mods,
+        life_bar,
+        timestamp,
+        frames,
+        seed,
+        online_score_id,
+        target_practice_accuracy,
+        lazer,
+    })
+}
+
+/// Parses frames of the form `w|x|y|z` separated by commas where `w` is the
+/// time since the previous frame.
+fn parse_frames(data: &[u8]) -> Result<(Vec<ReplayFrame>, Option<i32>), ReplayError> {
+    let data = std::str::from_utf8(data).map_err(|_| ReplayError::Invalid("replay frames"))?;
+
+    let mut frames = Vec::with_capacity(data.len() / 16);
+    let mut seed = None;
+    let mut time = 0.0;
+
+    for (i, frame) in data.split(',').enumerate() {
+        let mut split = frame.split('|');
+
+        let (Some(w), Some(x), Some(y), Some(z)) =
+            (split.next(), split.next(), split.next(), split.next())
+        else {
+            continue;
+        };
+
+        if w == SEED_FRAME {
+            seed = z.parse().ok();
+
+            continue;
+        }
+
+        let diff: f64 = w.parse().map_err(|_| INVALID_FRAME)?;
+        let x: f32 = x.parse().map_err(|_| INVALID_FRAME)?;
+        let y: f32 = y.parse().map_err(|_| INVALID_FRAME)?;
+        let keys: i32 = z.parse().map_err(|_| INVALID_FRAME)?;
+
+        time += diff;
+
+        // * at the start of the replay, stable places two replay frames, at
+        // * time 0 and SkipBoundary - 1, respectively. both frames use a
+        // * position of (256, -500). ignore these frames as they serve no
+        // * real purpose (and can even mislead ruleset-specific handlers - see
+        // * mania)
+        #[allow(clippy::float_cmp)]
+        if i < 2 && x == 256.0 && y == -500.0 {
+            continue;
+        }
+
+        // * Todo: At some point we probably want to rewind and play back the
+        // * negative-time frames but for now we'll achieve equal playback to
+        // * stable by skipping negative frames
+        if diff < 0.0 {
+            continue;
+        }
+
+        frames.push(ReplayFrame {
+            time,
+            x,
+            y,
+            keys: keys as u32,
+        });
+    }
+
+    Ok((frames, seed))
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    const fn take(&mut self, len: usize) -> Result<&'a [u8], ReplayError> {
+        if len > self.0.len() {
+            return Err(ReplayError::Eof);
+        }
+
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+
+        Ok(bytes)
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], ReplayError> {
+        let (bytes, rest) = self.0.split_first_chunk().ok_or(ReplayError::Eof)?;
+        self.0 = rest;
+
+        Ok(*bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, ReplayError> {
+        self.bytes().map(|[n]| n)
+    }
+
+    fn u16(&mut self) -> Result<u16, ReplayError> {
+        self.bytes().map(u16::from_le_bytes)
+    }
+
+    fn i32(&mut self) -> Result<i32, ReplayError> {
+        self.bytes().map(i32::from_le_bytes)
+    }
+
+    fn i64(&mut self) -> Result<i64, ReplayError> {
+        self.bytes().map(i64::from_le_bytes)
+    }
+
+    fn f64(&mut self) -> Result<f64, ReplayError> {
+        self.bytes().map(f64::from_le_bytes)
+    }
+
+    /// A length-prefixed byte array or `None` if the length is not positive.
+    fn byte_array(&mut self) -> Result<Option<&'a [u8]>, ReplayError> {
+        match usize::try_from(self.i32()?) {
+            Ok(len @ 1..) => self.take(len).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// A string that is either absent or prefixed with its ULEB128 length.
+    fn str(&mut self) -> Result<String, ReplayError> {
+        match self.u8()? {
+            0x00 => return Ok(String::new()),
+            0x0B => {}
+            _ => return Err(ReplayError::Invalid("string")),
+        }
+
+        let len = self.uleb128()?;
+        let bytes = self.take(len)?;
+
+        String::from_utf8(bytes.to_vec()).map_err(|_| ReplayError::Invalid("string"))
+    }
+
+    fn uleb128(&mut self) -> Result<usize, ReplayError> {
+        let mut value = 0_usize;
+
+        for shift in (0..usize::BITS).step_by(7) {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7F) << shift;
+
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+
+        Err(ReplayError::Invalid("string length"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames() {
+        let data = b"0|256|-500|0,-1|256|-500|0,2809|34.5|30.75|1,16|49.5|78.75|0,\
+            -3|100|100|0,4|1|2|5,-12345|0|0|7364,";
+
+        let (frames, seed) = parse_frames(data).unwrap();
+
+        assert_eq!(seed, Some(7364));
+        assert_eq!(
+            frames,
+            [
+                ReplayFrame {
+                    time: 2808.0,
+                    x: 34.5,
+                    y: 30.75,
+                    keys: 1
+                },
+                ReplayFrame {
+                    time: 2824.0,
+                    x: 49.5,
+                    y: 78.75,
+                    keys: 0
+                },
+                ReplayFrame {
+                    time: 2825.0,
+                    x: 1.0,
+                    y: 2.0,
+                    keys: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn strings() {
+        let mut reader = Reader(&[0x00, 0x0B, 0x03, b'o', b's', b'u', 0x0B, 0x05, b'a']);
+
+        assert_eq!(reader.str().unwrap(), "");
+        assert_eq!(reader.str().unwrap(), "osu");
+        assert!(matches!(reader.str(), Err(ReplayError::Eof)));
+    }
+}
@@ -0,0 +1,130 @@
+use std::{
+    fmt::Write as _,
+    io::{self, Write},
+};
+
+use crate::util::lzma;
+
+use super::{decode::TARGET_PRACTICE, lazer, Replay, ReplayFrame};
+
+pub fn encode<W: Write>(replay: &Replay, mut writer: W) -> Result<(), io::Error> {
+    let mut bytes = Vec::with_capacity(256);
+
+    bytes.push(replay.mode as u8);
+    bytes.extend_from_slice(&replay.version.to_le_bytes());
+    write_str(&mut bytes, &replay.beatmap_md5);
+    write_str(&mut bytes, &replay.player_name);
+    write_str(&mut bytes, &replay.replay_md5);
+
+    let counts = [
+        replay.n300,
+        replay.n100,
+        replay.n50,
+        replay.n_geki,
+        replay.n_katu,
+        replay.misses,
+    ];
+
+    for count in counts {
+        bytes.extend_from_slice(&count.to_le_bytes());
+    }
+
+    bytes.extend_from_slice(&replay.score.to_le_bytes());
+    bytes.extend_from_slice(&replay.max_combo.to_le_bytes());
+    bytes.push(u8::from(replay.perfect));
+    bytes.extend_from_slice(&replay.mods.to_le_bytes());
+    write_str(&mut bytes, &replay.life_bar);
+    bytes.extend_from_slice(&replay.timestamp.to_le_bytes());
+
+    let frames = lzma::compress(frames_to_string(&replay.frames, replay.seed).as_bytes());
+    write_byte_array(&mut bytes, &frames)?;
+
+    if replay.version >= 20_140_721 {
+        bytes.extend_from_slice(&replay.online_score_id.to_le_bytes());
+    } else if replay.version >= 20_121_008 {
+        bytes.extend_from_slice(&(replay.online_score_id as i32).to_le_bytes());
+    }
+
+    if replay.mods & TARGET_PRACTICE > 0 {
+        let acc = replay.target_practice_accuracy.unwrap_or(0.0);
+        bytes.extend_from_slice(&acc.to_le_bytes());
+    }
+
+    if replay.version >= Replay::FIRST_LAZER_VERSION {
+        let score_info = replay
+            .lazer
+            .as_ref()
+            .map(|info| lzma::compress(lazer::score_info_to_json(info).as_bytes()))
+            .unwrap_or_default();
+
+        write_byte_array(&mut bytes, &score_info)?;
+    }
+
+    writer.write_all(&bytes)?;
+
+    writer.flush()
+}
+
+/// Joins frames as `w|x|y|z` where `w` is the time since the previous frame.
+///
+/// Times are rounded to whole milliseconds like osu!stable does.
+fn frames_to_string(frames: &[ReplayFrame], seed: Option<i32>) -> String {
+    let mut content = String::with_capacity(frames.len() * 16);
+    let mut prev_time = 0.0;
+
+    for frame in frames {
+        let time = frame.time.round();
+
+        let _ = write!(
+            content,
+            "{}|{}|{}|{},",
+            time - prev_time,
+            frame.x,
+            frame.y,
+            frame.keys
+        );
+
+        prev_time = time;
+    }
+
+    if let Some(seed) = seed {
+        let _ = write!(content, "-12345|0|0|{seed},");
+    }
+
+    content
+}
+
+fn write_str(bytes: &mut Vec<u8>, s: &str) {
+    if s.is_empty() {
+        bytes.push(0x00);
+
+        return;
+    }
+
+    bytes.push(0x0B);
+
+    let mut len = s.len();
+
+    loop {
+        let byte = (len & 0x7F) as u8;
+        len >>= 7;
+
+        if len == 0 {
+            bytes.push(byte);
+
+            break;
+        }
+
+        bytes.push(byte | 0x80);
+    }
+
+    bytes.extend_from_slice(s.as_bytes());
+}
+
+fn write_byte_array(bytes: &mut Vec<u8>, array: &[u8]) -> Result<(), io::Error> {
+    let len = i32::try_from(array.len()).map_err(io::Error::other)?;
+    bytes.extend_from_slice(&len.to_le_bytes());
+    bytes.extend_from_slice(array);
+
+    Ok(())
+}
@@ -0,0 +1,368 @@
+use rosu_map::section::general::GameMode;
+use rosu_mods::{
+    generated_mods::MirrorOsu, GameMod, GameMode as ModsMode, GameMods as GameModsLazer,
+};
+
+use crate::util::json::{self, JsonValue};
+
+use super::{LazerScoreInfo, LazerStatistics, ReplayError};
+
+const INVALID: ReplayError = ReplayError::Invalid("lazer score info");
+
+/// Parses the JSON score info that osu!lazer appends to replays.
+pub fn parse_score_info(data: &[u8], mode: GameMode) -> Result<LazerScoreInfo, ReplayError> {
+    let data = std::str::from_utf8(data).map_err(|_| INVALID)?;
+    let value = json::parse(data).map_err(|_| INVALID)?;
+
+    let mut info = LazerScoreInfo {
+        online_id: value.get("online_id").and_then(as_i64),
+        client_version: value
+            .get("client_version")
+            .and_then(JsonValue::as_str)
+            .map(str::to_owned),
+        rank: value
+            .get("rank")
+            .and_then(JsonValue::as_str)
+            .map(str::to_owned),
+        user_id: value.get("user_id").and_then(as_i64),
+        total_score_without_mods: value.get("total_score_without_mods").and_then(as_i64),
+        ..LazerScoreInfo::default()
+    };
+
+    if let Some(mods) = value.get("mods") {
+        info.mods = parse_mods(mods, mode)?;
+    }
+
+    if let Some(statistics) = value.get("statistics") {
+        info.statistics = parse_statistics(statistics)?;
+    }
+
+    if let Some(statistics) = value.get("maximum_statistics") {
+        info.maximum_statistics = parse_statistics(statistics)?;
+    }
+
+    Ok(info)
+}
+
+/// Writes the score info as JSON so that [`parse_score_info`] restores it.
+///
+/// Only the mod settings that are parsed are written.
+pub fn score_info_to_json(info: &LazerScoreInfo) -> String {
+    let mut entries = Vec::new();
+
+    let mut push_i64 = |key: &str, value: Option<i64>| {
+        if let Some(value) = value {
+            entries.push((key.to_owned(), JsonValue::Number(value as f64)));
+        }
+    };
+
+    push_i64("online_id", info.online_id);
+    push_i64("user_id", info.user_id);
+    push_i64("total_score_without_mods", info.total_score_without_mods);
+
+    let strings = [
+        ("client_version", &info.client_version),
+        ("rank", &info.rank),
+    ];
+
+    for (key, value) in strings {
+        if let Some(value) = value {
+            entries.push((key.to_owned(), JsonValue::String(value.clone())));
+        }
+    }
+
+    entries.push(("mods".to_owned(), mods_to_json(&info.mods)));
+    entries.push((
+        "statistics".to_owned(),
+        statistics_to_json(&info.statistics),
+    ));
+    entries.push((
+        "maximum_statistics".to_owned(),
+        statistics_to_json(&info.maximum_statistics),
+    ));
+
+    JsonValue::Object(entries).to_string()
+}
+
+fn as_i64(value: &JsonValue) -> Option<i64> {
+    value.as_f64().map(|n| n as i64)
+}
+
+fn parse_statistics(value: &JsonValue) -> Result<LazerStatistics, ReplayError> {
+    let mut statistics = LazerStatistics::default();
+
+    for (key, value) in value.as_object().ok_or(INVALID)? {
+        let count = value.as_f64().ok_or(INVALID)? as u32;
+
+        if let Some(field) = statistic(&mut statistics, key) {
+            *field = count;
+        }
+    }
+
+    Ok(statistics)
+}
+
+/// Zero counts are omitted.
+fn statistics_to_json(statistics: &LazerStatistics) -> JsonValue {
+    let mut statistics = *statistics;
+
+    let entries = STATISTICS
+        .iter()
+        .filter_map(|&key| {
+            let count = *statistic(&mut statistics, key)?;
+
+            (count > 0).then(|| (key.to_owned(), JsonValue::Number(f64::from(count))))
+        })
+        .collect();
+
+    JsonValue::Object(entries)
+}
+
+const STATISTICS: [&str; 17] = [
+    "miss",
+    "meh",
+    "ok",
+    "good",
+    "great",
+    "perfect",
+    "small_tick_miss",
+    "small_tick_hit",
+    "large_tick_miss",
+    "large_tick_hit",
+    "slider_tail_hit",
+    "small_bonus",
+    "large_bonus",
+    "ignore_miss",
+    "ignore_hit",
+    "combo_break",
+    "legacy_combo_increase",
+];
+
+fn statistic<'s>(statistics: &'s mut LazerStatistics, key: &str) -> Option<&'s mut u32> {
+    let field = match key {
+        "miss" => &mut statistics.miss,
+        "meh" => &mut statistics.meh,
+        "ok" => &mut statistics.ok,
+        "good" => &mut statistics.good,
+        "great" => &mut statistics.great,
+        "perfect" => &mut statistics.perfect,
+        "small_tick_miss" => &mut statistics.small_tick_miss,
+        "small_tick_hit" => &mut statistics.small_tick_hit,
+        "large_tick_miss" => &mut statistics.large_tick_miss,
+        "large_tick_hit" => &mut statistics.large_tick_hit,
+        "slider_tail_hit" => &mut statistics.slider_tail_hit,
+        "small_bonus" => &mut statistics.small_bonus,
+        "large_bonus" => &mut statistics.large_bonus,
+        "ignore_miss" => &mut statistics.ignore_miss,
+        "ignore_hit" => &mut statistics.ignore_hit,
+        "combo_break" => &mut statistics.combo_break,
+        "legacy_combo_increase" => &mut statistics.legacy_combo_increase,
+        _ => return None,
+    };
+
+    Some(field)
+}
+
+fn parse_mods(value: &JsonValue, mode: GameMode) -> Result<GameModsLazer, ReplayError> {
+    let mode = ModsMode::from(mode as u8);
+    let mut mods = GameModsLazer::new();
+
+    for value in value.as_array().ok_or(INVALID)? {
+        let acronym = value
+            .get("acronym")
+            .and_then(JsonValue::as_str)
+            .ok_or(INVALID)?;
+
+        let mut gamemod = GameMod::new(acronym, mode);
+
+        if let Some(settings) = value.get("settings") {
+            for (key, value) in settings.as_object().ok_or(INVALID)? {
+                apply_setting(&mut gamemod, key, value);
+            }
+        }
+
+        mods.insert(gamemod);
+    }
+
+    Ok(mods)
+}
+
+fn mods_to_json(mods: &GameModsLazer) -> JsonValue {
+    let values = mods
+        .iter()
+        .map(|gamemod| {
+            let mut entries = vec![(
+                "acronym".to_owned(),
+                JsonValue::String(gamemod.acronym().as_str().to_owned()),
+            )];
+
+            let settings = settings_to_json(gamemod);
+
+            if !settings.is_empty() {
+                entries.push(("settings".to_owned(), JsonValue::Object(settings)));
+            }
+
+            JsonValue::Object(entries)
+        })
+        .collect();
+
+    JsonValue::Array(values)
+}
+
+/// All keys of settings that [`apply_setting`] handles.
+const SETTINGS: [&str; 11] = [
+    "speed_change",
+    "circle_size",
+    "approach_rate",
+    "drain_rate",
+    "overall_difficulty",
+    "scroll_speed",
+    "seed",
+    "extended_limits",
+    "hard_rock_offsets",
+    "no_slider_head_accuracy",
+    "reflection",
+];
+
+fn settings_to_json(gamemod: &GameMod) -> Vec<(String, JsonValue)> {
+    let mut gamemod = gamemod.clone();
+
+    SETTINGS
+        .iter()
+        .filter_map(|&key| {
+            let value = if let Some(setting) = f64_setting(&mut gamemod, key) {
+                JsonValue::Number((*setting)?)
+            } else if let Some(setting) = bool_setting(&mut gamemod, key) {
+                JsonValue::Bool((*setting)?)
+            } else if let (GameMod::MirrorOsu(MirrorOsu { reflection }), "reflection") =
+                (&gamemod, key)
+            {
+                JsonValue::String(reflection.clone()?)
+            } else {
+                return None;
+            };
+
+            Some((key.to_owned(), value))
+        })
+        .collect()
+}
+
+/// Applies the mod settings that are relevant for calculations; all other
+/// settings are ignored.
+fn apply_setting(gamemod: &mut GameMod, key: &str, value: &JsonValue) {
+    if let Some(setting) = f64_setting(gamemod, key) {
+        *setting = value.as_f64();
+    } else if let Some(setting) = bool_setting(gamemod, key) {
+        *setting = value.as_bool();
+    } else if let (GameMod::MirrorOsu(MirrorOsu { reflection }), "reflection") = (gamemod, key) {
+        *reflection = match value {
+            JsonValue::String(s) => Some(s.clone()),
+            JsonValue::Number(n) => Some(n.to_string()),
+            _ => None,
+        };
+    }
+}
+
+fn f64_setting<'m>(gamemod: &'m mut GameMod, key: &str) -> Option<&'m mut Option<f64>> {
+    let setting = match (gamemod, key) {
+        (GameMod::DoubleTimeOsu(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DoubleTimeTaiko(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DoubleTimeCatch(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DoubleTimeMania(m), "speed_change") => &mut m.speed_change,
+        (GameMod::NightcoreOsu(m), "speed_change") => &mut m.speed_change,
+        (GameMod::NightcoreTaiko(m), "speed_change") => &mut m.speed_change,
+        (GameMod::NightcoreCatch(m), "speed_change") => &mut m.speed_change,
+        (GameMod::NightcoreMania(m), "speed_change") => &mut m.speed_change,
+        (GameMod::HalfTimeOsu(m), "speed_change") => &mut m.speed_change,
+        (GameMod::HalfTimeTaiko(m), "speed_change") => &mut m.speed_change,
+        (GameMod::HalfTimeCatch(m), "speed_change") => &mut m.speed_change,
+        (GameMod::HalfTimeMania(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DaycoreOsu(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DaycoreTaiko(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DaycoreCatch(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DaycoreMania(m), "speed_change") => &mut m.speed_change,
+        (GameMod::DifficultyAdjustOsu(m), "circle_size") => &mut m.circle_size,
+        (GameMod::DifficultyAdjustOsu(m), "approach_rate") => &mut m.approach_rate,
+        (GameMod::DifficultyAdjustOsu(m), "drain_rate") => &mut m.drain_rate,
+        (GameMod::DifficultyAdjustOsu(m), "overall_difficulty") => &mut m.overall_difficulty,
+        (GameMod::DifficultyAdjustTaiko(m), "scroll_speed") => &mut m.scroll_speed,
+        (GameMod::DifficultyAdjustTaiko(m), "drain_rate") => &mut m.drain_rate,
+        (GameMod::DifficultyAdjustTaiko(m), "overall_difficulty") => &mut m.overall_difficulty,
+        (GameMod::DifficultyAdjustCatch(m), "circle_size") => &mut m.circle_size,
+        (GameMod::DifficultyAdjustCatch(m), "approach_rate") => &mut m.approach_rate,
+        (GameMod::DifficultyAdjustCatch(m), "drain_rate") => &mut m.drain_rate,
+        (GameMod::DifficultyAdjustCatch(m), "overall_difficulty") => &mut m.overall_difficulty,
+        (GameMod::DifficultyAdjustMania(m), "drain_rate") => &mut m.drain_rate,
+        (GameMod::DifficultyAdjustMania(m), "overall_difficulty") => &mut m.overall_difficulty,
+        (GameMod::RandomTaiko(m), "seed") => &mut m.seed,
+        (GameMod::RandomMania(m), "seed") => &mut m.seed,
+        _ => return None,
+    };
+
+    Some(setting)
+}
+
+fn bool_setting<'m>(gamemod: &'m mut GameMod, key: &str) -> Option<&'m mut Option<bool>> {
+    let setting = match (gamemod, key) {
+        (GameMod::DifficultyAdjustOsu(m), "extended_limits") => &mut m.extended_limits,
+        (GameMod::DifficultyAdjustTaiko(m), "extended_limits") => &mut m.extended_limits,
+        (GameMod::DifficultyAdjustCatch(m), "extended_limits") => &mut m.extended_limits,
+        (GameMod::DifficultyAdjustCatch(m), "hard_rock_offsets") => &mut m.hard_rock_offsets,
+        (GameMod::DifficultyAdjustMania(m), "extended_limits") => &mut m.extended_limits,
+        (GameMod::ClassicOsu(m), "no_slider_head_accuracy") => &mut m.no_slider_head_accuracy,
+        _ => return None,
+    };
+
+    Some(setting)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mods() {
+        let json = br#"{
+            "mods": [
+                { "acronym": "HD" },
+                { "acronym": "DT", "settings": { "speed_change": 1.3, "adjust_pitch": true } },
+                { "acronym": "DA", "settings": { "approach_rate": 11, "extended_limits": true } },
+                { "acronym": "MR", "settings": { "reflection": "1" } }
+            ],
+            "statistics": { "great": 10, "slider_tail_hit": 3, "unknown": 5 }
+        }"#;
+
+        let info = parse_score_info(json, GameMode::Osu).unwrap();
+        let has = |f: fn(&GameMod) -> bool| info.mods.iter().any(f);
+
+        assert_eq!(info.mods.len(), 4);
+        assert!(has(|m| matches!(m, GameMod::HiddenOsu(_))));
+        assert!(has(
+            |m| matches!(m, GameMod::DoubleTimeOsu(m) if m.speed_change == Some(1.3))
+        ));
+        assert!(has(|m| matches!(
+            m,
+            GameMod::DifficultyAdjustOsu(m)
+                if m.approach_rate == Some(11.0) && m.extended_limits == Some(true)
+        )));
+        assert!(has(
+            |m| matches!(m, GameMod::MirrorOsu(m) if m.reflection.as_deref() == Some("1"))
+        ));
+
+        assert_eq!(info.statistics.great, 10);
+        assert_eq!(info.statistics.slider_tail_hit, 3);
+        assert_eq!(info.maximum_statistics, LazerStatistics::default());
+
+        let json = score_info_to_json(&info);
+        assert_eq!(
+            parse_score_info(json.as_bytes(), GameMode::Osu).unwrap(),
+            info
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse_score_info(b"{", GameMode::Osu).is_err());
+        assert!(parse_score_info(br#"{"mods": [{}]}"#, GameMode::Osu).is_err());
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    error, fmt,
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use rosu_map::section::general::GameMode;
+
+use crate::{
+    any::ScoreState,
+    mania,
+    model::{beatmap::Md5Hash, mode::ConvertError, mods::rosu_mods::GameMods as GameModsLazer},
+    osu::OsuReplayJudge,
+    taiko,
+    util::lzma::LzmaError,
+    Beatmap, Difficulty, GameMods,
+};
+
+pub use self::statistics::{HitErrorBucket, HitErrorStatistics};
+
+mod decode;
+mod encode;
+mod lazer;
+mod statistics;
+
+/// A score that was decoded from an `.osr` replay file.
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{model::replay::Replay, Beatmap, Performance};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let replay = Replay::from_path("/path/to/replay.osr")?;
+/// let map = Beatmap::from_path("/path/to/map.osu")?;
+///
+/// let attrs = Performance::new(&map)
+///     .mods(replay.mods())
+///     .lazer(replay.is_lazer())
+///     .state(replay.score_state())
+///     .calculate();
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Replay {
+    pub mode: GameMode,
+    /// The version of the game that created the replay, e.g. `20240101`.
+    ///
+    /// Versions of osu!lazer start at `30000000`.
+    pub version: i32,
+    /// The MD5 hash of the `.osu` file as hex string.
+    pub beatmap_md5: String,
+    pub player_name: String,
+    /// The MD5 hash of the replay as hex string.
+    pub replay_md5: String,
+    pub n300: u16,
+    pub n100: u16,
+    pub n50: u16,
+    pub n_geki: u16,
+    pub n_katu: u16,
+    pub misses: u16,
+    /// The total score as displayed in-game.
+    pub score: i32,
+    pub max_combo: u16,
+    /// Whether the score was a full combo.
+    pub perfect: bool,
+    /// Bitflags of legacy mods.
+    ///
+    /// Use [`Replay::mods`] to also consider mod settings of osu!lazer.
+    pub mods: u32,
+    /// Comma separated `time|health` pairs.
+    pub life_bar: String,
+    /// Timestamp in Windows ticks, i.e. 100 nanoseconds since `0001-01-01`.
+    pub timestamp: i64,
+    /// The input frames.
+    ///
+    /// Like in osu!lazer, the two initial frames of osu!stable and frames
+    /// with negative time offsets are skipped.
+    pub frames: Vec<ReplayFrame>,
+    /// The seed that was stored in the frames.
+    pub seed: Option<i32>,
+    pub online_score_id: i64,
+    /// The accuracy of scores with `TargetPractice`.
+    pub target_practice_accuracy: Option<f64>,
+    /// Additional info of scores that were set on osu!lazer.
+    pub lazer: Option<LazerScoreInfo>,
+}
+
+impl Replay {
+    /// The version from which replays contain [`LazerScoreInfo`].
+    pub const FIRST_LAZER_VERSION: i32 = 30_000_001;
+
+    /// Decode a [`Replay`] by providing a path to an `.osr` file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ReplayError> {
+        fs::read(path)
+            .map_err(ReplayError::Io)
+            .and_then(|bytes| Self::from_bytes(&bytes))
+    }
+
+    /// Decode a [`Replay`] by providing the content of an `.osr` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ReplayError> {
+        decode::decode(bytes)
+    }
+
+    /// Encode a [`Replay`] into the content of an `.osr` file.
+    ///
+    /// Frame times are rounded to whole milliseconds. [`LazerScoreInfo`] is
+    /// written as JSON but only with the mod settings that are decoded.
+    ///
+    /// In case of writing directly to a file, it is recommended to pass the
+    /// file wrapped in a [`BufWriter`] or just use [`encode_to_path`].
+    ///
+    /// [`encode_to_path`]: Replay::encode_to_path
+    pub fn encode<W: Write>(&self, writer: W) -> Result<(), io::Error> {
+        encode::encode(self, writer)
+    }
+
+    /// Encode a [`Replay`] into the content of an `.osr` file and store it at
+    /// the given path.
+    pub fn encode_to_path<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
+        let file = File::create(path)?;
+
+        self.encode(BufWriter::new(file))
+    }
+
+    /// Parses [`Replay::beatmap_md5`] so it can be compared with
+    /// [`Beatmap::checksum`].
+    ///
+    /// [`Beatmap::checksum`]: crate::Beatmap::checksum
+    pub fn beatmap_checksum(&self) -> Option<Md5Hash> {
+        let hex = self.beatmap_md5.as_bytes();
+
+        if hex.len() != 32 {
+            return None;
+        }
+
+        let mut bytes = [0; 16];
+
+        for (byte, pair) in bytes.iter_mut().zip(hex.chunks_exact(2)) {
+            let pair = std::str::from_utf8(pair).ok()?;
+            *byte = u8::from_str_radix(pair, 16).ok()?;
+        }
+
+        Some(Md5Hash(bytes))
+    }
+
+    /// Whether the score was set on osu!lazer.
+    pub const fn is_lazer(&self) -> bool {
+        self.lazer.is_some()
+    }
+
+    /// The score's mods.
+    ///
+    /// For osu!lazer scores these include mod settings such as custom clock
+    /// rates.
+    pub fn mods(&self) -> GameMods {
+        match self.lazer {
+            Some(ref lazer) => GameMods::Lazer(lazer.mods.clone()),
+            None => GameMods::from(self.mods),
+        }
+    }
+
+    /// The [`ScoreState`] at the end of the replay.
+    pub fn score_state(&self) -> ScoreState {
+        let mut state = ScoreState {
+            max_combo: u32::from(self.max_combo),
+            n_geki: u32::from(self.n_geki),
+            n_katu: u32::from(self.n_katu),
+            n300: u32::from(self.n300),
+            n100: u32::from(self.n100),
+            n50: u32::from(self.n50),
+            misses: u32::from(self.misses),
+            ..ScoreState::new()
+        };
+
+        if let (GameMode::Osu, Some(lazer)) = (self.mode, &self.lazer) {
+            state.osu_large_tick_hits = lazer.statistics.large_tick_hit;
+            state.osu_small_tick_hits = lazer.statistics.small_tick_hit;
+            state.slider_end_hits = lazer.statistics.slider_tail_hit;
+        }
+
+        state
+    }
+
+    /// Match the frames against the hitobjects of the given map and
+    /// calculate [`HitErrorStatistics`].
+    ///
+    /// Returns `None` for osu!catch replays or if no object was hit.
+    pub fn hit_error_statistics(
+        &self,
+        map: &Beatmap,
+    ) -> Result<Option<HitErrorStatistics>, ConvertError> {
+        let difficulty = Difficulty::new().mods(self.mods()).lazer(self.is_lazer());
+
+        let hit_errors = match self.mode {
+            GameMode::Osu => {
+                return OsuReplayJudge::new(map)
+                    .mods(self.mods())
+                    .lazer(self.is_lazer())
+                    .judge(&self.frames)
+                    .map(|judged| judged.hit_error_statistics());
+            }
+            GameMode::Taiko => taiko::hit_errors(&difficulty, map, &self.frames)?,
+            GameMode::Catch => return Ok(None),
+            GameMode::Mania => mania::hit_errors(&difficulty, map, &self.frames)?,
+        };
+
+        Ok(HitErrorStatistics::new(hit_errors))
+    }
+}
+
+/// A single input frame of a [`Replay`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ReplayFrame {
+    /// The time in milliseconds.
+    pub time: f64,
+    pub x: f32,
+    pub y: f32,
+    /// Bitflags of the pressed keys.
+    ///
+    /// For osu!mania, `x` contains the pressed columns instead.
+    pub keys: u32,
+}
+
+impl ReplayFrame {
+    pub const M1: u32 = 1 << 0;
+    pub const M2: u32 = 1 << 1;
+    pub const K1: u32 = 1 << 2;
+    pub const K2: u32 = 1 << 3;
+    pub const SMOKE: u32 = 1 << 4;
+}
+
+/// Score info that osu!lazer stores in addition to the legacy replay format.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LazerScoreInfo {
+    pub online_id: Option<i64>,
+    /// The mods including their settings.
+    pub mods: GameModsLazer,
+    /// The achieved hit results.
+    pub statistics: LazerStatistics,
+    /// The hit results of a perfect play.
+    pub maximum_statistics: LazerStatistics,
+    pub client_version: Option<String>,
+    pub rank: Option<String>,
+    pub user_id: Option<i64>,
+    pub total_score_without_mods: Option<i64>,
+}
+
+/// Amounts of each hit result of an osu!lazer score.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LazerStatistics {
+    pub miss: u32,
+    pub meh: u32,
+    pub ok: u32,
+    pub good: u32,
+    pub great: u32,
+    pub perfect: u32,
+    pub small_tick_miss: u32,
+    pub small_tick_hit: u32,
+    pub large_tick_miss: u32,
+    pub large_tick_hit: u32,
+    pub slider_tail_hit: u32,
+    pub small_bonus: u32,
+    pub large_bonus: u32,
+    pub ignore_miss: u32,
+    pub ignore_hit: u32,
+    pub combo_break: u32,
+    pub legacy_combo_increase: u32,
+}
+
+/// All the ways that decoding a [`Replay`] can fail.
+#[derive(Debug)]
+pub enum ReplayError {
+    /// The content ended unexpectedly.
+    Eof,
+    /// A value had an invalid representation.
+    Invalid(&'static str),
+    /// Failed to read the file.
+    Io(io::Error),
+    /// Compressed data could not be decompressed.
+    Lzma(LzmaError),
+}
+
+impl error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Self::Eof | Self::Invalid(_) => None,
+            Self::Io(source) => Some(source),
+            Self::Lzma(source) => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof => f.write_str("unexpected end of content"),
+            Self::Invalid(what) => write!(f, "invalid {what}"),
+            Self::Io(_) => f.write_str("failed to read file"),
+            Self::Lzma(_) => f.write_str("failed to decompress data"),
+        }
+    }
+}
+
+impl From<LzmaError> for ReplayError {
+    fn from(err: LzmaError) -> Self {
+        Self::Lzma(err)
+    }
+}
@@ -0,0 +1,167 @@
+/// Statistics of measured hit errors.
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{model::replay::Replay, osu::OsuPerformance, Beatmap};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let replay = Replay::from_path("/path/to/replay.osr")?;
+/// let map = Beatmap::from_path("/path/to/map.osu")?;
+///
+/// let mut calc = OsuPerformance::new(&map)
+///     .mods(replay.mods())
+///     .lazer(replay.is_lazer())
+///     .state(replay.score_state().into());
+///
+/// if let Some(stats) = replay.hit_error_statistics(&map)? {
+///     calc = calc.speed_deviation(stats.deviation());
+/// }
+///
+/// let attrs = calc.calculate();
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct HitErrorStatistics {
+    /// Ten times the standard deviation of the hit errors.
+    pub unstable_rate: f64,
+    /// The mean hit error; negative if hits were early on average.
+    pub mean: f64,
+    /// The mean of all early hit errors.
+    pub early_mean: Option<f64>,
+    /// The mean of all late hit errors.
+    pub late_mean: Option<f64>,
+    /// All hit errors in milliseconds, adjusted to the clock rate; negative
+    /// if the hit was early.
+    pub hit_errors: Vec<f64>,
+}
+
+impl HitErrorStatistics {
+    /// The maximum amount of buckets in a [`HitErrorStatistics::histogram`].
+    pub const MAX_HISTOGRAM_BUCKETS: usize = 10_000;
+
+    /// Calculate statistics for the given hit errors.
+    ///
+    /// Returns `None` if `hit_errors` is empty.
+    pub fn new(hit_errors: Vec<f64>) -> Option<Self> {
+        if hit_errors.is_empty() {
+            return None;
+        }
+
+        let len = hit_errors.len() as f64;
+        let mean = hit_errors.iter().sum::<f64>() / len;
+
+        let variance = hit_errors
+            .iter()
+            .map(|hit_error| (hit_error - mean) * (hit_error - mean))
+            .sum::<f64>()
+            / len;
+
+        let mean_of = |filter: fn(f64) -> bool| {
+            let (sum, count) = hit_errors
+                .iter()
+                .filter(|hit_error| filter(**hit_error))
+                .fold((0.0, 0), |(sum, count), hit_error| {
+                    (sum + hit_error, count + 1)
+                });
+
+            (count > 0).then(|| sum / f64::from(count))
+        };
+
+        Some(Self {
+            unstable_rate: variance.sqrt() * 10.0,
+            mean,
+            early_mean: mean_of(|hit_error| hit_error < 0.0),
+            late_mean: mean_of(|hit_error| hit_error >= 0.0),
+            hit_errors,
+        })
+    }
+
+    /// The standard deviation of the hit errors.
+    pub fn deviation(&self) -> f64 {
+        self.unstable_rate / 10.0
+    }
+
+    /// Count hit errors in buckets of the given width.
+    ///
+    /// Buckets are aligned to multiples of `bucket_width` and range from the
+    /// earliest to the latest hit error.
+    ///
+    /// Returns an empty `Vec` if `bucket_width` is not a finite positive
+    /// number or if more than [`MAX_HISTOGRAM_BUCKETS`] buckets would be
+    /// required.
+    ///
+    /// [`MAX_HISTOGRAM_BUCKETS`]: Self::MAX_HISTOGRAM_BUCKETS
+    pub fn histogram(&self, bucket_width: f64) -> Vec<HitErrorBucket> {
+        if !(bucket_width.is_finite() && bucket_width > 0.0) {
+            return Vec::new();
+        }
+
+        let bucket_idx = |hit_error: f64| (hit_error / bucket_width).floor() as i64;
+
+        let (min, max) =
+            self.hit_errors
+                .iter()
+                .fold((i64::MAX, i64::MIN), |(min, max), hit_error| {
+                    let idx = bucket_idx(*hit_error);
+
+                    (min.min(idx), max.max(idx))
+                });
+
+        if max.abs_diff(min) >= Self::MAX_HISTOGRAM_BUCKETS as u64 {
+            return Vec::new();
+        }
+
+        let mut buckets: Vec<_> = (min..=max)
+            .map(|idx| HitErrorBucket {
+                start: idx as f64 * bucket_width,
+                count: 0,
+            })
+            .collect();
+
+        for hit_error in self.hit_errors.iter() {
+            buckets[(bucket_idx(*hit_error) - min) as usize].count += 1;
+        }
+
+        buckets
+    }
+}
+
+/// A bucket of [`HitErrorStatistics::histogram`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HitErrorBucket {
+    /// The inclusive start of the bucket in milliseconds.
+    pub start: f64,
+    /// The amount of hit errors within the bucket.
+    pub count: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn statistics() {
+        let stats = HitErrorStatistics::new(vec![-10.0, -2.0, 4.0, 8.0]).unwrap();
+
+        assert!((stats.mean - 0.0).abs() < f64::EPSILON);
+        assert!((stats.unstable_rate - 10.0 * 46.0_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(stats.early_mean, Some(-6.0));
+        assert_eq!(stats.late_mean, Some(6.0));
+
+        let histogram = stats.histogram(5.0);
+        let counts: Vec<_> = histogram.iter().map(|bucket| bucket.count).collect();
+
+        assert!((histogram[0].start + 10.0).abs() < f64::EPSILON);
+        assert_eq!(counts, [1, 1, 1, 1]);
+
+        assert!(stats.histogram(0.0).is_empty());
+        assert!(stats.histogram(-5.0).is_empty());
+        assert!(stats.histogram(f64::NAN).is_empty());
+        assert!(stats.histogram(f64::INFINITY).is_empty());
+        assert!(stats.histogram(1e-3).is_empty());
+        assert_eq!(stats.histogram(0.01).len(), 1801);
+
+        assert!(HitErrorStatistics::new(Vec::new()).is_none());
+    }
+}
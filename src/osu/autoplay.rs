@@ -0,0 +1,477 @@
+use std::iter;
+
+use rosu_map::{
+    section::{
+        general::GameMode,
+        hit_objects::{BorrowedCurve, CurveBuffers},
+    },
+    util::Pos,
+};
+
+use crate::{
+    any::Difficulty,
+    model::{
+        hit_object::HitObjectKind,
+        mode::ConvertError,
+        mods::{GameMods, Reflection},
+        replay::{Replay, ReplayFrame},
+    },
+    Beatmap,
+};
+
+use super::{
+    convert::convert_objects,
+    difficulty::OsuDifficultySetup,
+    judge::{OsuHitResult, OsuReplayJudge},
+    object::{OsuObject, OsuObjectKind, OsuSlider},
+};
+
+/// Time between two frames while the cursor moves, follows a slider, or
+/// spins a spinner.
+const FRAME_TIME: f64 = 1000.0 / 60.0;
+
+/// Duration for which a key is held after an object ended.
+const KEY_UP_DELAY: f64 = 50.0;
+
+/// Duration of the cursor's movement towards the first object.
+const LEAD_IN: f64 = 1000.0;
+
+const SPINNER_CENTER: Pos = Pos::new(256.0, 192.0);
+
+const SPIN_RADIUS: f32 = 50.0;
+
+/// Angular velocity while spinning in radians per millisecond.
+const SPIN_SPEED: f64 = 0.04;
+
+/// The osu!stable version of replays created by [`OsuAutoplay::replay`].
+const REPLAY_VERSION: i32 = 20_240_101;
+
+/// Generates autoplay frames for an osu!standard [`Beatmap`].
+///
+/// The cursor hits every circle and slider head exactly on time, follows
+/// each slider along its path, and spins spinners around the playfield's
+/// center. Objects that start while a slider or spinner is still held are
+/// hit with the other key which then keeps holding the earlier object.
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{osu::OsuAutoplay, Beatmap};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let map = Beatmap::from_path("/path/to/map.osu")?;
+///
+/// let autoplay = OsuAutoplay::new(&map).mods(16); // HR
+/// let frames = autoplay.generate()?;
+///
+/// autoplay.replay()?.encode_to_path("/path/to/autoplay.osr")?;
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct OsuAutoplay<'map> {
+    map: &'map Beatmap,
+    difficulty: Difficulty,
+}
+
+impl<'map> OsuAutoplay<'map> {
+    /// Create a new autoplay generator for the given map.
+    pub const fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            difficulty: Difficulty::new(),
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`] for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Generate the frames of a perfect play, sorted by time.
+    ///
+    /// As in `.osr` files, times are in milliseconds of the map, i.e. not
+    /// adjusted to the clock rate.
+    pub fn generate(&self) -> Result<Vec<ReplayFrame>, ConvertError> {
+        let mods = self.difficulty.get_mods();
+        let map = self.map.convert_ref(GameMode::Osu, mods)?;
+
+        let OsuDifficultySetup {
+            scaling_factor,
+            mut attrs,
+            time_preempt,
+            ..
+        } = OsuDifficultySetup::new(&self.difficulty, &map);
+
+        let reflection = mods.reflection();
+
+        let objects = convert_objects(
+            &map,
+            &scaling_factor,
+            reflection,
+            time_preempt,
+            usize::MAX,
+            &mut attrs,
+        );
+
+        let mut generator = Generator::new(objects.len());
+        let mut curve_bufs = CurveBuffers::default();
+
+        for (i, (h, hit_object)) in objects.iter().zip(map.hit_objects.iter()).enumerate() {
+            // Alternate keys so that each object is a new press
+            let key = if i % 2 == 0 {
+                ReplayFrame::M1
+            } else {
+                ReplayFrame::M2
+            };
+
+            // Objects that start while a previous one is still held, e.g.
+            // circles during sliders in 2B maps, interrupt its frames
+            let interrupted = generator.interrupt(h.start_time);
+
+            match (&h.kind, &hit_object.kind) {
+                (OsuObjectKind::Slider(slider), HitObjectKind::Slider(hit_slider)) => {
+                    let path = SliderPath {
+                        h,
+                        slider,
+                        curve: hit_slider.curve(GameMode::Osu, &mut curve_bufs),
+                        span_count: hit_slider.span_count() as f64,
+                        reflection,
+                    };
+
+                    generator.move_to(h.stacked_pos(), h.start_time);
+                    generator.hold(h.start_time, slider.end_time, key, |time| {
+                        path.position_at(time)
+                    });
+                }
+                (OsuObjectKind::Spinner(spinner), _) => {
+                    let end_time = h.start_time + spinner.duration;
+
+                    generator.move_to(spin_position(h.start_time), h.start_time);
+                    generator.hold(h.start_time, end_time, key, spin_position);
+                }
+                _ => {
+                    generator.move_to(h.stacked_pos(), h.start_time);
+                    generator.press(h.start_time, key);
+                }
+            }
+
+            let end_time = h.end_time();
+            let mut interrupted = interrupted.into_iter().skip_while(|f| f.time <= end_time);
+
+            if let Some(frame) = interrupted.next() {
+                generator.resume(iter::once(frame).chain(interrupted), key);
+            } else {
+                let next_start = objects.get(i + 1).map_or(f64::INFINITY, |h| h.start_time);
+                generator.release(end_time, next_start);
+            }
+        }
+
+        Ok(generator.frames)
+    }
+
+    /// Generate a [`Replay`] of a perfect play.
+    ///
+    /// The replay's hit results are based on judging the frames of
+    /// [`OsuAutoplay::generate`] as osu!stable would. Mod settings, e.g.
+    /// custom clock rates, cannot be stored in the legacy replay format and
+    /// are lost.
+    pub fn replay(&self) -> Result<Replay, ConvertError> {
+        let frames = self.generate()?;
+        let mods = self.difficulty.get_mods();
+
+        let judged = OsuReplayJudge::new(self.map)
+            .mods(mods.clone())
+            .lazer(false)
+            .judge(&frames)?;
+
+        let perfect = judged
+            .objects
+            .iter()
+            .all(|h| h.result != OsuHitResult::Miss && !h.slider_break);
+
+        let state = judged.state;
+
+        Ok(Replay {
+            mode: GameMode::Osu,
+            version: REPLAY_VERSION,
+            beatmap_md5: self
+                .map
+                .checksum
+                .map_or_else(String::new, |checksum| checksum.to_string()),
+            player_name: String::from("osu!"),
+            replay_md5: String::new(),
+            n300: state.n300 as u16,
+            n100: state.n100 as u16,
+            n50: state.n50 as u16,
+            n_geki: 0,
+            n_katu: 0,
+            misses: state.misses as u16,
+            score: 0,
+            max_combo: state.max_combo as u16,
+            perfect,
+            mods: mods.bits(),
+            life_bar: String::new(),
+            timestamp: 0,
+            frames,
+            seed: None,
+            online_score_id: 0,
+            target_practice_accuracy: None,
+            lazer: None,
+        })
+    }
+}
+
+struct Generator {
+    frames: Vec<ReplayFrame>,
+    cursor: Pos,
+    /// Time of the latest frame.
+    time: Option<f64>,
+}
+
+impl Generator {
+    fn new(len: usize) -> Self {
+        Self {
+            frames: Vec::with_capacity(len * 8),
+            cursor: SPINNER_CENTER,
+            time: None,
+        }
+    }
+
+    fn push(&mut self, time: f64, pos: Pos, keys: u32) {
+        self.frames.push(ReplayFrame {
+            time,
+            x: pos.x,
+            y: pos.y,
+            keys,
+        });
+
+        self.cursor = pos;
+        self.time = Some(time);
+    }
+
+    /// Moves the cursor without pressing keys so that it reaches `target`
+    /// at `end_time`.
+    fn move_to(&mut self, target: Pos, end_time: f64) {
+        let start_time = if let Some(time) = self.time {
+            time
+        } else {
+            let time = (end_time - LEAD_IN).max(0.0);
+
+            if time < end_time {
+                self.push(time, self.cursor, 0);
+            }
+
+            time
+        };
+
+        let start = self.cursor;
+        let duration = end_time - start_time;
+        let mut time = start_time + FRAME_TIME;
+
+        while time < end_time {
+            let t = ((time - start_time) / duration) as f32;
+            let eased = t * t * (3.0 - 2.0 * t);
+            self.push(time, start + (target - start) * eased, 0);
+            time += FRAME_TIME;
+        }
+
+        self.cursor = target;
+    }
+
+    fn press(&mut self, time: f64, key: u32) {
+        self.push(time, self.cursor, key);
+    }
+
+    /// Holds `key` from `start_time` until `end_time` while the cursor is at
+    /// the given position for each frame.
+    fn hold(&mut self, start_time: f64, end_time: f64, key: u32, pos_at: impl Fn(f64) -> Pos) {
+        let mut time = start_time;
+
+        while time < end_time {
+            self.push(time, pos_at(time), key);
+            time += FRAME_TIME;
+        }
+
+        self.push(end_time, pos_at(end_time), key);
+    }
+
+    /// Releases all keys after an object ended at `end_time` but before the
+    /// next object starts unless that one starts even earlier.
+    fn release(&mut self, end_time: f64, next_start: f64) {
+        let time = (end_time + KEY_UP_DELAY)
+            .min(f64::midpoint(end_time, next_start))
+            .max(self.time.unwrap_or(end_time));
+
+        self.push(time, self.cursor, 0);
+    }
+
+    /// Removes and returns all frames at or after `start_time`.
+    fn interrupt(&mut self, start_time: f64) -> Vec<ReplayFrame> {
+        let idx = self.frames.partition_point(|frame| frame.time < start_time);
+        let interrupted = self.frames.split_off(idx);
+
+        if !interrupted.is_empty() {
+            let last = self.frames.last();
+            self.time = last.map(|frame| frame.time);
+
+            if let Some(frame) = last {
+                self.cursor = Pos::new(frame.x, frame.y);
+            }
+        }
+
+        interrupted
+    }
+
+    /// Continues interrupted frames while holding `key` instead of their
+    /// original key.
+    fn resume(&mut self, frames: impl Iterator<Item = ReplayFrame>, key: u32) {
+        for frame in frames {
+            let keys = if frame.keys == 0 { 0 } else { key };
+            self.push(frame.time, Pos::new(frame.x, frame.y), keys);
+        }
+    }
+}
+
+/// The position on a slider's path for any point in time.
+struct SliderPath<'a> {
+    h: &'a OsuObject,
+    slider: &'a OsuSlider,
+    curve: BorrowedCurve<'a>,
+    span_count: f64,
+    reflection: Reflection,
+}
+
+impl SliderPath<'_> {
+    fn position_at(&self, time: f64) -> Pos {
+        let duration = self.slider.end_time - self.h.start_time;
+
+        if duration <= 0.0 {
+            return self.h.stacked_pos();
+        }
+
+        let progress = ((time - self.h.start_time) / duration).clamp(0.0, 1.0);
+        let span = (progress * self.span_count) as i32;
+        let mut span_progress = progress * self.span_count % 1.0;
+
+        if span % 2 == 1 {
+            span_progress = 1.0 - span_progress;
+        }
+
+        let mut offset = self.curve.position_at(span_progress);
+
+        match self.reflection {
+            Reflection::None => {}
+            Reflection::Vertical => offset.y = -offset.y,
+            Reflection::Horizontal => offset.x = -offset.x,
+            Reflection::Both => offset = Pos::new(-offset.x, -offset.y),
+        }
+
+        self.h.stacked_pos() + offset
+    }
+}
+
+fn spin_position(time: f64) -> Pos {
+    let angle = time * SPIN_SPEED;
+    let offset = Pos::new(angle.cos() as f32, angle.sin() as f32) * SPIN_RADIUS;
+
+    SPINNER_CENTER + offset
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        model::{
+            beatmap::BeatmapBuilder, control_point::TimingPoint, hit_object::PathControlPoint,
+        },
+        osu::Osu,
+        Difficulty,
+    };
+
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+        for mods in [0, 16] {
+            let attrs = Difficulty::new()
+                .mods(mods)
+                .calculate_for_mode::<Osu>(&map)
+                .unwrap();
+
+            let frames = OsuAutoplay::new(&map).mods(mods).generate().unwrap();
+
+            assert!(frames.windows(2).all(|w| w[0].time <= w[1].time));
+
+            let judged = OsuReplayJudge::new(&map).mods(mods).judge(&frames).unwrap();
+
+            let state = judged.state;
+            assert_eq!(state.n300 as usize, map.hit_objects.len());
+            assert_eq!(state.max_combo, attrs.max_combo);
+            assert_eq!(state.large_tick_hits, attrs.n_large_ticks);
+            assert_eq!(state.slider_end_hits, attrs.n_sliders);
+        }
+    }
+
+    #[test]
+    fn replay_roundtrip() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let replay = OsuAutoplay::new(&map).mods(8 + 64).replay().unwrap();
+
+        assert!(replay.perfect);
+        assert_eq!(usize::from(replay.n300), map.hit_objects.len());
+
+        let mut bytes = Vec::new();
+        replay.encode(&mut bytes).unwrap();
+        let decoded = Replay::from_bytes(&bytes).unwrap();
+
+        assert_eq!(decoded.frames.len(), replay.frames.len());
+        assert_eq!(decoded.mods, replay.mods);
+
+        let judged = OsuReplayJudge::new(&map)
+            .mods(decoded.mods())
+            .lazer(false)
+            .judge(&decoded.frames)
+            .unwrap();
+
+        assert_eq!(judged.state.misses, 0);
+        assert_eq!(u16::try_from(judged.state.max_combo), Ok(replay.max_combo));
+    }
+    #[test]
+    fn overlapping_objects() {
+        let control_points = vec![
+            PathControlPoint::new(Pos::new(0.0, 0.0)),
+            PathControlPoint::new(Pos::new(200.0, 0.0)),
+        ];
+
+        // A circle in the middle of a slider from 1000ms to 2000ms
+        let map = BeatmapBuilder::new()
+            .slider_multiplier(1.0)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .slider(Pos::new(100.0, 100.0), 1000.0, control_points, 0, None)
+            .circle(Pos::new(300.0, 300.0), 1500.0)
+            .circle(Pos::new(100.0, 300.0), 2500.0)
+            .build()
+            .unwrap();
+
+        let frames = OsuAutoplay::new(&map).generate().unwrap();
+
+        assert!(frames.windows(2).all(|w| w[0].time <= w[1].time));
+
+        let circle = frames.iter().find(|frame| frame.time >= 1500.0).unwrap();
+        assert!((circle.time - 1500.0).abs() < f64::EPSILON);
+        assert_eq!(circle.keys, ReplayFrame::M2);
+
+        // The slider is held with the circle's key until its end
+        let slider_end = frames.iter().rfind(|frame| frame.time < 2000.0).unwrap();
+        assert_eq!(slider_end.keys, ReplayFrame::M2);
+
+        let judged = OsuReplayJudge::new(&map).judge(&frames).unwrap();
+        assert_eq!(judged.state.misses, 0);
+    }
+}
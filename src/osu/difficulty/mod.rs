@@ -45,10 +45,10 @@ pub fn difficulty(
 }
 
 pub struct OsuDifficultySetup {
-    scaling_factor: ScalingFactor,
-    map_attrs: BeatmapAttributes,
-    attrs: OsuDifficultyAttributes,
-    time_preempt: f64,
+    pub scaling_factor: ScalingFactor,
+    pub map_attrs: BeatmapAttributes,
+    pub attrs: OsuDifficultyAttributes,
+    pub time_preempt: f64,
 }
 
 impl OsuDifficultySetup {
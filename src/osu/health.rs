@@ -0,0 +1,44 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+
+use crate::{
+    any::health::{HealthJudgement, JudgementKind},
+    Beatmap,
+};
+
+use super::object::{NestedSliderObjectKind, OsuObject, OsuObjectKind};
+
+pub fn health_judgements(map: &Beatmap) -> Vec<HealthJudgement> {
+    let mut curve_bufs = CurveBuffers::default();
+    let mut ticks_buf = Vec::new();
+
+    let mut judgements = Vec::with_capacity(map.hit_objects.len());
+
+    for (i, h) in map.hit_objects.iter().enumerate() {
+        let h = OsuObject::new(h, map, &mut curve_bufs, &mut ticks_buf);
+
+        if let OsuObjectKind::Slider(ref slider) = h.kind {
+            // Head
+            judgements.push(HealthJudgement::new(
+                h.start_time,
+                i,
+                JudgementKind::SliderEnd,
+            ));
+
+            for nested in slider.nested_objects.iter() {
+                let kind = match nested.kind {
+                    NestedSliderObjectKind::Repeat | NestedSliderObjectKind::Tail => {
+                        JudgementKind::SliderEnd
+                    }
+                    NestedSliderObjectKind::Tick => JudgementKind::LargeTick,
+                };
+
+                judgements.push(HealthJudgement::new(nested.start_time, i, kind));
+            }
+        }
+
+        // The circle, the slider itself, or the spinner's completion
+        judgements.push(HealthJudgement::new(h.end_time(), i, JudgementKind::Object));
+    }
+
+    judgements
+}
@@ -0,0 +1,647 @@
+use std::f64::consts::{PI, TAU};
+
+use rosu_map::{section::general::GameMode, util::Pos};
+
+use crate::{
+    any::Difficulty,
+    model::{
+        beatmap::{BeatmapAttributesBuilder, HitWindows},
+        mode::ConvertError,
+        mods::GameMods,
+        replay::{HitErrorStatistics, ReplayFrame},
+    },
+    util::difficulty::difficulty_range,
+    Beatmap,
+};
+
+use super::{
+    convert::convert_objects,
+    difficulty::OsuDifficultySetup,
+    object::{NestedSliderObjectKind, OsuObject, OsuObjectKind, OsuSlider},
+    score_state::OsuScoreState,
+};
+
+/// Presses earlier than this before an object's start time are ignored.
+const MISS_WINDOW: f64 = 400.0;
+
+/// Radius of the follow circle relative to the object radius.
+const FOLLOW_AREA: f64 = 2.4;
+
+const SPINNER_CENTER: Pos = Pos::new(256.0, 192.0);
+
+const MAX_ROTATIONS_PER_SEC: f64 = 477.0 / 60.0;
+
+/// Both mouse buttons; keyboard keys always come with their mouse button.
+const BUTTONS: u32 = ReplayFrame::M1 | ReplayFrame::M2;
+
+/// Judges the frames of an osu!standard replay against a [`Beatmap`].
+///
+/// # Example
+///
+/// ```no_run
+/// use rosu_pp::{model::replay::Replay, osu::OsuReplayJudge, Beatmap};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let replay = Replay::from_path("/path/to/replay.osr")?;
+/// let map = Beatmap::from_path("/path/to/map.osu")?;
+///
+/// let judged = OsuReplayJudge::new(&map)
+///     .mods(replay.mods())
+///     .lazer(replay.is_lazer())
+///     .judge(&replay.frames)?;
+///
+/// println!("{:?}", judged.state);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[must_use]
+pub struct OsuReplayJudge<'map> {
+    map: &'map Beatmap,
+    difficulty: Difficulty,
+}
+
+impl<'map> OsuReplayJudge<'map> {
+    /// Create a new replay judge for the given map.
+    pub const fn new(map: &'map Beatmap) -> Self {
+        Self {
+            map,
+            difficulty: Difficulty::new(),
+        }
+    }
+
+    /// Specify mods.
+    ///
+    /// See [`Difficulty::mods`] for accepted types.
+    pub fn mods(mut self, mods: impl Into<GameMods>) -> Self {
+        self.difficulty = self.difficulty.mods(mods);
+
+        self
+    }
+
+    /// Adjust the clock rate used in the calculation.
+    ///
+    /// If none is specified, it will take the clock rate based on the mods
+    /// i.e. 1.5 for DT, 0.75 for HT and 1.0 otherwise.
+    pub fn clock_rate(mut self, clock_rate: f64) -> Self {
+        self.difficulty = self.difficulty.clock_rate(clock_rate);
+
+        self
+    }
+
+    /// Whether the replay was set on osu!lazer or osu!stable.
+    ///
+    /// Defaults to `true`.
+    ///
+    /// On osu!lazer, sliders are judged by their head unless the `Classic`
+    /// mod disables slider head accuracy and the resulting [`OsuScoreState`]
+    /// includes slider tick and slider end hits.
+    pub fn lazer(mut self, lazer: bool) -> Self {
+        self.difficulty = self.difficulty.lazer(lazer);
+
+        self
+    }
+
+    /// Judge each hitobject of the map based on the given frames.
+    ///
+    /// Frames must be sorted by time, e.g. as in [`Replay::frames`].
+    ///
+    /// [`Replay::frames`]: crate::model::replay::Replay::frames
+    pub fn judge(&self, frames: &[ReplayFrame]) -> Result<OsuJudgedReplay, ConvertError> {
+        let map = self
+            .map
+            .convert_ref(GameMode::Osu, self.difficulty.get_mods())?;
+
+        let OsuDifficultySetup {
+            scaling_factor,
+            map_attrs,
+            mut attrs,
+            time_preempt,
+        } = OsuDifficultySetup::new(&self.difficulty, &map);
+
+        let mods = self.difficulty.get_mods();
+
+        let objects = convert_objects(
+            &map,
+            &scaling_factor,
+            mods.reflection(),
+            time_preempt,
+            usize::MAX,
+            &mut attrs,
+        );
+
+        let judge = Judge {
+            frames,
+            windows: Windows::new(&map_attrs.hit_windows, map_attrs.clock_rate),
+            radius: scaling_factor.radius,
+            clock_rate: map_attrs.clock_rate,
+        };
+
+        let lazer = self.difficulty.get_lazer();
+        let classic = mods.no_slider_head_acc(lazer);
+
+        Ok(judge.judge(&objects, lazer, classic))
+    }
+}
+
+/// The judged hitobjects of a replay.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OsuJudgedReplay {
+    /// The resulting score state.
+    pub state: OsuScoreState,
+    /// The judgement of each hitobject in the map's order.
+    pub objects: Vec<OsuObjectJudgement>,
+}
+
+impl OsuJudgedReplay {
+    /// Calculate [`HitErrorStatistics`] based on the hit error of each
+    /// circle and slider head.
+    ///
+    /// Returns `None` if no object was hit.
+    pub fn hit_error_statistics(&self) -> Option<HitErrorStatistics> {
+        let hit_errors = self.objects.iter().filter_map(|h| h.hit_error).collect();
+
+        HitErrorStatistics::new(hit_errors)
+    }
+}
+
+/// The judgement of a single hitobject.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OsuObjectJudgement {
+    /// The start time of the hitobject.
+    pub start_time: f64,
+    /// The hitobject's judgement.
+    pub result: OsuHitResult,
+    /// The offset of the circle or slider head hit in milliseconds, adjusted
+    /// to the clock rate; negative if the hit was early.
+    ///
+    /// `None` for misses and spinners.
+    pub hit_error: Option<f64>,
+    /// Whether the combo broke during a slider, i.e. its head, a tick, or a
+    /// repeat was missed.
+    pub slider_break: bool,
+}
+
+/// The judgement of a hitobject.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum OsuHitResult {
+    Great,
+    Ok,
+    Meh,
+    Miss,
+}
+
+/// Hit windows in map time.
+struct Windows {
+    great: f64,
+    ok: f64,
+    meh: f64,
+}
+
+impl Windows {
+    fn new(hit_windows: &HitWindows, clock_rate: f64) -> Self {
+        Self {
+            great: hit_windows.od_great * clock_rate,
+            ok: hit_windows.od_ok.unwrap_or(0.0) * clock_rate,
+            meh: hit_windows.od_meh.unwrap_or(0.0) * clock_rate,
+        }
+    }
+
+    fn result(&self, offset: f64) -> OsuHitResult {
+        let offset = offset.abs();
+
+        if offset <= self.great {
+            OsuHitResult::Great
+        } else if offset <= self.ok {
+            OsuHitResult::Ok
+        } else if offset <= self.meh {
+            OsuHitResult::Meh
+        } else {
+            OsuHitResult::Miss
+        }
+    }
+}
+
+/// A circle or slider head that was clicked.
+#[derive(Copy, Clone)]
+struct HeadHit {
+    time: f64,
+    result: OsuHitResult,
+}
+
+struct Judge<'a> {
+    frames: &'a [ReplayFrame],
+    windows: Windows,
+    radius: f64,
+    clock_rate: f64,
+}
+
+impl Judge<'_> {
+    fn judge(&self, objects: &[OsuObject], lazer: bool, classic: bool) -> OsuJudgedReplay {
+        let heads = self.judge_heads(objects);
+
+        let mut state = OsuScoreState::new();
+        let mut judgements = Vec::with_capacity(objects.len());
+
+        // Time of each combo change and whether the combo increased or broke
+        let mut combo_events = Vec::new();
+
+        for (h, head) in objects.iter().zip(heads) {
+            let head_time = head.map_or(h.start_time + self.windows.meh, |head| head.time);
+            let head_result = head.map_or(OsuHitResult::Miss, |head| head.result);
+            let head_hit = head_result != OsuHitResult::Miss;
+
+            let hit_error = head
+                .filter(|_| head_hit)
+                .map(|head| (head.time - h.start_time) / self.clock_rate);
+
+            let (result, slider_break) = match h.kind {
+                OsuObjectKind::Circle => {
+                    combo_events.push((head_time, head_hit));
+
+                    (head_result, false)
+                }
+                OsuObjectKind::Slider(ref slider) => {
+                    combo_events.push((head_time, head_hit));
+
+                    let mut nested_hits = 0;
+                    let mut slider_break = !head_hit;
+
+                    for (time, pos, kind) in nested_objects(h, slider) {
+                        let hit = self.is_tracking(time, pos);
+                        nested_hits += u32::from(hit);
+
+                        match kind {
+                            NestedSliderObjectKind::Tail if hit => {
+                                if classic {
+                                    state.small_tick_hits += 1;
+                                } else {
+                                    state.slider_end_hits += 1;
+                                }
+
+                                combo_events.push((time, true));
+                            }
+                            // Missing the tail does not break combo
+                            NestedSliderObjectKind::Tail => {}
+                            NestedSliderObjectKind::Repeat | NestedSliderObjectKind::Tick => {
+                                state.large_tick_hits += u32::from(hit);
+                                slider_break |= !hit;
+                                combo_events.push((time, hit));
+                            }
+                        }
+                    }
+
+                    let result = if classic {
+                        state.large_tick_hits += u32::from(head_hit);
+
+                        let hits = u32::from(head_hit) + nested_hits;
+                        let total = 1 + slider.nested_objects.len() as u32;
+
+                        if hits == total {
+                            OsuHitResult::Great
+                        } else if hits * 2 >= total {
+                            OsuHitResult::Ok
+                        } else if hits > 0 {
+                            OsuHitResult::Meh
+                        } else {
+                            OsuHitResult::Miss
+                        }
+                    } else {
+                        head_result
+                    };
+
+                    (result, slider_break)
+                }
+                OsuObjectKind::Spinner(spinner) => {
+                    let end_time = h.start_time + spinner.duration;
+                    let result = self.judge_spinner(h.start_time, end_time);
+                    combo_events.push((end_time, result != OsuHitResult::Miss));
+
+                    (result, false)
+                }
+            };
+
+            match result {
+                OsuHitResult::Great => state.n300 += 1,
+                OsuHitResult::Ok => state.n100 += 1,
+                OsuHitResult::Meh => state.n50 += 1,
+                OsuHitResult::Miss => state.misses += 1,
+            }
+
+            judgements.push(OsuObjectJudgement {
+                start_time: h.start_time,
+                result,
+                hit_error: hit_error.filter(|_| !h.is_spinner()),
+                slider_break,
+            });
+        }
+
+        combo_events.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+        let mut combo = 0;
+
+        for (_, hit) in combo_events {
+            combo = if hit { combo + 1 } else { 0 };
+            state.max_combo = state.max_combo.max(combo);
+        }
+
+        if !lazer {
+            state.large_tick_hits = 0;
+            state.small_tick_hits = 0;
+            state.slider_end_hits = 0;
+        }
+
+        OsuJudgedReplay {
+            state,
+            objects: judgements,
+        }
+    }
+
+    /// Assigns presses to circles and slider heads.
+    ///
+    /// Like on osu!lazer and osu!stable, an object can only be hit after all
+    /// previous objects have been judged.
+    fn judge_heads(&self, objects: &[OsuObject]) -> Vec<Option<HeadHit>> {
+        let mut heads = vec![None; objects.len()];
+
+        let mut hittable = objects
+            .iter()
+            .enumerate()
+            .filter(|(_, h)| !h.is_spinner())
+            .peekable();
+
+        for (time, pos) in self.presses() {
+            // Objects whose hit window passed are missed
+            while hittable
+                .next_if(|(_, h)| h.start_time + self.windows.meh < time)
+                .is_some()
+            {}
+
+            let Some((i, h)) = hittable.peek() else {
+                break;
+            };
+
+            let offset = time - h.start_time;
+
+            if offset < -MISS_WINDOW || f64::from(pos.distance(h.stacked_pos())) > self.radius {
+                continue;
+            }
+
+            heads[*i] = Some(HeadHit {
+                time,
+                result: self.windows.result(offset),
+            });
+
+            hittable.next();
+        }
+
+        heads
+    }
+
+    /// Time and position of each newly pressed button.
+    fn presses(&self) -> impl Iterator<Item = (f64, Pos)> + '_ {
+        let prev_keys = std::iter::once(0).chain(self.frames.iter().map(|frame| frame.keys));
+
+        self.frames
+            .iter()
+            .zip(prev_keys)
+            .flat_map(|(frame, prev_keys)| {
+                let pressed = frame.keys & !prev_keys & BUTTONS;
+                let press = (frame.time, Pos::new(frame.x, frame.y));
+
+                std::iter::repeat_n(press, pressed.count_ones() as usize)
+            })
+    }
+
+    /// Whether a button is held and the cursor is within the follow circle.
+    fn is_tracking(&self, time: f64, pos: Pos) -> bool {
+        let idx = self.frames.partition_point(|frame| frame.time <= time);
+
+        let Some(prev) = idx.checked_sub(1).map(|i| &self.frames[i]) else {
+            return false;
+        };
+
+        if prev.keys & BUTTONS == 0 {
+            return false;
+        }
+
+        let prev_pos = Pos::new(prev.x, prev.y);
+
+        let cursor = match self.frames.get(idx) {
+            Some(next) if next.time > prev.time => {
+                let t = ((time - prev.time) / (next.time - prev.time)) as f32;
+
+                prev_pos + (Pos::new(next.x, next.y) - prev_pos) * t
+            }
+            _ => prev_pos,
+        };
+
+        f64::from(cursor.distance(pos)) <= self.radius * FOLLOW_AREA
+    }
+
+    fn judge_spinner(&self, start_time: f64, end_time: f64) -> OsuHitResult {
+        let od = BeatmapAttributesBuilder::osu_great_hit_window_to_od(self.windows.great);
+        let min_rotations_per_sec = 0.6 * difficulty_range(od, 3.0, 5.0, 7.5);
+        let required = ((end_time - start_time) / 1000.0 * min_rotations_per_sec).trunc();
+
+        if required <= 0.0 {
+            return OsuHitResult::Great;
+        }
+
+        let progress = self.spinner_rotations(start_time, end_time) / required;
+
+        if progress >= 1.0 {
+            OsuHitResult::Great
+        } else if progress > 0.9 {
+            OsuHitResult::Ok
+        } else if progress > 0.75 {
+            OsuHitResult::Meh
+        } else {
+            OsuHitResult::Miss
+        }
+    }
+
+    fn spinner_rotations(&self, start_time: f64, end_time: f64) -> f64 {
+        let mut total = 0.0;
+        let mut prev: Option<(f64, f64)> = None;
+
+        let frames = self
+            .frames
+            .iter()
+            .skip_while(|frame| frame.time < start_time)
+            .take_while(|frame| frame.time <= end_time);
+
+        for frame in frames {
+            if frame.keys & BUTTONS == 0 {
+                prev = None;
+
+                continue;
+            }
+
+            let diff = Pos::new(frame.x, frame.y) - SPINNER_CENTER;
+            let angle = f64::from(diff.y).atan2(f64::from(diff.x));
+
+            if let Some((prev_time, prev_angle)) = prev {
+                let mut delta = angle - prev_angle;
+
+                if delta > PI {
+                    delta -= TAU;
+                } else if delta < -PI {
+                    delta += TAU;
+                }
+
+                let max = MAX_ROTATIONS_PER_SEC * TAU * (frame.time - prev_time) / 1000.0;
+                total += delta.clamp(-max, max);
+            }
+
+            prev = Some((frame.time, angle));
+        }
+
+        total.abs() / TAU
+    }
+}
+
+/// Time, position, and kind of a slider's nested objects at which they are
+/// judged.
+fn nested_objects<'a>(
+    h: &'a OsuObject,
+    slider: &'a OsuSlider,
+) -> impl Iterator<Item = (f64, Pos, NestedSliderObjectKind)> + 'a {
+    slider
+        .nested_objects
+        .iter()
+        .map(|nested| match nested.kind {
+            // * The tail is judged at the tracking end time rather than the
+            // * actual end of the slider.
+            NestedSliderObjectKind::Tail => (
+                h.start_time + slider.lazy_travel_time,
+                slider.lazy_end_pos,
+                nested.kind,
+            ),
+            NestedSliderObjectKind::Repeat | NestedSliderObjectKind::Tick => {
+                (nested.start_time, nested.pos + h.stack_offset, nested.kind)
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Frames that hit each object at the given offset and track every
+    /// slider.
+    fn frames(map: &Beatmap, lazer: bool, offset: f64) -> Vec<ReplayFrame> {
+        let difficulty = Difficulty::new().lazer(lazer);
+
+        let OsuDifficultySetup {
+            scaling_factor,
+            mut attrs,
+            time_preempt,
+            ..
+        } = OsuDifficultySetup::new(&difficulty, map);
+
+        let objects = convert_objects(
+            map,
+            &scaling_factor,
+            difficulty.get_mods().reflection(),
+            time_preempt,
+            usize::MAX,
+            &mut attrs,
+        );
+
+        let mut frames = Vec::new();
+        let frame = |time: f64, pos: Pos, keys: u32| ReplayFrame {
+            time,
+            x: pos.x,
+            y: pos.y,
+            keys,
+        };
+
+        for (i, h) in objects.iter().enumerate() {
+            // Alternate keys so that each object is a new press
+            let key = if i % 2 == 0 {
+                ReplayFrame::M1
+            } else {
+                ReplayFrame::M2
+            };
+
+            match h.kind {
+                OsuObjectKind::Circle => {
+                    frames.push(frame(h.start_time + offset, h.stacked_pos(), key));
+                }
+                OsuObjectKind::Slider(ref slider) => {
+                    frames.push(frame(h.start_time + offset, h.stacked_pos(), key));
+
+                    let mut nested: Vec<_> = nested_objects(h, slider)
+                        .filter(|(time, ..)| *time > h.start_time + offset)
+                        .map(|(time, pos, _)| frame(time, pos, key))
+                        .collect();
+
+                    nested.sort_by(|a, b| a.time.total_cmp(&b.time));
+                    frames.extend(nested);
+                }
+                OsuObjectKind::Spinner(spinner) => {
+                    let mut time = h.start_time;
+
+                    while time <= h.start_time + spinner.duration {
+                        let angle = time / 1000.0 * 5.0 * TAU;
+                        let pos = Pos::new(angle.cos() as f32, angle.sin() as f32) * 50.0;
+                        frames.push(frame(time, SPINNER_CENTER + pos, key));
+                        time += 16.0;
+                    }
+                }
+            }
+        }
+
+        frames.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+        frames
+    }
+
+    #[test]
+    fn perfect() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let attrs = Difficulty::new()
+            .calculate_for_mode::<crate::osu::Osu>(&map)
+            .unwrap();
+
+        for lazer in [false, true] {
+            let judged = OsuReplayJudge::new(&map)
+                .lazer(lazer)
+                .judge(&frames(&map, lazer, 0.0))
+                .unwrap();
+
+            let state = judged.state;
+            assert_eq!(state.n300 as usize, map.hit_objects.len());
+            assert_eq!(state.misses, 0);
+            assert_eq!(state.max_combo, attrs.max_combo);
+
+            if lazer {
+                assert_eq!(state.large_tick_hits, attrs.n_large_ticks);
+                assert_eq!(state.slider_end_hits, attrs.n_sliders);
+            } else {
+                assert_eq!(state.large_tick_hits, 0);
+            }
+
+            assert!(judged.objects.iter().all(|h| !h.slider_break));
+        }
+    }
+
+    #[test]
+    fn late_hits() {
+        let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+        let judge = OsuReplayJudge::new(&map).lazer(true);
+        let windows = map.attributes().build().hit_windows;
+
+        let offset = f64::midpoint(windows.od_great, windows.od_ok.unwrap());
+        let judged = judge.judge(&frames(&map, true, offset)).unwrap();
+
+        for h in judged.objects.iter().filter(|h| h.hit_error.is_some()) {
+            assert_eq!(h.result, OsuHitResult::Ok);
+            assert!((h.hit_error.unwrap() - offset).abs() < 1e-6);
+        }
+
+        let judged = judge.judge(&[]).unwrap();
+        assert_eq!(judged.state.misses as usize, map.hit_objects.len());
+        assert_eq!(judged.state.max_combo, 0);
+    }
+}
@@ -0,0 +1,133 @@
+use rosu_map::section::hit_objects::CurveBuffers;
+use rosu_mods::GameModsLegacy;
+
+use crate::{
+    any::{legacy_score::combo_score, LegacyObjectScore, LegacyScoreAttributes},
+    model::mods::GameMods,
+    Beatmap,
+};
+
+use super::object::{NestedSliderObjectKind, OsuObject, OsuObjectKind};
+
+const MAX_ROTATIONS_PER_SEC: f64 = 477.0 / 60.0;
+
+/// * Normally, this value depends on the final overall difficulty. For
+/// * simplicity, we'll only consider the worst case that maximises bonus
+/// * score.
+const MIN_ROTATIONS_PER_SEC: f64 = 3.0;
+
+pub fn legacy_score(
+    map: &Beatmap,
+    mods: &GameMods,
+    difficulty_multiplier: u32,
+) -> LegacyScoreAttributes {
+    let mod_multiplier = mod_multiplier(mods);
+    let score_multiplier = f64::from(difficulty_multiplier) * mod_multiplier;
+
+    let mut curve_bufs = CurveBuffers::default();
+    let mut ticks_buf = Vec::new();
+
+    let mut combo = 0;
+
+    let objects = map
+        .hit_objects
+        .iter()
+        .map(|h| {
+            let h = OsuObject::new(h, map, &mut curve_bufs, &mut ticks_buf);
+            let mut score = LegacyObjectScore::new(h.start_time);
+
+            match h.kind {
+                OsuObjectKind::Circle => {}
+                OsuObjectKind::Slider(ref slider) => {
+                    // Head
+                    score.accuracy_score += 30;
+                    combo += 1;
+
+                    for nested in slider.nested_objects.iter() {
+                        score.accuracy_score += match nested.kind {
+                            NestedSliderObjectKind::Repeat | NestedSliderObjectKind::Tail => 30,
+                            NestedSliderObjectKind::Tick => 10,
+                        };
+
+                        combo += 1;
+                    }
+                }
+                OsuObjectKind::Spinner(spinner) => {
+                    let secs = spinner.duration / 1000.0;
+
+                    // * The total amount of half spins possible for the entire spinner.
+                    let total_half_spins = (secs * MAX_ROTATIONS_PER_SEC * 2.0) as i32;
+                    // * The amount of half spins that are required to successfully complete the spinner (i.e. get a 300).
+                    let half_spins_for_completion = (secs * MIN_ROTATIONS_PER_SEC) as i32;
+                    // * To be able to receive bonus points, the spinner must be rotated another 1.5 times.
+                    let half_spins_before_bonus = half_spins_for_completion + 3;
+
+                    for i in 0..=total_half_spins {
+                        if i > half_spins_before_bonus && (i - half_spins_before_bonus) % 2 == 0 {
+                            score.bonus_score += 1100;
+                        } else if i > 1 && i % 2 == 0 {
+                            score.bonus_score += 100;
+                        }
+                    }
+                }
+            }
+
+            // The circle, the slider's end, or the spinner's completion
+            score.accuracy_score += 300;
+            score.combo_score += combo_score(combo, 300, score_multiplier);
+
+            if !matches!(h.kind, OsuObjectKind::Slider(_)) {
+                combo += 1;
+            }
+
+            score
+        })
+        .collect();
+
+    LegacyScoreAttributes::new(difficulty_multiplier, mod_multiplier, combo, objects)
+}
+
+fn mod_multiplier(mods: &GameMods) -> f64 {
+    let mods = GameModsLegacy::from_bits(mods.bits());
+    let score_v2 = mods.contains(GameModsLegacy::ScoreV2);
+
+    if mods.intersects(GameModsLegacy::Relax | GameModsLegacy::Autopilot) {
+        return 0.0;
+    }
+
+    let mut multiplier = 1.0;
+
+    if mods.contains(GameModsLegacy::NoFail) && !score_v2 {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::Easy) {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::HalfTime) {
+        multiplier *= 0.3;
+    }
+
+    if mods.contains(GameModsLegacy::Hidden) {
+        multiplier *= 1.06;
+    }
+
+    if mods.contains(GameModsLegacy::HardRock) {
+        multiplier *= if score_v2 { 1.1 } else { 1.06 };
+    }
+
+    if mods.contains(GameModsLegacy::DoubleTime) {
+        multiplier *= if score_v2 { 1.2 } else { 1.12 };
+    }
+
+    if mods.contains(GameModsLegacy::Flashlight) {
+        multiplier *= 1.12;
+    }
+
+    if mods.contains(GameModsLegacy::SpunOut) {
+        multiplier *= 0.9;
+    }
+
+    multiplier
+}
@@ -8,17 +8,25 @@ use crate::{
     Difficulty,
 };
 
+pub(crate) use self::{health::health_judgements, legacy_score::legacy_score};
+
 pub use self::{
     attributes::{OsuDifficultyAttributes, OsuPerformanceAttributes},
+    autoplay::OsuAutoplay,
     difficulty::gradual::OsuGradualDifficulty,
+    judge::{OsuHitResult, OsuJudgedReplay, OsuObjectJudgement, OsuReplayJudge},
     performance::{gradual::OsuGradualPerformance, OsuPerformance},
     score_state::{OsuScoreOrigin, OsuScoreState},
     strains::OsuStrains,
 };
 
 mod attributes;
+mod autoplay;
 mod convert;
 mod difficulty;
+mod health;
+mod judge;
+mod legacy_score;
 mod object;
 mod performance;
 mod score_state;
@@ -27,6 +27,7 @@ pub(super) struct OsuPerformanceCalculator<'mods> {
     state: OsuScoreState,
     effective_miss_count: f64,
     using_classic_slider_acc: bool,
+    measured_speed_deviation: Option<f64>,
 }
 
 impl<'a> OsuPerformanceCalculator<'a> {
@@ -37,6 +38,7 @@ impl<'a> OsuPerformanceCalculator<'a> {
         state: OsuScoreState,
         effective_miss_count: f64,
         using_classic_slider_acc: bool,
+        measured_speed_deviation: Option<f64>,
     ) -> Self {
         Self {
             attrs,
@@ -45,6 +47,7 @@ impl<'a> OsuPerformanceCalculator<'a> {
             state,
             effective_miss_count,
             using_classic_slider_acc,
+            measured_speed_deviation,
         }
     }
 }
@@ -95,7 +98,9 @@ impl OsuPerformanceCalculator<'_> {
                 .min(total_hits);
         }
 
-        let speed_deviation = self.calculate_speed_deviation();
+        let speed_deviation = self
+            .measured_speed_deviation
+            .or_else(|| self.calculate_speed_deviation());
 
         let aim_value = self.compute_aim_value();
         let speed_value = self.compute_speed_value(speed_deviation);
@@ -135,6 +135,7 @@ mod tests {
     use super::*;
 
     #[test]
+    #[allow(clippy::manual_is_multiple_of)]
     fn next_and_nth() {
         let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
 
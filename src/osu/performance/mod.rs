@@ -11,7 +11,7 @@ use crate::{
     mania::ManiaPerformance,
     model::{mode::ConvertError, mods::GameMods},
     taiko::TaikoPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{map_or_attrs::MapOrAttrs, solve},
     Beatmap,
 };
 
@@ -39,6 +39,7 @@ pub struct OsuPerformance<'map> {
     pub(crate) n100: Option<u32>,
     pub(crate) n50: Option<u32>,
     pub(crate) misses: Option<u32>,
+    pub(crate) speed_deviation: Option<f64>,
     pub(crate) hitresult_priority: HitResultPriority,
 }
 
@@ -230,6 +231,19 @@ impl<'map> OsuPerformance<'map> {
         self
     }
 
+    /// Specify the measured standard deviation of hit errors in
+    /// milliseconds, e.g. through [`HitErrorStatistics::deviation`].
+    ///
+    /// If none is specified, the deviation on speed notes will be estimated
+    /// based on the hitresults.
+    ///
+    /// [`HitErrorStatistics::deviation`]: crate::model::replay::HitErrorStatistics::deviation
+    pub const fn speed_deviation(mut self, deviation: f64) -> Self {
+        self.speed_deviation = Some(deviation);
+
+        self
+    }
+
     /// Use the specified settings of the given [`Difficulty`].
     pub fn difficulty(mut self, difficulty: Difficulty) -> Self {
         self.difficulty = difficulty;
@@ -733,11 +747,58 @@ impl<'map> OsuPerformance<'map> {
             state,
             effective_miss_count,
             using_classic_slider_acc,
+            self.speed_deviation,
         );
 
         Ok(inner.calculate())
     }
 
+    /// Find the minimum accuracy between `0.0` and `100.0` that still reaches
+    /// `target_pp` or `None` if even 100% accuracy falls short.
+    ///
+    /// Specified `n300`, `n100`, and `n50` are ignored. Since they are
+    /// generated from the accuracy, the result may be marginally above the
+    /// true minimum.
+    pub fn min_accuracy_for_pp(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        self.map_or_attrs.difficulty_attrs(&self.difficulty)?;
+        self.n300 = None;
+        self.n100 = None;
+        self.n50 = None;
+
+        solve::min_accuracy(target_pp, |acc| {
+            self.clone().accuracy(acc).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the maximum amount of misses that still reaches `target_pp` or
+    /// `None` if even no misses fall short.
+    pub fn max_misses_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .n_objects();
+
+        solve::max_value(target_pp, max, |misses| {
+            self.clone()
+                .misses(misses)
+                .calculate()
+                .map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the minimum combo that still reaches `target_pp` or `None` if
+    /// even a full combo falls short.
+    pub fn min_combo_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .max_combo();
+
+        solve::min_value(target_pp, max, |combo| {
+            self.clone().combo(combo).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Osu>) -> Self {
         Self {
             map_or_attrs,
@@ -751,6 +812,7 @@ impl<'map> OsuPerformance<'map> {
             n100: None,
             n50: None,
             misses: None,
+            speed_deviation: None,
             hitresult_priority: HitResultPriority::DEFAULT,
         }
     }
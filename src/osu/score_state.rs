@@ -2,6 +2,7 @@ use crate::util::{float_ext::FloatExt, hint::unlikely};
 
 /// Aggregation for a score's current state.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OsuScoreState {
     /// Maximum combo that the score has had so far. **Not** the maximum
     /// possible combo of the map so far.
@@ -103,6 +104,7 @@ impl Default for OsuScoreState {
 
 /// Type to pass [`OsuScoreState::accuracy`] and specify the origin of a score.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum OsuScoreOrigin {
     /// For scores set on osu!stable
     Stable,
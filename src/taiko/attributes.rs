@@ -2,6 +2,7 @@ use crate::taiko::performance::TaikoPerformance;
 
 /// The result of a difficulty calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoDifficultyAttributes {
     /// The difficulty of the stamina skill.
     pub stamina: f64,
@@ -49,6 +50,7 @@ impl TaikoDifficultyAttributes {
 
 /// The result of a performance calculation on an osu!taiko map.
 #[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoPerformanceAttributes {
     /// The difficulty attributes that were used for the performance calculation
     pub difficulty: TaikoDifficultyAttributes,
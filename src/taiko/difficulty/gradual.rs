@@ -282,6 +282,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::manual_is_multiple_of)]
     fn next_and_nth() {
         let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
 
@@ -0,0 +1,165 @@
+use rosu_map::section::{general::GameMode, hit_objects::hit_samples::HitSoundType};
+
+use crate::{
+    model::{mode::ConvertError, replay::ReplayFrame},
+    util::difficulty::difficulty_range,
+    Beatmap, Difficulty,
+};
+
+use super::{
+    convert,
+    object::{HitType, TaikoObject},
+};
+
+/// Centre keys; left and right.
+const CENTRE: u32 = ReplayFrame::M1 | ReplayFrame::K1;
+
+/// Rim keys; left and right.
+const RIM: u32 = ReplayFrame::M2 | ReplayFrame::K2;
+
+/// Time in which the second key of a strong hit is not judged separately.
+const STRONG_HIT_WINDOW: f64 = 30.0;
+
+/// Match presses of the frames to circles and return the offset of each
+/// successful hit, adjusted to the clock rate.
+pub fn hit_errors(
+    difficulty: &Difficulty,
+    map: &Beatmap,
+    frames: &[ReplayFrame],
+) -> Result<Vec<f64>, ConvertError> {
+    let mut map = map.convert_ref(GameMode::Taiko, difficulty.get_mods())?;
+
+    if let Some(seed) = difficulty.get_mods().random_seed() {
+        convert::apply_random_to_beatmap(map.to_mut(), seed);
+    }
+
+    let clock_rate = difficulty.get_clock_rate();
+    let hit_windows = map.attributes().difficulty(difficulty).hit_windows();
+
+    // Hit windows in map time
+    let ok_window = hit_windows.od_ok.unwrap_or(0.0) * clock_rate;
+    let od = (50.0 - hit_windows.od_great * clock_rate) / 3.0;
+    let miss_window = miss_window(od);
+
+    let mut circles = map
+        .hit_objects
+        .iter()
+        .zip(map.hit_sounds.iter())
+        .map(|(h, sound)| {
+            (
+                TaikoObject::new(h, *sound),
+                sound.has_flag(HitSoundType::FINISH),
+            )
+        })
+        .filter(|(h, _)| h.is_hit())
+        .peekable();
+
+    let mut hit_errors = Vec::new();
+
+    // Hit type and time of the last strong hit
+    let mut last_strong: Option<(HitType, f64)> = None;
+
+    let prev_keys = std::iter::once(0).chain(frames.iter().map(|frame| frame.keys));
+
+    for (frame, prev_keys) in frames.iter().zip(prev_keys) {
+        let pressed = frame.keys & !prev_keys;
+
+        let hit_types = [
+            (pressed & CENTRE, HitType::Center),
+            (pressed & RIM, HitType::Rim),
+        ];
+
+        for (keys, hit_type) in hit_types {
+            for _ in 0..keys.count_ones() {
+                let time = frame.time;
+
+                if last_strong.is_some_and(|(strong_type, strong_time)| {
+                    strong_type == hit_type && time - strong_time <= STRONG_HIT_WINDOW
+                }) {
+                    last_strong = None;
+
+                    continue;
+                }
+
+                // Circles whose hit window passed are missed
+                while circles
+                    .next_if(|(h, _)| h.start_time + ok_window < time)
+                    .is_some()
+                {}
+
+                let Some((h, is_strong)) =
+                    circles.next_if(|(h, _)| h.start_time - miss_window <= time)
+                else {
+                    continue;
+                };
+
+                let offset = time - h.start_time;
+
+                // Wrong hit types and early hits outside of the ok window
+                // are misses
+                if h.hit_type != hit_type || offset < -ok_window {
+                    continue;
+                }
+
+                hit_errors.push(offset / clock_rate);
+
+                last_strong = is_strong.then_some((hit_type, time));
+            }
+        }
+    }
+
+    Ok(hit_errors)
+}
+
+const fn miss_window(od: f64) -> f64 {
+    difficulty_range(od, 135.0, 95.0, 70.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn perfect() {
+        let map = Beatmap::from_path("./resources/1028484.osu").unwrap();
+        let difficulty = Difficulty::new();
+
+        let frames: Vec<_> = map
+            .hit_objects
+            .iter()
+            .zip(map.hit_sounds.iter())
+            .map(|(h, sound)| TaikoObject::new(h, *sound))
+            .filter(TaikoObject::is_hit)
+            .enumerate()
+            .flat_map(|(i, h)| {
+                let key = match (h.hit_type, i % 2) {
+                    (HitType::Center, 0) => ReplayFrame::M1,
+                    (HitType::Center, _) => ReplayFrame::K1,
+                    (_, 0) => ReplayFrame::M2,
+                    (_, _) => ReplayFrame::K2,
+                };
+
+                [
+                    ReplayFrame {
+                        time: h.start_time + 5.0,
+                        x: 0.0,
+                        y: 0.0,
+                        keys: key,
+                    },
+                    ReplayFrame {
+                        time: h.start_time + 6.0,
+                        x: 0.0,
+                        y: 0.0,
+                        keys: 0,
+                    },
+                ]
+            })
+            .collect();
+
+        let hit_errors = hit_errors(&difficulty, &map, &frames).unwrap();
+        let n_circles = map.hit_objects.iter().filter(|h| h.is_circle()).count();
+
+        assert_eq!(hit_errors.len(), n_circles);
+        assert!(hit_errors.iter().all(|offset| (offset - 5.0).abs() < 1e-9));
+    }
+}
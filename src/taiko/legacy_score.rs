@@ -0,0 +1,224 @@
+use rosu_map::section::hit_objects::hit_samples::HitSoundType;
+use rosu_mods::GameModsLegacy;
+
+use crate::{
+    any::{LegacyObjectScore, LegacyScoreAttributes},
+    model::{control_point::TimingPoint, hit_object::HitObjectKind, mods::GameMods},
+    Beatmap,
+};
+
+use super::convert::drum_roll_duration;
+
+/// * Normally, this value depends on the final overall difficulty. For
+/// * simplicity, we'll only consider the worst case that maximises rotations.
+const MIN_ROTATIONS_PER_SEC: f64 = 7.5;
+
+pub fn legacy_score(
+    map: &Beatmap,
+    mods: &GameMods,
+    difficulty_multiplier: u32,
+) -> LegacyScoreAttributes {
+    let mod_multiplier = mod_multiplier(mods);
+    let score_multiplier = f64::from(difficulty_multiplier) * mod_multiplier;
+
+    let mut combo = 0;
+
+    let objects = map
+        .hit_objects
+        .iter()
+        .zip(map.hit_sounds.iter())
+        .map(|(h, sound)| {
+            let mut score = LegacyObjectScore::new(h.start_time);
+            let is_strong = sound.has_flag(HitSoundType::FINISH);
+
+            match h.kind {
+                HitObjectKind::Circle => {
+                    let strong_multiplier = if is_strong { 2 } else { 1 };
+
+                    score.accuracy_score += 300 * strong_multiplier;
+                    score.combo_score +=
+                        combo_score(combo, 300, score_multiplier) * strong_multiplier;
+
+                    combo += 1;
+                }
+                HitObjectKind::Slider(ref slider) => {
+                    let duration = drum_roll_duration(map, h.start_time, slider);
+                    let end_time = h.start_time + f64::from(duration);
+
+                    let beat_len = map
+                        .timing_point_at(h.start_time)
+                        .map_or(TimingPoint::DEFAULT_BEAT_LEN, |point| point.beat_len);
+
+                    // * lazer's `TaikoBeatmapConverter` sets `DrumRoll.TickRate` as
+                    // * `SliderTickRate == 3 ? 3 : 4`
+                    let tick_rate = if (map.slider_tick_rate - 3.0).abs() < f64::EPSILON {
+                        3.0
+                    } else {
+                        4.0
+                    };
+
+                    let tick_spacing = beat_len / tick_rate;
+
+                    if tick_spacing > 0.0 {
+                        let tick_value = if is_strong { 600 } else { 300 };
+                        let mut time = h.start_time;
+
+                        while time < end_time + tick_spacing / 2.0 {
+                            score.bonus_score += tick_value;
+                            time += tick_spacing;
+                        }
+                    }
+                }
+                HitObjectKind::Spinner(spinner) => {
+                    // * The amount of half spins that are required to successfully complete the spinner (i.e. get a 300).
+                    let half_spins = (spinner.duration / 1000.0 * MIN_ROTATIONS_PER_SEC) as i32;
+                    let half_spins = ((half_spins as f32 * 1.65) as i32).max(1);
+
+                    score.bonus_score += 300 * (half_spins as u32 + 1);
+
+                    // Swell completion
+                    score.bonus_score += 300;
+                    score.combo_score += combo_score(combo, 300, score_multiplier);
+                }
+                HitObjectKind::Hold(_) => {}
+            }
+
+            score
+        })
+        .collect();
+
+    LegacyScoreAttributes::new(difficulty_multiplier, mod_multiplier, combo, objects)
+}
+
+fn combo_score(combo: u32, hit_value: u32, score_multiplier: f64) -> u32 {
+    // Integer divisions are intentional to match osu!stable
+    (f64::from(hit_value / 35 * 2) * score_multiplier * f64::from(combo.min(100) / 10)) as u32
+}
+
+fn mod_multiplier(mods: &GameMods) -> f64 {
+    let mods = GameModsLegacy::from_bits(mods.bits());
+    let score_v2 = mods.contains(GameModsLegacy::ScoreV2);
+
+    if mods.contains(GameModsLegacy::Relax) {
+        return 0.0;
+    }
+
+    let mut multiplier = 1.0;
+
+    if mods.contains(GameModsLegacy::NoFail) && !score_v2 {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::Easy) {
+        multiplier *= 0.5;
+    }
+
+    if mods.contains(GameModsLegacy::HalfTime) {
+        multiplier *= 0.3;
+    }
+
+    if mods.contains(GameModsLegacy::Hidden) {
+        multiplier *= 1.06;
+    }
+
+    if mods.contains(GameModsLegacy::HardRock) {
+        multiplier *= if score_v2 { 1.1 } else { 1.06 };
+    }
+
+    if mods.contains(GameModsLegacy::DoubleTime) {
+        multiplier *= if score_v2 { 1.2 } else { 1.12 };
+    }
+
+    if mods.contains(GameModsLegacy::Flashlight) {
+        multiplier *= 1.12;
+    }
+
+    multiplier
+}
+
+#[cfg(test)]
+mod tests {
+    use rosu_map::section::general::GameMode;
+
+    use crate::model::{
+        beatmap::BeatmapBuilder,
+        hit_object::{HitObject, PathControlPoint, PathType, Pos, Slider},
+    };
+
+    use super::*;
+
+    /// A map with a single drum roll of 1000ms at 120bpm.
+    fn drum_roll(slider_tick_rate: f64, hit_sound: u8) -> Beatmap {
+        let control_points = vec![
+            PathControlPoint {
+                pos: Pos::new(0.0, 0.0),
+                path_type: Some(PathType::LINEAR),
+            },
+            PathControlPoint {
+                pos: Pos::new(200.0, 0.0),
+                path_type: None,
+            },
+        ];
+
+        let slider = Slider {
+            expected_dist: Some(200.0),
+            repeats: 0,
+            control_points: control_points.into_boxed_slice(),
+            node_sounds: vec![HitSoundType::from(HitSoundType::NONE); 2].into_boxed_slice(),
+        };
+
+        BeatmapBuilder::new()
+            .mode(GameMode::Taiko)
+            .slider_multiplier(1.0)
+            .slider_tick_rate(slider_tick_rate)
+            .timing_point(TimingPoint::new(0.0, 500.0))
+            .hit_object(
+                HitObject {
+                    pos: Pos::new(0.0, 0.0),
+                    start_time: 1000.0,
+                    kind: HitObjectKind::Slider(slider),
+                },
+                HitSoundType::from(hit_sound),
+            )
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn drum_roll_ticks() {
+        // Ticks every 125ms from 1000ms through 2000ms
+        let map = drum_roll(1.0, HitSoundType::NONE);
+        let attrs = legacy_score(&map, &GameMods::DEFAULT, 5);
+
+        assert_eq!(drum_roll_duration(&map, 1000.0, slider(&map)), 1000);
+        assert_eq!(attrs.bonus_score, 9 * 300);
+        assert_eq!(attrs.accuracy_score + attrs.combo_score, 0);
+        assert_eq!(attrs.max_combo, 0);
+
+        // A tick rate of 3 keeps its tick rate, anything else uses 4
+        let map = drum_roll(3.0, HitSoundType::NONE);
+        assert_eq!(
+            legacy_score(&map, &GameMods::DEFAULT, 5).bonus_score,
+            7 * 300
+        );
+
+        let map = drum_roll(2.0, HitSoundType::NONE);
+        assert_eq!(
+            legacy_score(&map, &GameMods::DEFAULT, 5).bonus_score,
+            9 * 300
+        );
+
+        let map = drum_roll(1.0, HitSoundType::FINISH);
+        assert_eq!(
+            legacy_score(&map, &GameMods::DEFAULT, 5).bonus_score,
+            9 * 600
+        );
+    }
+
+    fn slider(map: &Beatmap) -> &Slider {
+        match map.hit_objects[0].kind {
+            HitObjectKind::Slider(ref slider) => slider,
+            _ => unreachable!(),
+        }
+    }
+}
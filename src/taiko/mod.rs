@@ -8,6 +8,10 @@ use crate::{
     Difficulty,
 };
 
+pub(crate) use self::{
+    convert::drum_roll_duration, hit_errors::hit_errors, legacy_score::legacy_score,
+};
+
 pub use self::{
     attributes::{TaikoDifficultyAttributes, TaikoPerformanceAttributes},
     difficulty::gradual::TaikoGradualDifficulty,
@@ -19,6 +23,8 @@ pub use self::{
 mod attributes;
 mod convert;
 mod difficulty;
+mod hit_errors;
+mod legacy_score;
 mod object;
 mod performance;
 mod score_state;
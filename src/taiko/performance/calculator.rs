@@ -8,6 +8,7 @@ pub(super) struct TaikoPerformanceCalculator<'mods> {
     attrs: TaikoDifficultyAttributes,
     mods: &'mods GameMods,
     state: TaikoScoreState,
+    measured_unstable_rate: Option<f64>,
 }
 
 impl<'a> TaikoPerformanceCalculator<'a> {
@@ -15,8 +16,14 @@ impl<'a> TaikoPerformanceCalculator<'a> {
         attrs: TaikoDifficultyAttributes,
         mods: &'a GameMods,
         state: TaikoScoreState,
+        measured_unstable_rate: Option<f64>,
     ) -> Self {
-        Self { attrs, mods, state }
+        Self {
+            attrs,
+            mods,
+            state,
+            measured_unstable_rate,
+        }
     }
 }
 
@@ -26,7 +33,9 @@ impl TaikoPerformanceCalculator<'_> {
         // * and increasing the miss penalty for shorter object counts lower than 1000.
         let total_successful_hits = self.total_successful_hits();
 
-        let estimated_unstable_rate = self.compute_deviation_upper_bound().map(|v| v * 10.0);
+        let estimated_unstable_rate = self
+            .measured_unstable_rate
+            .or_else(|| self.compute_deviation_upper_bound().map(|v| v * 10.0));
 
         let effective_miss_count = if total_successful_hits > 0 {
             (1000.0 / f64::from(total_successful_hits)).max(1.0) * f64::from(self.state.misses)
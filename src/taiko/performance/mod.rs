@@ -8,7 +8,7 @@ use crate::{
     any::{Difficulty, HitResultPriority, IntoModePerformance, IntoPerformance},
     model::{mode::ConvertError, mods::GameMods},
     osu::OsuPerformance,
-    util::map_or_attrs::MapOrAttrs,
+    util::{map_or_attrs::MapOrAttrs, solve},
     Performance,
 };
 
@@ -18,6 +18,9 @@ mod calculator;
 pub mod gradual;
 
 /// Performance calculator on osu!taiko maps.
+///
+/// Unlike osu! and osu!catch, there is no `min_combo_for_pp` because combo
+/// does not affect osu!taiko performance.
 #[derive(Clone, Debug, PartialEq)]
 #[must_use]
 pub struct TaikoPerformance<'map> {
@@ -29,6 +32,7 @@ pub struct TaikoPerformance<'map> {
     n300: Option<u32>,
     n100: Option<u32>,
     misses: Option<u32>,
+    unstable_rate: Option<f64>,
 }
 
 impl<'map> TaikoPerformance<'map> {
@@ -124,6 +128,19 @@ impl<'map> TaikoPerformance<'map> {
         self
     }
 
+    /// Specify the measured unstable rate of the play, e.g. through
+    /// [`HitErrorStatistics::unstable_rate`].
+    ///
+    /// If none is specified, the unstable rate will be estimated based on
+    /// the hitresults.
+    ///
+    /// [`HitErrorStatistics::unstable_rate`]: crate::model::replay::HitErrorStatistics::unstable_rate
+    pub const fn unstable_rate(mut self, unstable_rate: f64) -> Self {
+        self.unstable_rate = Some(unstable_rate);
+
+        self
+    }
+
     /// Specify the accuracy of a play between `0.0` and `100.0`.
     /// This will be used to generate matching hitresults.
     pub fn accuracy(mut self, acc: f64) -> Self {
@@ -314,7 +331,45 @@ impl<'map> TaikoPerformance<'map> {
             MapOrAttrs::Map(ref map) => self.difficulty.calculate_for_mode::<Taiko>(map)?,
         };
 
-        Ok(TaikoPerformanceCalculator::new(attrs, self.difficulty.get_mods(), state).calculate())
+        let inner = TaikoPerformanceCalculator::new(
+            attrs,
+            self.difficulty.get_mods(),
+            state,
+            self.unstable_rate,
+        );
+
+        Ok(inner.calculate())
+    }
+
+    /// Find the minimum accuracy between `0.0` and `100.0` that still reaches
+    /// `target_pp` or `None` if even 100% accuracy falls short.
+    ///
+    /// Specified `n300` and `n100` are ignored. Since they are generated from
+    /// the accuracy, the result may be marginally above the true minimum.
+    pub fn min_accuracy_for_pp(mut self, target_pp: f64) -> Result<Option<f64>, ConvertError> {
+        self.map_or_attrs.difficulty_attrs(&self.difficulty)?;
+        self.n300 = None;
+        self.n100 = None;
+
+        solve::min_accuracy(target_pp, |acc| {
+            self.clone().accuracy(acc).calculate().map(|attrs| attrs.pp)
+        })
+    }
+
+    /// Find the maximum amount of misses that still reaches `target_pp` or
+    /// `None` if even no misses fall short.
+    pub fn max_misses_for_pp(mut self, target_pp: f64) -> Result<Option<u32>, ConvertError> {
+        let max = self
+            .map_or_attrs
+            .difficulty_attrs(&self.difficulty)?
+            .max_combo();
+
+        solve::max_value(target_pp, max, |misses| {
+            self.clone()
+                .misses(misses)
+                .calculate()
+                .map(|attrs| attrs.pp)
+        })
     }
 
     pub(crate) const fn from_map_or_attrs(map_or_attrs: MapOrAttrs<'map, Taiko>) -> Self {
@@ -324,6 +379,7 @@ impl<'map> TaikoPerformance<'map> {
             combo: None,
             acc: None,
             misses: None,
+            unstable_rate: None,
             n300: None,
             n100: None,
             hitresult_priority: HitResultPriority::DEFAULT,
@@ -363,6 +419,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n100,
             n50: _,
             misses,
+            speed_deviation: _,
             hitresult_priority,
         } = osu;
 
@@ -375,6 +432,7 @@ impl<'map> TryFrom<OsuPerformance<'map>> for TaikoPerformance<'map> {
             n300,
             n100,
             misses,
+            unstable_rate: None,
         })
     }
 }
@@ -11,6 +11,7 @@ use super::difficulty::TaikoSkills;
 ///
 /// Suitable to plot the difficulty of a map over time.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TaikoStrains {
     /// Strain peaks of the color skill.
     pub color: Vec<f64>,
@@ -50,6 +50,16 @@ pub const fn smootherstep(x: f64, start: f64, end: f64) -> f64 {
     x * x * x * (x * (6.0 * x - 15.0) + 10.0)
 }
 
+pub const fn difficulty_range(difficulty: f64, min: f64, mid: f64, max: f64) -> f64 {
+    if difficulty > 5.0 {
+        mid + (max - mid) * (difficulty - 5.0) / 5.0
+    } else if difficulty < 5.0 {
+        mid - (mid - min) * (5.0 - difficulty) / 5.0
+    } else {
+        mid
+    }
+}
+
 pub const fn reverse_lerp(x: f64, start: f64, end: f64) -> f64 {
     f64::clamp((x - start) / (end - start), 0.0, 1.0)
 }
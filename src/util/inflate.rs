@@ -0,0 +1,407 @@
+//! Decoder for raw DEFLATE streams as specified in [RFC 1951].
+//!
+//! [RFC 1951]: https://www.rfc-editor.org/rfc/rfc1951
+
+use std::{error, fmt};
+
+const MAX_BITS: usize = 15;
+const MAX_LIT_CODES: usize = 286;
+const MAX_DIST_CODES: usize = 30;
+const FIXED_LIT_CODES: usize = 288;
+
+const LEN_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+
+const LEN_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+
+/// Order in which code length code lengths are stored in a dynamic block.
+const CODE_LEN_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompress a raw DEFLATE stream.
+///
+/// The output may not exceed `max_len` bytes. Since `max_len` may come from
+/// untrusted input, the output is not preallocated but grows as needed.
+pub fn inflate(input: &[u8], max_len: usize) -> Result<Vec<u8>, InflateError> {
+    let mut reader = BitReader::new(input);
+    let mut output = Vec::new();
+
+    loop {
+        let is_last = reader.bits(1)? == 1;
+
+        match reader.bits(2)? {
+            0 => stored_block(&mut reader, &mut output, max_len)?,
+            1 => {
+                let (lit, dist) = fixed_codes();
+                compressed_block(&mut reader, &mut output, max_len, &lit, &dist)?;
+            }
+            2 => {
+                let (lit, dist) = dynamic_codes(&mut reader)?;
+                compressed_block(&mut reader, &mut output, max_len, &lit, &dist)?;
+            }
+            _ => return Err(InflateError::BlockType),
+        }
+
+        if is_last {
+            return Ok(output);
+        }
+    }
+}
+
+fn stored_block(
+    reader: &mut BitReader<'_>,
+    output: &mut Vec<u8>,
+    max_len: usize,
+) -> Result<(), InflateError> {
+    reader.align();
+
+    let len = reader.u16()?;
+    let nlen = reader.u16()?;
+
+    if len != !nlen {
+        return Err(InflateError::StoredLength);
+    }
+
+    let bytes = reader.bytes(usize::from(len))?;
+
+    if output.len() + bytes.len() > max_len {
+        return Err(InflateError::TooLong);
+    }
+
+    output.extend_from_slice(bytes);
+
+    Ok(())
+}
+
+fn compressed_block(
+    reader: &mut BitReader<'_>,
+    output: &mut Vec<u8>,
+    max_len: usize,
+    lit: &Huffman,
+    dist: &Huffman,
+) -> Result<(), InflateError> {
+    loop {
+        let symbol = usize::from(lit.decode(reader)?);
+
+        if symbol < 256 {
+            if output.len() == max_len {
+                return Err(InflateError::TooLong);
+            }
+
+            output.push(symbol as u8);
+
+            continue;
+        } else if symbol == 256 {
+            return Ok(());
+        }
+
+        let idx = symbol - 257;
+
+        let (Some(&base), Some(&extra)) = (LEN_BASE.get(idx), LEN_EXTRA.get(idx)) else {
+            return Err(InflateError::Symbol);
+        };
+
+        let len = usize::from(base) + reader.bits(extra)? as usize;
+
+        let idx = usize::from(dist.decode(reader)?);
+
+        let (Some(&base), Some(&extra)) = (DIST_BASE.get(idx), DIST_EXTRA.get(idx)) else {
+            return Err(InflateError::Symbol);
+        };
+
+        let distance = usize::from(base) + reader.bits(extra)? as usize;
+
+        if distance > output.len() {
+            return Err(InflateError::Distance);
+        } else if output.len() + len > max_len {
+            return Err(InflateError::TooLong);
+        }
+
+        let start = output.len() - distance;
+
+        // Source and destination may overlap so bytes are copied one by one
+        for i in start..start + len {
+            output.push(output[i]);
+        }
+    }
+}
+
+fn fixed_codes() -> (Huffman, Huffman) {
+    let mut lengths = [0; FIXED_LIT_CODES];
+    lengths[..144].fill(8);
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+    lengths[280..].fill(8);
+
+    let lit = Huffman::new(&lengths).expect("valid fixed literal codes");
+    let dist = Huffman::new(&[5; MAX_DIST_CODES]).expect("valid fixed distance codes");
+
+    (lit, dist)
+}
+
+fn dynamic_codes(reader: &mut BitReader<'_>) -> Result<(Huffman, Huffman), InflateError> {
+    let lit_count = reader.bits(5)? as usize + 257;
+    let dist_count = reader.bits(5)? as usize + 1;
+    let code_len_count = reader.bits(4)? as usize + 4;
+
+    if lit_count > MAX_LIT_CODES || dist_count > MAX_DIST_CODES {
+        return Err(InflateError::CodeLengths);
+    }
+
+    let mut code_lens = [0; CODE_LEN_ORDER.len()];
+
+    for &idx in CODE_LEN_ORDER.iter().take(code_len_count) {
+        code_lens[idx] = reader.bits(3)? as u8;
+    }
+
+    let code_len_huffman = Huffman::new(&code_lens)?;
+
+    let mut lengths = [0; MAX_LIT_CODES + MAX_DIST_CODES];
+    let total = lit_count + dist_count;
+    let mut i = 0;
+
+    while i < total {
+        let symbol = code_len_huffman.decode(reader)?;
+
+        let (len, repeat) = match symbol {
+            0..=15 => {
+                lengths[i] = symbol as u8;
+                i += 1;
+
+                continue;
+            }
+            16 => {
+                let Some(&prev) = i.checked_sub(1).map(|prev| &lengths[prev]) else {
+                    return Err(InflateError::CodeLengths);
+                };
+
+                (prev, 3 + reader.bits(2)?)
+            }
+            17 => (0, 3 + reader.bits(3)?),
+            _ => (0, 11 + reader.bits(7)?),
+        };
+
+        let repeat = repeat as usize;
+
+        if i + repeat > total {
+            return Err(InflateError::CodeLengths);
+        }
+
+        lengths[i..i + repeat].fill(len);
+        i += repeat;
+    }
+
+    // The end-of-block code must be present
+    if lengths[256] == 0 {
+        return Err(InflateError::CodeLengths);
+    }
+
+    let lit = Huffman::new(&lengths[..lit_count])?;
+    let dist = Huffman::new(&lengths[lit_count..total])?;
+
+    Ok((lit, dist))
+}
+
+/// Canonical huffman code.
+struct Huffman {
+    /// Number of codes for each bit length.
+    counts: [u16; MAX_BITS + 1],
+    /// Symbols ordered by their code.
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, InflateError> {
+        let mut counts = [0; MAX_BITS + 1];
+
+        for &len in lengths {
+            counts[usize::from(len)] += 1;
+        }
+
+        // Check for an over-subscribed set of lengths. Incomplete codes are
+        // fine; invalid codes will be rejected while decoding.
+        let mut left = 1_i32;
+
+        for &count in counts.iter().skip(1) {
+            left <<= 1;
+            left -= i32::from(count);
+
+            if left < 0 {
+                return Err(InflateError::CodeLengths);
+            }
+        }
+
+        let mut offsets = [0; MAX_BITS + 1];
+
+        for len in 1..MAX_BITS {
+            offsets[len + 1] = offsets[len] + counts[len];
+        }
+
+        let mut symbols = vec![0; lengths.len()];
+
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len > 0 {
+                let offset = &mut offsets[usize::from(len)];
+                symbols[usize::from(*offset)] = symbol as u16;
+                *offset += 1;
+            }
+        }
+
+        Ok(Self { counts, symbols })
+    }
+
+    fn decode(&self, reader: &mut BitReader<'_>) -> Result<u16, InflateError> {
+        let mut code = 0;
+        let mut first = 0;
+        let mut idx = 0;
+
+        for &count in self.counts.iter().skip(1) {
+            code |= reader.bits(1)? as usize;
+            let count = usize::from(count);
+
+            if code < first + count {
+                return Ok(self.symbols[idx + code - first]);
+            }
+
+            idx += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(InflateError::Symbol)
+    }
+}
+
+/// Reads bits starting with the least significant bit of each byte.
+struct BitReader<'a> {
+    input: &'a [u8],
+    pos: usize,
+    buf: u32,
+    count: u8,
+}
+
+impl<'a> BitReader<'a> {
+    const fn new(input: &'a [u8]) -> Self {
+        Self {
+            input,
+            pos: 0,
+            buf: 0,
+            count: 0,
+        }
+    }
+
+    fn bits(&mut self, n: u8) -> Result<u32, InflateError> {
+        while self.count < n {
+            let byte = *self.input.get(self.pos).ok_or(InflateError::Eof)?;
+            self.pos += 1;
+            self.buf |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+
+        let bits = self.buf & ((1 << n) - 1);
+        self.buf >>= n;
+        self.count -= n;
+
+        Ok(bits)
+    }
+
+    /// Discard the remaining bits of the current byte.
+    const fn align(&mut self) {
+        self.buf = 0;
+        self.count = 0;
+    }
+
+    fn u16(&mut self) -> Result<u16, InflateError> {
+        let bytes = self.bytes(2)?;
+
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], InflateError> {
+        let bytes = self
+            .input
+            .get(self.pos..self.pos + len)
+            .ok_or(InflateError::Eof)?;
+
+        self.pos += len;
+
+        Ok(bytes)
+    }
+}
+
+/// Error when decompressing a DEFLATE stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InflateError {
+    BlockType,
+    CodeLengths,
+    Distance,
+    Eof,
+    StoredLength,
+    Symbol,
+    TooLong,
+}
+
+impl error::Error for InflateError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for InflateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::BlockType => "invalid block type",
+            Self::CodeLengths => "invalid code lengths",
+            Self::Distance => "distance too far back",
+            Self::Eof => "unexpected end of data",
+            Self::StoredLength => "stored block length did not match its complement",
+            Self::Symbol => "invalid symbol",
+            Self::TooLong => "decompressed data exceeds expected length",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored() {
+        let input = [0x01, 0x05, 0x00, 0xFA, 0xFF, b'h', b'e', b'l', b'l', b'o'];
+
+        assert_eq!(inflate(&input, 5).unwrap(), b"hello");
+        assert_eq!(inflate(&input, 4), Err(InflateError::TooLong));
+    }
+
+    #[test]
+    fn fixed() {
+        // "osu! osu! osu! osu!"
+        let input = [0xCB, 0x2F, 0x2E, 0x55, 0x54, 0xC8, 0x47, 0x21, 0x00];
+
+        assert_eq!(inflate(&input, 64).unwrap(), b"osu! osu! osu! osu!");
+    }
+
+    #[test]
+    fn truncated() {
+        let input = [0xCB, 0x2F, 0x2E];
+
+        assert_eq!(inflate(&input, 64), Err(InflateError::Eof));
+    }
+}
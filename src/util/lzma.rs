@@ -0,0 +1,636 @@
+//! Decoder and encoder for LZMA streams in the legacy `.lzma` format as used
+//! by osu! replays.
+//!
+//! The format consists of a 13 byte header containing the properties, the
+//! dictionary size, and the uncompressed size, followed by the range coded
+//! data. See the [LZMA specification] for more info.
+//!
+//! [LZMA specification]: https://www.7-zip.org/sdk.html
+
+use std::{error, fmt};
+
+const HEADER_LEN: usize = 13;
+const UNKNOWN_SIZE: u64 = u64::MAX;
+
+const NUM_STATES: usize = 12;
+const NUM_POS_BITS_MAX: usize = 4;
+const NUM_LEN_TO_POS_STATES: usize = 4;
+const NUM_ALIGN_BITS: u32 = 4;
+const END_POS_MODEL_INDEX: u32 = 14;
+const NUM_FULL_DISTANCES: usize = 1 << (END_POS_MODEL_INDEX >> 1);
+const MATCH_MIN_LEN: usize = 2;
+
+const NUM_BIT_MODEL_TOTAL_BITS: u32 = 11;
+const BIT_MODEL_TOTAL: u16 = 1 << NUM_BIT_MODEL_TOTAL_BITS;
+const NUM_MOVE_BITS: u32 = 5;
+const PROB_INIT: u16 = BIT_MODEL_TOTAL / 2;
+const TOP_VALUE: u32 = 1 << 24;
+
+/// Decompress an LZMA stream including its header.
+///
+/// The output may not exceed `max_len` bytes.
+pub fn decompress(input: &[u8], max_len: usize) -> Result<Vec<u8>, LzmaError> {
+    let header = input.get(..HEADER_LEN).ok_or(LzmaError::Eof)?;
+
+    let mut props = u32::from(header[0]);
+
+    if props >= 9 * 5 * 5 {
+        return Err(LzmaError::Properties);
+    }
+
+    let lc = props % 9;
+    props /= 9;
+    let lp = props % 5;
+    let pb = props / 5;
+
+    let dict_size = u32::from_le_bytes([header[1], header[2], header[3], header[4]]).max(1 << 12);
+
+    let mut size_bytes = [0; 8];
+    size_bytes.copy_from_slice(&header[5..HEADER_LEN]);
+    let unpack_size = u64::from_le_bytes(size_bytes);
+
+    let unpack_size = if unpack_size == UNKNOWN_SIZE {
+        None
+    } else if unpack_size > max_len as u64 {
+        return Err(LzmaError::TooLong);
+    } else {
+        Some(unpack_size as usize)
+    };
+
+    let mut decoder = Decoder::new(lc, lp, pb, dict_size);
+    let mut rc = RangeDecoder::new(&input[HEADER_LEN..])?;
+    let mut output = Vec::with_capacity(unpack_size.unwrap_or(0));
+
+    decoder.decode(&mut rc, &mut output, unpack_size, max_len)?;
+
+    Ok(output)
+}
+
+struct RangeDecoder<'a> {
+    input: &'a [u8],
+    pos: usize,
+    range: u32,
+    code: u32,
+}
+
+impl<'a> RangeDecoder<'a> {
+    fn new(input: &'a [u8]) -> Result<Self, LzmaError> {
+        let mut rc = Self {
+            input,
+            pos: 0,
+            range: u32::MAX,
+            code: 0,
+        };
+
+        if rc.next_byte()? != 0 {
+            return Err(LzmaError::Data);
+        }
+
+        for _ in 0..4 {
+            rc.code = (rc.code << 8) | u32::from(rc.next_byte()?);
+        }
+
+        if rc.code == rc.range {
+            return Err(LzmaError::Data);
+        }
+
+        Ok(rc)
+    }
+
+    fn next_byte(&mut self) -> Result<u8, LzmaError> {
+        let byte = *self.input.get(self.pos).ok_or(LzmaError::Eof)?;
+        self.pos += 1;
+
+        Ok(byte)
+    }
+
+    fn normalize(&mut self) -> Result<(), LzmaError> {
+        if self.range < TOP_VALUE {
+            self.range <<= 8;
+            self.code = (self.code << 8) | u32::from(self.next_byte()?);
+        }
+
+        Ok(())
+    }
+
+    fn direct_bits(&mut self, num_bits: u32) -> Result<u32, LzmaError> {
+        let mut res = 0_u32;
+
+        for _ in 0..num_bits {
+            self.range >>= 1;
+            self.code = self.code.wrapping_sub(self.range);
+            let t = 0_u32.wrapping_sub(self.code >> 31);
+            self.code = self.code.wrapping_add(self.range & t);
+
+            if self.code == self.range {
+                return Err(LzmaError::Data);
+            }
+
+            self.normalize()?;
+            res = (res << 1).wrapping_add(t.wrapping_add(1));
+        }
+
+        Ok(res)
+    }
+
+    fn bit(&mut self, prob: &mut u16) -> Result<u32, LzmaError> {
+        let bound = (self.range >> NUM_BIT_MODEL_TOTAL_BITS) * u32::from(*prob);
+
+        let bit = if self.code < bound {
+            *prob += (BIT_MODEL_TOTAL - *prob) >> NUM_MOVE_BITS;
+            self.range = bound;
+
+            0
+        } else {
+            *prob -= *prob >> NUM_MOVE_BITS;
+            self.code -= bound;
+            self.range -= bound;
+
+            1
+        };
+
+        self.normalize()?;
+
+        Ok(bit)
+    }
+
+    fn bit_tree(&mut self, probs: &mut [u16], num_bits: u32) -> Result<u32, LzmaError> {
+        let mut m = 1;
+
+        for _ in 0..num_bits {
+            m = (m << 1) + self.bit(&mut probs[m as usize])?;
+        }
+
+        Ok(m - (1 << num_bits))
+    }
+
+    fn bit_tree_reverse(&mut self, probs: &mut [u16], num_bits: u32) -> Result<u32, LzmaError> {
+        let mut m = 1;
+        let mut symbol = 0;
+
+        for i in 0..num_bits {
+            let bit = self.bit(&mut probs[m as usize])?;
+            m = (m << 1) + bit;
+            symbol |= bit << i;
+        }
+
+        Ok(symbol)
+    }
+}
+
+struct LenDecoder {
+    choice: u16,
+    choice2: u16,
+    low: [[u16; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+    mid: [[u16; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+    high: [u16; 1 << 8],
+}
+
+impl LenDecoder {
+    const fn new() -> Self {
+        Self {
+            choice: PROB_INIT,
+            choice2: PROB_INIT,
+            low: [[PROB_INIT; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+            mid: [[PROB_INIT; 1 << 3]; 1 << NUM_POS_BITS_MAX],
+            high: [PROB_INIT; 1 << 8],
+        }
+    }
+
+    fn decode(&mut self, rc: &mut RangeDecoder<'_>, pos_state: usize) -> Result<usize, LzmaError> {
+        let len = if rc.bit(&mut self.choice)? == 0 {
+            rc.bit_tree(&mut self.low[pos_state], 3)?
+        } else if rc.bit(&mut self.choice2)? == 0 {
+            8 + rc.bit_tree(&mut self.mid[pos_state], 3)?
+        } else {
+            16 + rc.bit_tree(&mut self.high, 8)?
+        };
+
+        Ok(len as usize)
+    }
+}
+
+struct Decoder {
+    lc: u32,
+    lp: u32,
+    pb: u32,
+    dict_size: u32,
+    literal_probs: Vec<u16>,
+    pos_slot: [[u16; 1 << 6]; NUM_LEN_TO_POS_STATES],
+    pos_decoders: [u16; 1 + NUM_FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+    align: [u16; 1 << NUM_ALIGN_BITS],
+    is_match: [u16; NUM_STATES << NUM_POS_BITS_MAX],
+    is_rep: [u16; NUM_STATES],
+    is_rep_g0: [u16; NUM_STATES],
+    is_rep_g1: [u16; NUM_STATES],
+    is_rep_g2: [u16; NUM_STATES],
+    is_rep0_long: [u16; NUM_STATES << NUM_POS_BITS_MAX],
+    match_len: LenDecoder,
+    rep_len: LenDecoder,
+}
+
+impl Decoder {
+    fn new(lc: u32, lp: u32, pb: u32, dict_size: u32) -> Self {
+        Self {
+            lc,
+            lp,
+            pb,
+            dict_size,
+            literal_probs: vec![PROB_INIT; 0x300 << (lc + lp)],
+            pos_slot: [[PROB_INIT; 1 << 6]; NUM_LEN_TO_POS_STATES],
+            pos_decoders: [PROB_INIT; 1 + NUM_FULL_DISTANCES - END_POS_MODEL_INDEX as usize],
+            align: [PROB_INIT; 1 << NUM_ALIGN_BITS],
+            is_match: [PROB_INIT; NUM_STATES << NUM_POS_BITS_MAX],
+            is_rep: [PROB_INIT; NUM_STATES],
+            is_rep_g0: [PROB_INIT; NUM_STATES],
+            is_rep_g1: [PROB_INIT; NUM_STATES],
+            is_rep_g2: [PROB_INIT; NUM_STATES],
+            is_rep0_long: [PROB_INIT; NUM_STATES << NUM_POS_BITS_MAX],
+            match_len: LenDecoder::new(),
+            rep_len: LenDecoder::new(),
+        }
+    }
+
+    fn decode(
+        &mut self,
+        rc: &mut RangeDecoder<'_>,
+        output: &mut Vec<u8>,
+        unpack_size: Option<usize>,
+        max_len: usize,
+    ) -> Result<(), LzmaError> {
+        let mut reps = [0_usize; 4];
+        let mut state = 0;
+
+        loop {
+            // The end marker is optional if the size is known
+            if unpack_size == Some(output.len()) {
+                return Ok(());
+            }
+
+            let pos_state = output.len() & ((1 << self.pb) - 1);
+            let state2 = (state << NUM_POS_BITS_MAX) + pos_state;
+
+            if rc.bit(&mut self.is_match[state2])? == 0 {
+                if output.len() == max_len {
+                    return Err(LzmaError::TooLong);
+                }
+
+                self.decode_literal(rc, output, state, reps[0])?;
+
+                state = match state {
+                    0..=3 => 0,
+                    4..=9 => state - 3,
+                    _ => state - 6,
+                };
+
+                continue;
+            }
+
+            let len = if rc.bit(&mut self.is_rep[state])? != 0 {
+                if output.is_empty() {
+                    return Err(LzmaError::Data);
+                }
+
+                if rc.bit(&mut self.is_rep_g0[state])? == 0 {
+                    if rc.bit(&mut self.is_rep0_long[state2])? == 0 {
+                        // Short rep: a single byte at the last distance
+                        state = if state < 7 { 9 } else { 11 };
+                        copy_match(output, reps[0], 1, max_len)?;
+
+                        continue;
+                    }
+                } else {
+                    let dist = if rc.bit(&mut self.is_rep_g1[state])? == 0 {
+                        reps[1]
+                    } else if rc.bit(&mut self.is_rep_g2[state])? == 0 {
+                        let dist = reps[2];
+                        reps[2] = reps[1];
+
+                        dist
+                    } else {
+                        let dist = reps[3];
+                        reps[3] = reps[2];
+                        reps[2] = reps[1];
+
+                        dist
+                    };
+
+                    reps[1] = reps[0];
+                    reps[0] = dist;
+                }
+
+                state = if state < 7 { 8 } else { 11 };
+
+                self.rep_len.decode(rc, pos_state)?
+            } else {
+                reps[3] = reps[2];
+                reps[2] = reps[1];
+                reps[1] = reps[0];
+
+                let len = self.match_len.decode(rc, pos_state)?;
+                state = if state < 7 { 7 } else { 10 };
+
+                let dist = self.decode_distance(rc, len)?;
+
+                if dist == u32::MAX {
+                    // End marker
+                    return match unpack_size {
+                        Some(_) => Err(LzmaError::Size),
+                        None if rc.code == 0 => Ok(()),
+                        None => Err(LzmaError::Data),
+                    };
+                }
+
+                if dist >= self.dict_size {
+                    return Err(LzmaError::Distance);
+                }
+
+                reps[0] = dist as usize;
+
+                len
+            };
+
+            let len = len + MATCH_MIN_LEN;
+
+            if unpack_size.is_some_and(|size| output.len() + len > size) {
+                return Err(LzmaError::Size);
+            }
+
+            copy_match(output, reps[0], len, max_len)?;
+        }
+    }
+
+    fn decode_literal(
+        &mut self,
+        rc: &mut RangeDecoder<'_>,
+        output: &mut Vec<u8>,
+        state: usize,
+        rep0: usize,
+    ) -> Result<(), LzmaError> {
+        let prev_byte = output.last().map_or(0, |&byte| usize::from(byte));
+        let lit_state =
+            ((output.len() & ((1 << self.lp) - 1)) << self.lc) + (prev_byte >> (8 - self.lc));
+        let probs = &mut self.literal_probs[0x300 * lit_state..0x300 * (lit_state + 1)];
+
+        let mut symbol = 1_u32;
+
+        if state >= 7 {
+            let mut match_byte = u32::from(
+                *output
+                    .len()
+                    .checked_sub(rep0 + 1)
+                    .and_then(|idx| output.get(idx))
+                    .ok_or(LzmaError::Distance)?,
+            );
+
+            while symbol < 0x100 {
+                let match_bit = (match_byte >> 7) & 1;
+                match_byte <<= 1;
+                let bit = rc.bit(&mut probs[(((1 + match_bit) << 8) + symbol) as usize])?;
+                symbol = (symbol << 1) | bit;
+
+                if match_bit != bit {
+                    break;
+                }
+            }
+        }
+
+        while symbol < 0x100 {
+            symbol = (symbol << 1) | rc.bit(&mut probs[symbol as usize])?;
+        }
+
+        output.push((symbol - 0x100) as u8);
+
+        Ok(())
+    }
+
+    fn decode_distance(&mut self, rc: &mut RangeDecoder<'_>, len: usize) -> Result<u32, LzmaError> {
+        let len_state = len.min(NUM_LEN_TO_POS_STATES - 1);
+        let pos_slot = rc.bit_tree(&mut self.pos_slot[len_state], 6)?;
+
+        if pos_slot < 4 {
+            return Ok(pos_slot);
+        }
+
+        let num_direct_bits = (pos_slot >> 1) - 1;
+        let mut dist = (2 | (pos_slot & 1)) << num_direct_bits;
+
+        if pos_slot < END_POS_MODEL_INDEX {
+            let probs = &mut self.pos_decoders[(dist - pos_slot) as usize..];
+            dist += rc.bit_tree_reverse(probs, num_direct_bits)?;
+        } else {
+            dist = dist
+                .wrapping_add(rc.direct_bits(num_direct_bits - NUM_ALIGN_BITS)? << NUM_ALIGN_BITS);
+            dist = dist.wrapping_add(rc.bit_tree_reverse(&mut self.align, NUM_ALIGN_BITS)?);
+        }
+
+        Ok(dist)
+    }
+}
+
+/// Appends `len` bytes starting `rep0 + 1` bytes before the end of `output`.
+fn copy_match(
+    output: &mut Vec<u8>,
+    rep0: usize,
+    len: usize,
+    max_len: usize,
+) -> Result<(), LzmaError> {
+    let Some(start) = output.len().checked_sub(rep0 + 1) else {
+        return Err(LzmaError::Distance);
+    };
+
+    if output.len() + len > max_len {
+        return Err(LzmaError::TooLong);
+    }
+
+    // Source and destination may overlap so bytes are copied one by one
+    for i in start..start + len {
+        output.push(output[i]);
+    }
+
+    Ok(())
+}
+
+/// Properties of [`compress`]'s output: `lc=3`, `lp=0`, `pb=2`.
+const COMPRESS_PROPS: u8 = (2 * 5) * 9 + 3;
+const COMPRESS_DICT_SIZE: u32 = 1 << 16;
+
+/// Encode data as LZMA stream including its header.
+///
+/// Only literals are encoded so the data is not actually compressed but any
+/// LZMA decoder can read the output.
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    const LC: u32 = 3;
+    const POS_MASK: usize = (1 << 2) - 1;
+
+    let mut output = Vec::with_capacity(HEADER_LEN + data.len() + data.len() / 8 + 8);
+    output.push(COMPRESS_PROPS);
+    output.extend_from_slice(&COMPRESS_DICT_SIZE.to_le_bytes());
+    output.extend_from_slice(&(data.len() as u64).to_le_bytes());
+
+    let mut rc = RangeEncoder::new(output);
+
+    // The state remains zero after literals so only its first row is needed
+    let mut is_match = [PROB_INIT; NUM_POS_BITS_MAX];
+    let mut literal_probs = vec![PROB_INIT; 0x300 << LC];
+    let mut prev_byte = 0;
+
+    for (pos, &byte) in data.iter().enumerate() {
+        rc.encode_bit(&mut is_match[pos & POS_MASK], 0);
+
+        let probs = &mut literal_probs[0x300 * usize::from(prev_byte >> (8 - LC))..];
+        let mut symbol = 1;
+
+        for i in (0..8).rev() {
+            let bit = u32::from(byte >> i) & 1;
+            rc.encode_bit(&mut probs[symbol], bit);
+            symbol = (symbol << 1) | bit as usize;
+        }
+
+        prev_byte = byte;
+    }
+
+    rc.finish()
+}
+
+struct RangeEncoder {
+    output: Vec<u8>,
+    low: u64,
+    range: u32,
+    cache: u8,
+    cache_size: u64,
+}
+
+impl RangeEncoder {
+    const fn new(output: Vec<u8>) -> Self {
+        Self {
+            output,
+            low: 0,
+            range: u32::MAX,
+            cache: 0,
+            cache_size: 1,
+        }
+    }
+
+    fn encode_bit(&mut self, prob: &mut u16, bit: u32) {
+        let bound = (self.range >> NUM_BIT_MODEL_TOTAL_BITS) * u32::from(*prob);
+
+        if bit == 0 {
+            self.range = bound;
+            *prob += (BIT_MODEL_TOTAL - *prob) >> NUM_MOVE_BITS;
+        } else {
+            self.low += u64::from(bound);
+            self.range -= bound;
+            *prob -= *prob >> NUM_MOVE_BITS;
+        }
+
+        while self.range < TOP_VALUE {
+            self.range <<= 8;
+            self.shift_low();
+        }
+    }
+
+    fn shift_low(&mut self) {
+        if self.low < 0xFF00_0000 || self.low > u64::from(u32::MAX) {
+            let carry = (self.low >> 32) as u8;
+            let mut byte = self.cache;
+
+            while self.cache_size > 0 {
+                self.output.push(byte.wrapping_add(carry));
+                byte = 0xFF;
+                self.cache_size -= 1;
+            }
+
+            self.cache = (self.low >> 24) as u8;
+        }
+
+        self.cache_size += 1;
+        self.low = (self.low & 0x00FF_FFFF) << 8;
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        for _ in 0..5 {
+            self.shift_low();
+        }
+
+        self.output
+    }
+}
+
+/// Error when decompressing an LZMA stream.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LzmaError {
+    Data,
+    Distance,
+    Eof,
+    Properties,
+    Size,
+    TooLong,
+}
+
+impl error::Error for LzmaError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        None
+    }
+}
+
+impl fmt::Display for LzmaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Data => "corrupted data",
+            Self::Distance => "distance too far back",
+            Self::Eof => "unexpected end of data",
+            Self::Properties => "invalid properties",
+            Self::Size => "decompressed data did not match the expected size",
+            Self::TooLong => "decompressed data exceeds maximum length",
+        };
+
+        f.write_str(msg)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Created through Python's `lzma.compress(data, format=lzma.FORMAT_ALONE)`
+    // which uses an end marker instead of a known size.
+    const OSU: [u8; 30] = [
+        0x5D, 0x00, 0x00, 0x80, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0x37,
+        0x9C, 0xCA, 0xFD, 0x5E, 0x25, 0xDA, 0xCB, 0x69, 0x6F, 0xFF, 0xFF, 0xF7, 0x49, 0x00, 0x00,
+    ];
+
+    #[test]
+    fn compress_roundtrip() {
+        let data: Vec<u8> = (0..5000_u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .chain(*b"0|256|-500|0,-1|256|-500|0,")
+            .collect();
+
+        let compressed = compress(&data);
+        assert_eq!(decompress(&compressed, data.len()).unwrap(), data);
+
+        assert_eq!(decompress(&compress(&[]), 0).unwrap(), []);
+    }
+
+    #[test]
+    fn end_marker() {
+        assert_eq!(decompress(&OSU, 64).unwrap(), b"osu! osu! osu! osu!");
+        assert_eq!(decompress(&OSU, 4), Err(LzmaError::TooLong));
+    }
+
+    #[test]
+    fn known_size() {
+        let mut input = OSU;
+        input[5..13].copy_from_slice(&19_u64.to_le_bytes());
+
+        assert_eq!(decompress(&input, 64).unwrap(), b"osu! osu! osu! osu!");
+        assert_eq!(decompress(&input, 4), Err(LzmaError::TooLong));
+    }
+
+    #[test]
+    fn truncated() {
+        assert_eq!(decompress(&OSU[..20], 64), Err(LzmaError::Eof));
+        assert_eq!(decompress(&OSU[..8], 64), Err(LzmaError::Eof));
+    }
+}
@@ -3,8 +3,15 @@ use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
 };
 
-use crate::{model::mode::IGameMode, Beatmap};
+use crate::{
+    model::mode::{ConvertError, IGameMode},
+    Beatmap, Difficulty,
+};
 
+#[allow(
+    clippy::large_enum_variant,
+    reason = "attributes are generic and generally not much smaller than a map"
+)]
 pub enum MapOrAttrs<'map, M: IGameMode> {
     Map(Cow<'map, Beatmap>),
     Attrs(M::DifficultyAttributes),
@@ -21,6 +28,22 @@ impl<M: IGameMode> MapOrAttrs<'_, M> {
 
         attrs
     }
+
+    /// Return the difficulty attributes, calculating and storing them first
+    /// if `self` is still a map so that subsequent calls reuse them.
+    pub fn difficulty_attrs(
+        &mut self,
+        difficulty: &Difficulty,
+    ) -> Result<&M::DifficultyAttributes, ConvertError> {
+        match self {
+            Self::Map(map) => {
+                let attrs = difficulty.calculate_for_mode::<M>(map)?;
+
+                Ok(self.insert_attrs(attrs))
+            }
+            Self::Attrs(attrs) => Ok(attrs),
+        }
+    }
 }
 
 impl<M> Clone for MapOrAttrs<'_, M>
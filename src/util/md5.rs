@@ -0,0 +1,254 @@
+//! MD5 message-digest algorithm as specified in [RFC 1321].
+//!
+//! [RFC 1321]: https://www.rfc-editor.org/rfc/rfc1321
+
+const BLOCK_LEN: usize = 64;
+
+const INIT_STATE: [u32; 4] = [0x6745_2301, 0xEFCD_AB89, 0x98BA_DCFE, 0x1032_5476];
+
+const SHIFTS: [u32; 64] = [
+    7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 7, 12, 17, 22, 5, 9, 14, 20, 5, 9, 14, 20, 5, 9,
+    14, 20, 5, 9, 14, 20, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 4, 11, 16, 23, 6, 10, 15,
+    21, 6, 10, 15, 21, 6, 10, 15, 21, 6, 10, 15, 21,
+];
+
+/// `floor(abs(sin(i + 1)) * 2^32)`
+const CONSTANTS: [u32; 64] = [
+    0xD76A_A478,
+    0xE8C7_B756,
+    0x2420_70DB,
+    0xC1BD_CEEE,
+    0xF57C_0FAF,
+    0x4787_C62A,
+    0xA830_4613,
+    0xFD46_9501,
+    0x6980_98D8,
+    0x8B44_F7AF,
+    0xFFFF_5BB1,
+    0x895C_D7BE,
+    0x6B90_1122,
+    0xFD98_7193,
+    0xA679_438E,
+    0x49B4_0821,
+    0xF61E_2562,
+    0xC040_B340,
+    0x265E_5A51,
+    0xE9B6_C7AA,
+    0xD62F_105D,
+    0x0244_1453,
+    0xD8A1_E681,
+    0xE7D3_FBC8,
+    0x21E1_CDE6,
+    0xC337_07D6,
+    0xF4D5_0D87,
+    0x455A_14ED,
+    0xA9E3_E905,
+    0xFCEF_A3F8,
+    0x676F_02D9,
+    0x8D2A_4C8A,
+    0xFFFA_3942,
+    0x8771_F681,
+    0x6D9D_6122,
+    0xFDE5_380C,
+    0xA4BE_EA44,
+    0x4BDE_CFA9,
+    0xF6BB_4B60,
+    0xBEBF_BC70,
+    0x289B_7EC6,
+    0xEAA1_27FA,
+    0xD4EF_3085,
+    0x0488_1D05,
+    0xD9D4_D039,
+    0xE6DB_99E5,
+    0x1FA2_7CF8,
+    0xC4AC_5665,
+    0xF429_2244,
+    0x432A_FF97,
+    0xAB94_23A7,
+    0xFC93_A039,
+    0x655B_59C3,
+    0x8F0C_CC92,
+    0xFFEF_F47D,
+    0x8584_5DD1,
+    0x6FA8_7E4F,
+    0xFE2C_E6E0,
+    0xA301_4314,
+    0x4E08_11A1,
+    0xF753_7E82,
+    0xBD3A_F235,
+    0x2AD7_D2BB,
+    0xEB86_D391,
+];
+
+/// Incremental MD5 hasher.
+pub struct Md5 {
+    state: [u32; 4],
+    buf: [u8; BLOCK_LEN],
+    buf_len: usize,
+    total_len: u64,
+}
+
+impl Md5 {
+    pub const fn new() -> Self {
+        Self {
+            state: INIT_STATE,
+            buf: [0; BLOCK_LEN],
+            buf_len: 0,
+            total_len: 0,
+        }
+    }
+
+    /// Hash all bytes at once.
+    pub fn digest(bytes: &[u8]) -> [u8; 16] {
+        let mut md5 = Self::new();
+        md5.update(bytes);
+
+        md5.finalize()
+    }
+
+    pub fn update(&mut self, mut bytes: &[u8]) {
+        self.total_len = self.total_len.wrapping_add(bytes.len() as u64);
+
+        if self.buf_len > 0 {
+            let n = bytes.len().min(BLOCK_LEN - self.buf_len);
+            self.buf[self.buf_len..self.buf_len + n].copy_from_slice(&bytes[..n]);
+            self.buf_len += n;
+            bytes = &bytes[n..];
+
+            if self.buf_len < BLOCK_LEN {
+                return;
+            }
+
+            let block = self.buf;
+            self.process(&block);
+            self.buf_len = 0;
+        }
+
+        let mut blocks = bytes.chunks_exact(BLOCK_LEN);
+
+        for block in blocks.by_ref() {
+            self.process(block.try_into().expect("block of 64 bytes"));
+        }
+
+        let rest = blocks.remainder();
+        self.buf[..rest.len()].copy_from_slice(rest);
+        self.buf_len = rest.len();
+    }
+
+    pub fn finalize(mut self) -> [u8; 16] {
+        let bit_len = self.total_len.wrapping_mul(8);
+
+        // Pad with a single set bit followed by zeros until 8 bytes are left
+        // in the block, then append the length in bits.
+        let padding_len = if self.buf_len < BLOCK_LEN - 8 {
+            BLOCK_LEN - 8 - self.buf_len
+        } else {
+            2 * BLOCK_LEN - 8 - self.buf_len
+        };
+
+        let mut padding = [0; BLOCK_LEN + 8];
+        padding[0] = 0x80;
+        padding[padding_len..padding_len + 8].copy_from_slice(&bit_len.to_le_bytes());
+
+        // Don't count the padding towards the total length
+        let total_len = self.total_len;
+        self.update(&padding[..padding_len + 8]);
+        self.total_len = total_len;
+
+        let mut digest = [0; 16];
+
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+
+        digest
+    }
+
+    #[allow(
+        clippy::many_single_char_names,
+        reason = "naming as in the specification"
+    )]
+    fn process(&mut self, block: &[u8; BLOCK_LEN]) {
+        let mut words = [0; 16];
+
+        for (word, chunk) in words.iter_mut().zip(block.chunks_exact(4)) {
+            *word = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        }
+
+        let [mut a, mut b, mut c, mut d] = self.state;
+
+        for i in 0..64 {
+            let (f, g) = match i / 16 {
+                0 => ((b & c) | (!b & d), i),
+                1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+                2 => (b ^ c ^ d, (3 * i + 5) % 16),
+                _ => (c ^ (b | !d), (7 * i) % 16),
+            };
+
+            let f = f
+                .wrapping_add(a)
+                .wrapping_add(CONSTANTS[i])
+                .wrapping_add(words[g]);
+
+            a = d;
+            d = c;
+            c = b;
+            b = b.wrapping_add(f.rotate_left(SHIFTS[i]));
+        }
+
+        self.state[0] = self.state[0].wrapping_add(a);
+        self.state[1] = self.state[1].wrapping_add(b);
+        self.state[2] = self.state[2].wrapping_add(c);
+        self.state[3] = self.state[3].wrapping_add(d);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::model::beatmap::Md5Hash;
+
+    use super::*;
+
+    fn hex(digest: [u8; 16]) -> String {
+        Md5Hash(digest).to_string()
+    }
+
+    #[test]
+    fn rfc_test_suite() {
+        let cases = [
+            ("", "d41d8cd98f00b204e9800998ecf8427e"),
+            ("a", "0cc175b9c0f1b6a831c399e269772661"),
+            ("abc", "900150983cd24fb0d6963f7d28e17f72"),
+            ("message digest", "f96b697d7cb7938d525a2f31aaf161d0"),
+            (
+                "abcdefghijklmnopqrstuvwxyz",
+                "c3fcd3d76192e4007dfb496cca67e13b",
+            ),
+            (
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "d174ab98d277d9f5a5611c2c9f419d9f",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "57edf4a22be3c955ac49da2e2107b67a",
+            ),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(hex(Md5::digest(input.as_bytes())), expected, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn incremental() {
+        let input: Vec<u8> = (0..=255).cycle().take(1000).collect();
+        let expected = Md5::digest(&input);
+
+        for chunk_len in [1, 7, 63, 64, 65, 200] {
+            let mut md5 = Md5::new();
+            input.chunks(chunk_len).for_each(|chunk| md5.update(chunk));
+
+            assert_eq!(md5.finalize(), expected, "chunk length {chunk_len}");
+        }
+    }
+}
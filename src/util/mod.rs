@@ -1,10 +1,15 @@
 pub mod difficulty;
 pub mod float_ext;
 pub mod hint;
+pub mod inflate;
 pub mod interval_grouping;
+pub mod json;
 pub mod limited_queue;
+pub mod lzma;
 pub mod map_or_attrs;
+pub mod md5;
 pub mod random;
+pub mod solve;
 pub mod sort;
 pub mod special_functions;
 pub mod strains_vec;
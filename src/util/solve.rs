@@ -0,0 +1,94 @@
+/// Amount of bisection steps when searching for an accuracy which results in
+/// a precision of roughly `0.00001%`.
+const ACCURACY_ITERATIONS: usize = 24;
+
+/// Finds the smallest accuracy between `0.0` and `100.0` for which `pp`
+/// reaches `target_pp`.
+///
+/// Assumes that pp does not decrease with higher accuracy. Returns `None` if
+/// even `100.0` does not reach the target.
+pub fn min_accuracy<E>(
+    target_pp: f64,
+    mut pp: impl FnMut(f64) -> Result<f64, E>,
+) -> Result<Option<f64>, E> {
+    if pp(100.0)? < target_pp {
+        return Ok(None);
+    } else if pp(0.0)? >= target_pp {
+        return Ok(Some(0.0));
+    }
+
+    let mut low = 0.0;
+    let mut high = 100.0;
+
+    for _ in 0..ACCURACY_ITERATIONS {
+        let mid = f64::midpoint(low, high);
+
+        if pp(mid)? >= target_pp {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+
+    Ok(Some(high))
+}
+
+/// Finds the smallest value between `0` and `max` for which `pp` reaches
+/// `target_pp`.
+///
+/// Assumes that pp does not decrease with higher values. Returns `None` if
+/// even `max` does not reach the target.
+pub fn min_value<E>(
+    target_pp: f64,
+    max: u32,
+    mut pp: impl FnMut(u32) -> Result<f64, E>,
+) -> Result<Option<u32>, E> {
+    if pp(max)? < target_pp {
+        return Ok(None);
+    }
+
+    let mut low = 0;
+    let mut high = max;
+
+    while low < high {
+        let mid = low + (high - low) / 2;
+
+        if pp(mid)? >= target_pp {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+
+    Ok(Some(high))
+}
+
+/// Finds the largest value between `0` and `max` for which `pp` still
+/// reaches `target_pp`.
+///
+/// Assumes that pp does not increase with higher values. Returns `None` if
+/// not even `0` reaches the target.
+pub fn max_value<E>(
+    target_pp: f64,
+    max: u32,
+    mut pp: impl FnMut(u32) -> Result<f64, E>,
+) -> Result<Option<u32>, E> {
+    if pp(0)? < target_pp {
+        return Ok(None);
+    }
+
+    let mut low = 0;
+    let mut high = max;
+
+    while low < high {
+        let mid = low + (high - low).div_ceil(2);
+
+        if pp(mid)? >= target_pp {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(Some(low))
+}
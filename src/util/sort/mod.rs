@@ -1,6 +1,10 @@
 use std::cmp::Ordering;
 
-pub use self::{csharp::sort as csharp, osu_legacy::sort as osu_legacy, tandem::TandemSorter};
+pub use self::{
+    csharp::sort as csharp,
+    osu_legacy::{sort as osu_legacy, sort_by_key as osu_legacy_by_key},
+    tandem::TandemSorter,
+};
 
 mod csharp;
 mod osu_legacy;
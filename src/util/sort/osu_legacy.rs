@@ -8,19 +8,32 @@ const QUICK_SORT_DEPTH_THRESHOLD: usize = 32;
 ///
 /// <https://github.com/ppy/osu/blob/e669e28dc9b6d79d82a36053e6a279de8dafddd1/osu.Game.Rulesets.Mania/MathUtils/LegacySortHelper.cs#L19>
 pub fn sort(keys: &mut [HitObject]) {
+    sort_by_key(keys, |h| h.start_time);
+}
+
+/// osu!'s legacy sorting algorithm for any type that provides a time.
+pub fn sort_by_key<T, F>(keys: &mut [T], key: F)
+where
+    F: Fn(&T) -> f64,
+{
     if keys.len() < 2 {
         return;
     }
 
-    depth_limited_quick_sort(keys, 0, keys.len() - 1, QUICK_SORT_DEPTH_THRESHOLD);
+    depth_limited_quick_sort(keys, 0, keys.len() - 1, QUICK_SORT_DEPTH_THRESHOLD, &key);
 }
 
-fn depth_limited_quick_sort(
-    keys: &mut [HitObject],
+fn depth_limited_quick_sort<T, F>(
+    keys: &mut [T],
     mut left: usize,
     mut right: usize,
     mut depth_limit: usize,
-) {
+    key: &F,
+) where
+    F: Fn(&T) -> f64,
+{
+    let cmp = |a: &T, b: &T| key(a).total_cmp(&key(b));
+
     loop {
         if depth_limit == 0 {
             super::heap_sort(keys, left, right, &cmp);
@@ -38,11 +51,11 @@ fn depth_limited_quick_sort(
         super::swap_if_greater(keys, &cmp, mid, j);
 
         loop {
-            while keys[i] < keys[mid] {
+            while key(&keys[i]) < key(&keys[mid]) {
                 i += 1;
             }
 
-            while keys[mid] < keys[j] {
+            while key(&keys[mid]) < key(&keys[j]) {
                 j -= 1;
             }
 
@@ -64,13 +77,13 @@ fn depth_limited_quick_sort(
 
         if j.saturating_sub(left) <= right - i {
             if left < j {
-                depth_limited_quick_sort(keys, left, j, depth_limit);
+                depth_limited_quick_sort(keys, left, j, depth_limit, key);
             }
 
             left = i;
         } else {
             if i < right {
-                depth_limited_quick_sort(keys, i, right, depth_limit);
+                depth_limited_quick_sort(keys, i, right, depth_limit, key);
             }
 
             right = j;
@@ -81,7 +94,3 @@ fn depth_limited_quick_sort(
         }
     }
 }
-
-fn cmp(a: &HitObject, b: &HitObject) -> Ordering {
-    a.start_time.total_cmp(&b.start_time)
-}
@@ -3,10 +3,19 @@ pub use self::{mods::*, paths::*};
 
 /// Paths to .osu files
 mod paths {
+    #![allow(unused, reason = "not all test files use all paths")]
+
     pub const OSU: &str = "./resources/2785319.osu";
     pub const TAIKO: &str = "./resources/1028484.osu";
     pub const CATCH: &str = "./resources/2118524.osu";
     pub const MANIA: &str = "./resources/1638954.osu";
+
+    /// Contains `OSU` (deflated) and `TAIKO` (stored) alongside an audio file
+    pub const OSZ: &str = "./resources/mapset.osz";
+
+    /// Replays of a play on `OSU`
+    pub const OSR: &str = "./resources/2785319.osr";
+    pub const OSR_LAZER: &str = "./resources/2785319-lazer.osr";
 }
 
 /// Bit values for mods
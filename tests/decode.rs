@@ -1,4 +1,11 @@
-use rosu_pp::{model::mode::GameMode, Beatmap, GameMods};
+use rosu_pp::{
+    model::{
+        beatmap::{BeatmapSummary, OszError, ParseBeatmapError, Section, StrictDecodeError},
+        hit_object::HitObject,
+        mode::GameMode,
+    },
+    Beatmap, GameMods,
+};
 
 use crate::common::assert_eq_float;
 
@@ -111,3 +118,234 @@ fn empty_mania() {
     let map = Beatmap::from_bytes(&[]).unwrap();
     let _ = map.convert(GameMode::Mania, &GameMods::default());
 }
+
+#[test]
+fn metadata() {
+    let map = Beatmap::from_path(common::OSU).unwrap();
+    let metadata = map.metadata.unwrap();
+
+    assert_eq!(metadata.audio_file, "audio.mp3");
+    assert_eq_float(metadata.audio_lead_in, 0.0);
+    assert_eq!(metadata.preview_time, 25309);
+    assert_eq!(metadata.title, "re[in]flaw");
+    assert_eq!(metadata.artist, "MYUKKE.");
+    assert_eq!(metadata.creator, "captin1");
+    assert_eq!(metadata.version, "toybot's Expert");
+    assert_eq!(metadata.source, "");
+    assert_eq!(metadata.beatmap_id, 2785319);
+    assert_eq!(metadata.beatmap_set_id, 1344871);
+}
+
+#[test]
+fn no_metadata() {
+    let map =
+        Beatmap::from_bytes(b"osu file format v14\n\n[HitObjects]\n256,192,1000,1,0\n").unwrap();
+
+    assert!(map.metadata.is_none());
+    assert_eq!(map.hit_objects.len(), 1);
+}
+
+#[test]
+fn strict() {
+    for path in [common::OSU, common::TAIKO, common::CATCH, common::MANIA] {
+        let decoded = Beatmap::from_path_strict(path).unwrap();
+
+        assert!(
+            decoded.warnings.is_empty(),
+            "{path}: {:?}",
+            decoded.warnings
+        );
+        assert_eq!(decoded.map, Beatmap::from_path(path).unwrap());
+    }
+}
+
+#[test]
+fn strict_warnings() {
+    let content = b"osu file format v14
+
+[Difficulty]
+OverallDifficulty:8
+
+[TimingPoints]
+0,NaN,4,0,0,100,1,0
+
+[HitObjects]
+256,192,1000,1,0
+256,192,abc,1,0
+256,192,2000,1,0,0:0:0:0:
+";
+
+    let decoded = Beatmap::from_bytes_strict(content).unwrap();
+
+    assert_eq!(decoded.map.hit_objects.len(), 2);
+    assert!(decoded.map.timing_points.is_empty());
+
+    let [timing, hit_object] = decoded.warnings.as_slice() else {
+        panic!("expected two warnings, got {:?}", decoded.warnings);
+    };
+
+    assert_eq!(timing.section, Section::TimingPoints);
+    assert_eq!(timing.line, 7);
+    assert!(matches!(
+        timing.error,
+        ParseBeatmapError::TimingControlPointNaN
+    ));
+
+    assert_eq!(hit_object.section, Section::HitObjects);
+    assert_eq!(hit_object.line, 11);
+    assert!(matches!(hit_object.error, ParseBeatmapError::Number(_)));
+}
+
+#[test]
+fn strict_errors() {
+    let missing_version = b"\n\n[HitObjects]\n256,192,1000,1,0\n";
+
+    assert!(matches!(
+        Beatmap::from_bytes_strict(missing_version),
+        Err(StrictDecodeError::FileFormat { line: 3 })
+    ));
+
+    let invalid_difficulty = b"osu file format v14\n\n[Difficulty]\nCircleSize:abc\n";
+
+    let err = Beatmap::from_bytes_strict(invalid_difficulty).unwrap_err();
+
+    let StrictDecodeError::Line { section, line, .. } = err else {
+        panic!("expected line error, got {err:?}");
+    };
+
+    assert_eq!(section, Section::Difficulty);
+    assert_eq!(line, 4);
+    assert_eq!(
+        err.to_string(),
+        "failed to parse line 4 in section Difficulty"
+    );
+}
+
+#[test]
+fn osz() {
+    let decoded = Beatmap::from_osz_path(common::OSZ).unwrap();
+
+    assert!(decoded.errors.is_empty(), "{:?}", decoded.errors);
+
+    let [osu, taiko] = decoded.maps.as_slice() else {
+        panic!("expected two maps, got {}", decoded.maps.len());
+    };
+
+    assert_eq!(osu.file_name, "2785319.osu");
+    assert_eq!(
+        osu.map,
+        Beatmap::from_path_with_checksum(common::OSU).unwrap()
+    );
+
+    assert_eq!(taiko.file_name, "1028484.osu");
+    assert_eq!(
+        taiko.map,
+        Beatmap::from_path_with_checksum(common::TAIKO).unwrap()
+    );
+}
+
+#[test]
+fn osz_corrupted() {
+    let mut bytes = std::fs::read(common::OSZ).unwrap();
+
+    // Flip a byte within the compressed content of the first file
+    bytes[100] ^= 0xFF;
+
+    let decoded = Beatmap::from_osz_bytes(&bytes).unwrap();
+
+    // The corrupted file is skipped while the other one is still decoded
+    let [taiko] = decoded.maps.as_slice() else {
+        panic!("expected one map, got {}", decoded.maps.len());
+    };
+
+    assert_eq!(taiko.file_name, "1028484.osu");
+    assert!(matches!(
+        decoded.errors.as_slice(),
+        [OszError::Inflate { .. } | OszError::Checksum { .. }]
+    ));
+}
+
+#[test]
+fn summary() {
+    for path in [common::OSU, common::TAIKO, common::CATCH, common::MANIA] {
+        let summary = BeatmapSummary::from_path(path).unwrap();
+        let map = Beatmap::from_path(path).unwrap();
+
+        assert_eq!(summary.version, map.version);
+        assert_eq!(summary.mode, map.mode);
+        assert_eq!(summary.ar, map.ar);
+        assert_eq!(summary.cs, map.cs);
+        assert_eq!(summary.hp, map.hp);
+        assert_eq!(summary.od, map.od);
+        assert_eq!(summary.slider_multiplier, map.slider_multiplier);
+        assert_eq!(summary.slider_tick_rate, map.slider_tick_rate);
+        assert_eq!(summary.n_objects() as usize, map.hit_objects.len());
+
+        let count = |f: fn(&HitObject) -> bool| map.hit_objects.iter().filter(|h| f(h)).count();
+
+        assert_eq!(summary.n_circles as usize, count(HitObject::is_circle));
+        assert_eq!(summary.n_sliders as usize, count(HitObject::is_slider));
+        assert_eq!(summary.n_spinners as usize, count(HitObject::is_spinner));
+        assert_eq!(summary.n_holds as usize, count(HitObject::is_hold_note));
+
+        let first = map
+            .hit_objects
+            .iter()
+            .map(|h| h.start_time)
+            .fold(f64::MAX, f64::min);
+        let last = map
+            .hit_objects
+            .iter()
+            .map(|h| h.start_time)
+            .fold(f64::MIN, f64::max);
+
+        assert_eq!(summary.first_object_time, first);
+        assert_eq!(summary.last_object_time, last);
+
+        assert_eq!(summary.upgrade_from_path(path).unwrap(), map);
+    }
+}
+
+#[test]
+fn summary_upgrade_mismatch() {
+    let summary = BeatmapSummary::from_path(common::OSU).unwrap();
+    let bytes = std::fs::read(common::TAIKO).unwrap();
+
+    assert!(summary.upgrade_from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn checksum() {
+    let map = Beatmap::from_path_with_checksum(common::OSU).unwrap();
+    let checksum = map.checksum.unwrap();
+
+    assert_eq!(checksum.to_string(), "3ca9a5e23c9a4e9332f4f6e2a48865f7");
+    assert!(Beatmap::from_path(common::OSU).unwrap().checksum.is_none());
+}
+
+#[test]
+fn fingerprint() {
+    let map = Beatmap::from_path(common::OSU).unwrap();
+    let fingerprint = map.fingerprint();
+
+    // Unaffected by the checksum and metadata
+    let mut with_checksum = Beatmap::from_path_with_checksum(common::OSU).unwrap();
+    with_checksum.metadata = None;
+    assert_eq!(with_checksum.fingerprint(), fingerprint);
+
+    // Unaffected by the file's formatting
+    let encoded = map.encode_to_string().unwrap();
+    let decoded = encoded.parse::<Beatmap>().unwrap();
+    assert_eq!(decoded.fingerprint(), fingerprint);
+
+    let mut modified = map.clone();
+    modified.od += 0.1;
+    assert_ne!(modified.fingerprint(), fingerprint);
+
+    let mut modified = map;
+    modified.hit_objects[100].start_time += 1.0;
+    assert_ne!(modified.fingerprint(), fingerprint);
+
+    let other = Beatmap::from_path(common::TAIKO).unwrap();
+    assert_ne!(other.fingerprint(), fingerprint);
+}
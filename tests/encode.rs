@@ -0,0 +1,106 @@
+use rosu_pp::{
+    model::{
+        beatmap::BeatmapBuilder,
+        control_point::{TimeSignature, TimingPoint},
+        hit_object::{PathControlPoint, PathType, Pos},
+        mode::GameMode,
+    },
+    Beatmap, Difficulty, GameMods,
+};
+
+use crate::common::assert_eq_float;
+
+mod common;
+
+#[track_caller]
+fn roundtrip(path: &str) {
+    let map = Beatmap::from_path(path).unwrap();
+    let encoded = map.encode_to_string().unwrap();
+    let decoded: Beatmap = encoded.parse().unwrap();
+
+    assert_eq!(decoded, map);
+}
+
+#[test]
+fn osu() {
+    roundtrip(common::OSU);
+}
+
+#[test]
+fn taiko() {
+    roundtrip(common::TAIKO);
+}
+
+#[test]
+fn catch() {
+    roundtrip(common::CATCH);
+}
+
+#[test]
+fn mania() {
+    roundtrip(common::MANIA);
+}
+
+#[test]
+fn time_signature() {
+    let mut map = Beatmap::from_path(common::OSU).unwrap();
+    map.timing_points[0].time_signature = TimeSignature::new_simple_triple();
+
+    let encoded = map.encode_to_string().unwrap();
+    let decoded: Beatmap = encoded.parse().unwrap();
+
+    assert_eq!(
+        decoded.timing_points[0].time_signature,
+        TimeSignature::new_simple_triple()
+    );
+    assert_eq!(decoded, map);
+}
+
+#[test]
+fn converted() {
+    let map = Beatmap::from_path(common::OSU).unwrap();
+
+    for mode in [GameMode::Taiko, GameMode::Catch, GameMode::Mania] {
+        let converted = map.convert_ref(mode, &GameMods::default()).unwrap();
+        let encoded = converted.encode_to_string().unwrap();
+        let mut decoded: Beatmap = encoded.parse().unwrap();
+
+        assert_eq!(decoded.mode, mode);
+        assert!(!decoded.is_convert);
+        decoded.is_convert = true;
+        assert_eq!(decoded.hit_objects.len(), converted.hit_objects.len());
+
+        let expected = Difficulty::new().calculate(&converted).stars();
+        let actual = Difficulty::new().calculate(&decoded).stars();
+
+        assert_eq_float(actual, expected);
+    }
+}
+
+#[test]
+fn typed_last_control_point() {
+    let point = |x, path_type| PathControlPoint {
+        pos: Pos::new(x, 0.0),
+        path_type,
+    };
+
+    // The last control point is a red anchor
+    let control_points = vec![
+        point(0.0, Some(PathType::BEZIER)),
+        point(100.0, None),
+        point(200.0, Some(PathType::LINEAR)),
+    ];
+
+    let map = BeatmapBuilder::new()
+        .slider_multiplier(1.0)
+        .timing_point(TimingPoint::new(0.0, 500.0))
+        .slider(Pos::new(0.0, 0.0), 1000.0, control_points, 1, Some(200.0))
+        .build()
+        .unwrap();
+
+    let encoded = map.encode_to_string().unwrap();
+    assert!(encoded.contains(",B|100:0|L|200:0,2,200,"), "{encoded}");
+
+    let decoded: Beatmap = encoded.parse().unwrap();
+    assert_eq!(decoded.hit_objects, map.hit_objects);
+}
@@ -391,6 +391,43 @@ fn convert_mania() {
     };
 }
 
+#[test]
+fn solve_for_pp() {
+    macro_rules! assert_solved {
+        ( $perf:ident: $path:ident ) => {{
+            let map = Beatmap::from_path(common::$path).unwrap();
+            let attrs = $perf::from(&map).calculate().unwrap().difficulty;
+            let perf = move || $perf::from(attrs.clone());
+
+            let target_pp = perf().accuracy(97.0).calculate().unwrap().pp;
+            let acc = perf().min_accuracy_for_pp(target_pp).unwrap().unwrap();
+            assert!((96.5..=97.1).contains(&acc), "{acc}");
+            assert!(perf().accuracy(acc).calculate().unwrap().pp >= target_pp);
+
+            let target_pp = perf().misses(5).calculate().unwrap().pp;
+            assert_eq!(perf().max_misses_for_pp(target_pp).unwrap(), Some(5));
+
+            assert_eq!(perf().min_accuracy_for_pp(1e6).unwrap(), None);
+            assert_eq!(perf().max_misses_for_pp(1e6).unwrap(), None);
+
+            perf
+        }};
+    }
+
+    let osu = assert_solved!(OsuPerformance: OSU);
+    let stable = || osu().lazer(false).accuracy(95.0);
+    let target_pp = stable().combo(200).calculate().unwrap().pp;
+    assert_eq!(stable().min_combo_for_pp(target_pp).unwrap(), Some(200));
+
+    let _ = assert_solved!(TaikoPerformance: TAIKO);
+
+    let catch = assert_solved!(CatchPerformance: CATCH);
+    let target_pp = catch().combo(200).calculate().unwrap().pp;
+    assert_eq!(catch().min_combo_for_pp(target_pp).unwrap(), Some(200));
+
+    let _ = assert_solved!(ManiaPerformance: MANIA);
+}
+
 fn run<A>(actual: &A, expected: &A, mods: u32)
 where
     A: AssertEq,
@@ -0,0 +1,134 @@
+use rosu_pp::{
+    model::{mode::GameMode, replay::Replay},
+    osu::{OsuPerformance, OsuReplayJudge},
+    taiko::TaikoPerformance,
+    Beatmap, GameMods, Performance,
+};
+
+use crate::common::assert_eq_float;
+
+mod common;
+
+#[test]
+fn stable() {
+    let replay = Replay::from_path(common::OSR).unwrap();
+    let map = Beatmap::from_path_with_checksum(common::OSU).unwrap();
+
+    assert_eq!(replay.mode, GameMode::Osu);
+    assert_eq!(replay.version, 20_240_101);
+    assert_eq!(replay.player_name, "Player");
+    assert_eq!(replay.beatmap_checksum(), map.checksum);
+    assert_eq!(replay.score, 12_345_678);
+    assert_eq!(replay.online_score_id, 4_567_890_123);
+    assert_eq!(replay.frames.len(), 40);
+    assert_eq!(replay.seed, Some(7364));
+    assert!(!replay.is_lazer());
+    assert!(
+        matches!(replay.mods(), GameMods::Legacy(mods) if mods.bits() == common::HD | common::DT)
+    );
+
+    let state = replay.score_state();
+    assert_eq!(state.n300, 550);
+    assert_eq!(state.n100, 40);
+    assert_eq!(state.n50, 3);
+    assert_eq!(state.misses, 2);
+    assert_eq!(state.max_combo, 1234);
+
+    let attrs = Performance::new(&map)
+        .mods(replay.mods())
+        .state(state)
+        .calculate();
+
+    assert!(attrs.pp() > 0.0);
+}
+
+#[test]
+fn lazer() {
+    let replay = Replay::from_path(common::OSR_LAZER).unwrap();
+    let lazer = replay.lazer.as_ref().unwrap();
+
+    assert!(replay.is_lazer());
+    assert_eq!(lazer.client_version.as_deref(), Some("2024.1009.1"));
+    assert_eq!(lazer.rank.as_deref(), Some("A"));
+    assert_eq!(lazer.user_id, Some(2));
+    assert_eq!(lazer.total_score_without_mods, Some(654_321));
+    assert_eq!(lazer.statistics.great, 550);
+    assert_eq!(lazer.statistics.ignore_hit, 10);
+
+    assert_eq_float(replay.mods().clock_rate(), 1.3);
+
+    let state = replay.score_state();
+    assert_eq!(state.osu_large_tick_hits, 180);
+    assert_eq!(state.slider_end_hits, 170);
+}
+
+#[track_caller]
+fn roundtrip(path: &str) {
+    let replay = Replay::from_path(path).unwrap();
+
+    let mut bytes = Vec::new();
+    replay.encode(&mut bytes).unwrap();
+
+    assert_eq!(Replay::from_bytes(&bytes).unwrap(), replay);
+}
+
+#[test]
+fn encode_stable() {
+    roundtrip(common::OSR);
+}
+
+#[test]
+fn encode_lazer() {
+    roundtrip(common::OSR_LAZER);
+}
+
+#[test]
+fn judge() {
+    let replay = Replay::from_path(common::OSR).unwrap();
+    let map = Beatmap::from_path(common::OSU).unwrap();
+
+    let judged = OsuReplayJudge::new(&map)
+        .mods(replay.mods())
+        .lazer(replay.is_lazer())
+        .judge(&replay.frames)
+        .unwrap();
+
+    assert_eq!(judged.objects.len(), map.hit_objects.len());
+    assert_eq!(judged.state.total_hits() as usize, map.hit_objects.len());
+
+    let taiko = Beatmap::from_path(common::TAIKO).unwrap();
+    assert!(OsuReplayJudge::new(&taiko).judge(&replay.frames).is_err());
+}
+
+#[test]
+fn measured_deviation() {
+    let replay = Replay::from_path(common::OSR).unwrap();
+    let map = Beatmap::from_path(common::OSU).unwrap();
+
+    let stats = replay.hit_error_statistics(&map).unwrap().unwrap();
+
+    // The fixture's 40 frames hit two circles, 2ms early and 78ms late, which
+    // are adjusted to the DT clock rate
+    assert_eq!(stats.hit_errors.len(), 2);
+    assert_eq_float(stats.hit_errors[0], -2.0 / 1.5);
+    assert_eq_float(stats.hit_errors[1], 78.0 / 1.5);
+    assert_eq_float(stats.mean, 38.0 / 1.5);
+    assert_eq_float(stats.unstable_rate, 400.0 / 1.5);
+    assert_eq_float(stats.deviation(), 40.0 / 1.5);
+
+    let attrs = OsuPerformance::new(&map)
+        .speed_deviation(12.0)
+        .calculate()
+        .unwrap();
+
+    assert_eq!(attrs.speed_deviation, Some(12.0));
+
+    let taiko = Beatmap::from_path(common::TAIKO).unwrap();
+
+    let attrs = TaikoPerformance::new(&taiko)
+        .unstable_rate(123.0)
+        .calculate()
+        .unwrap();
+
+    assert_eq!(attrs.estimated_unstable_rate, Some(123.0));
+}
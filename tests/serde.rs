@@ -0,0 +1,90 @@
+#![cfg(feature = "serde")]
+
+use rosu_pp::{
+    any::{
+        DifficultyAttributes, HitResultPriority, InspectDifficulty, ModsDependent,
+        PerformanceAttributes, ScoreState, Strains,
+    },
+    catch::{CatchDifficultyAttributes, CatchPerformanceAttributes, CatchScoreState, CatchStrains},
+    mania::{ManiaDifficultyAttributes, ManiaPerformanceAttributes, ManiaScoreState, ManiaStrains},
+    model::beatmap::{BeatmapAttributes, DensityLimit, HitWindows, SuspicionConfig},
+    osu::{
+        OsuDifficultyAttributes, OsuPerformanceAttributes, OsuScoreOrigin, OsuScoreState,
+        OsuStrains,
+    },
+    taiko::{TaikoDifficultyAttributes, TaikoPerformanceAttributes, TaikoScoreState, TaikoStrains},
+    Beatmap, GameMods,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+fn assert_serde<T: Serialize + DeserializeOwned>() {}
+
+#[test]
+fn implemented() {
+    assert_serde::<DifficultyAttributes>();
+    assert_serde::<PerformanceAttributes>();
+    assert_serde::<ScoreState>();
+    assert_serde::<Strains>();
+    assert_serde::<InspectDifficulty>();
+    assert_serde::<ModsDependent>();
+    assert_serde::<HitResultPriority>();
+
+    assert_serde::<OsuDifficultyAttributes>();
+    assert_serde::<OsuPerformanceAttributes>();
+    assert_serde::<OsuScoreState>();
+    assert_serde::<OsuScoreOrigin>();
+    assert_serde::<OsuStrains>();
+
+    assert_serde::<TaikoDifficultyAttributes>();
+    assert_serde::<TaikoPerformanceAttributes>();
+    assert_serde::<TaikoScoreState>();
+    assert_serde::<TaikoStrains>();
+
+    assert_serde::<CatchDifficultyAttributes>();
+    assert_serde::<CatchPerformanceAttributes>();
+    assert_serde::<CatchScoreState>();
+    assert_serde::<CatchStrains>();
+
+    assert_serde::<ManiaDifficultyAttributes>();
+    assert_serde::<ManiaPerformanceAttributes>();
+    assert_serde::<ManiaScoreState>();
+    assert_serde::<ManiaStrains>();
+
+    assert_serde::<BeatmapAttributes>();
+    assert_serde::<HitWindows>();
+    assert_serde::<SuspicionConfig>();
+    assert_serde::<DensityLimit>();
+    assert_serde::<GameMods>();
+    assert_serde::<Beatmap>();
+}
+
+fn roundtrip<T: Serialize + DeserializeOwned>(value: &T) -> T {
+    let json = serde_json::to_string(value).unwrap();
+
+    serde_json::from_str(&json).unwrap()
+}
+
+#[test]
+fn beatmap_roundtrip() {
+    let map = Beatmap::from_path_with_checksum("./resources/2785319.osu").unwrap();
+
+    assert_eq!(roundtrip(&map), map);
+}
+
+#[test]
+fn performance_attributes_roundtrip() {
+    let map = Beatmap::from_path("./resources/2785319.osu").unwrap();
+
+    let attrs = rosu_pp::Performance::new(&map)
+        .mods(8 + 64)
+        .accuracy(98.5)
+        .misses(2)
+        .calculate();
+
+    let PerformanceAttributes::Osu(ref osu) = attrs else {
+        panic!("expected osu attributes");
+    };
+
+    assert_eq!(&roundtrip::<OsuPerformanceAttributes>(osu), osu);
+    assert_eq!(roundtrip(&attrs), attrs);
+}